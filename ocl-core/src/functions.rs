@@ -37,7 +37,11 @@ use crate::ffi::{self, cl_bool, cl_int, cl_uint, cl_platform_id, cl_device_id, c
     cl_mem_flags, cl_mem_object_type, cl_buffer_create_type, cl_event, cl_program,
     cl_addressing_mode, cl_filter_mode, cl_command_queue_info, cl_command_queue, cl_image_info,
     cl_sampler, cl_sampler_info, cl_program_info, cl_kernel_info, cl_kernel_arg_info,
-    cl_kernel_work_group_info, cl_event_info, cl_profiling_info};
+    cl_kernel_work_group_info, cl_event_info, cl_profiling_info, cl_device_partition_property};
+#[cfg(feature = "opencl_version_2_0")]
+use crate::ffi::cl_queue_properties;
+#[cfg(feature = "opencl_version_2_1")]
+use crate::ffi::{cl_ulong, cl_kernel_sub_group_info};
 
 use crate::error::{Error as OclCoreError, Result as OclCoreResult};
 
@@ -47,13 +51,15 @@ use crate::{OclPrm, PlatformId, DeviceId, Context, ContextProperties, ContextInf
     CommandExecutionStatus, AddressingMode, FilterMode, PlatformInfo, PlatformInfoResult,
     DeviceInfo, DeviceInfoResult, CommandQueueInfo, CommandQueueInfoResult, MemInfo, MemInfoResult,
     ImageInfo, ImageInfoResult, SamplerInfo, SamplerInfoResult, ProgramInfo, ProgramInfoResult,
-    ProgramBuildInfo, ProgramBuildInfoResult, KernelInfo, KernelInfoResult, KernelArgInfo,
-    KernelArgInfoResult, KernelWorkGroupInfo, KernelWorkGroupInfoResult, ClEventPtrRef,
-    ClWaitListPtr, EventInfo, EventInfoResult, ProfilingInfo, ProfilingInfoResult,
+    ProgramBuildInfo, ProgramBuildInfoResult, ProgramBuildStatus, KernelInfo, KernelInfoResult,
+    KernelArgInfo, KernelArgInfoResult, KernelWorkGroupInfo, KernelWorkGroupInfoResult,
+    ClEventPtrRef, ClWaitListPtr, EventInfo, EventInfoResult, ProfilingInfo, ProfilingInfoResult,
     CreateContextCallbackFn, UserDataPtr, ClPlatformIdPtr, ClDeviceIdPtr, ClContextPtr,
     EventCallbackFn, BuildProgramCallbackFn, MemMigrationFlags, MapFlags, BufferRegion,
     BufferCreateType, OpenclVersion, ClVersions, Status, CommandQueueProperties, MemMap, AsMem,
     MemCmdRw, MemCmdAll, Event, ImageFormatParseResult};
+#[cfg(feature = "opencl_version_2_1")]
+use crate::KernelSubGroupInfo;
 
 #[cfg(not(feature="opencl_vendor_mesa"))]
 use crate::{GlContextInfo, GlContextInfoResult};
@@ -75,6 +81,24 @@ const PLATFORM_IDS_ATTEMPT_COUNT: u64 = 5;
 //============================================================================
 
 
+/// Sets the user event pointed to by `user_data` to complete
+/// (`CommandExecutionStatus::Complete`) once a program build has finished.
+///
+/// Intended for use with `::build_program_async`. `user_data` must be a
+/// `cl_event` which has not yet had its destructor run (`::release_event`).
+/// The build status is not checked.
+///
+pub extern "C" fn _complete_build_user_event(_program: cl_program, user_data: *mut c_void) {
+    if !user_data.is_null() {
+        unsafe {
+            let user_event = Event::from_raw(user_data as cl_event);
+
+            // NOTE: Panicking here would likely crash the calling module:
+            let _ = crate::set_user_event_status(&user_event, CommandExecutionStatus::Complete);
+        }
+    }
+}
+
 /// Don't be a dummy. Buckle your `_dummy_callback`.
 pub extern "C" fn _dummy_event_callback(_: ffi::cl_event, _: i32, _: *mut c_void) {}
 
@@ -132,11 +156,17 @@ static SDK_DOCS_URL_PRE: &'static str = "https://www.khronos.org/registry/cl/sdk
 static SDK_DOCS_URL_SUF: &'static str = ".html#errors";
 
 
+/// Optional details of an `ApiError`, boxed to keep errors small.
+struct ApiErrorInfo {
+    fn_info: Option<String>,
+    device_name: Option<String>,
+}
+
 /// An OpenCL API error.
 pub struct ApiError {
     status: Status,
     fn_name: &'static str,
-    fn_info: Option<String>,
+    info: Box<ApiErrorInfo>,
 }
 
 impl ApiError {
@@ -152,34 +182,46 @@ impl ApiError {
         ApiError {
             status,
             fn_name,
-            fn_info,
+            info: Box::new(ApiErrorInfo { fn_info, device_name: None }),
         }
     }
 
     pub fn status(&self) -> Status {
         self.status
     }
+
+    /// Returns the name of the device on which the failed command was
+    /// issued, if known.
+    pub fn device_name(&self) -> Option<&str> {
+        self.info.device_name.as_deref()
+    }
 }
 
 impl Fail for ApiError {}
 
 impl fmt::Display for ApiError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let fn_info_string = if let Some(ref fn_info) = self.fn_info {
+        let fn_info_string = if let Some(ref fn_info) = self.info.fn_info {
             format!("(\"{}\")", fn_info)
         } else {
             String::with_capacity(0)
         };
 
+        let device_string = if let Some(ref device_name) = self.info.device_name {
+            format!(" on '{}'", device_name)
+        } else {
+            String::with_capacity(0)
+        };
+
         let status_int = self.status as i32;
 
         write!(f, "\n\n\
             ################################ OPENCL ERROR ############################### \
-            \n\nError executing function: {}{}  \
+            \n\nError executing function: {}{}{}  \
             \n\nStatus error code: {:?} ({})  \
             \n\nPlease visit the following url for more information: \n\n{}{}{}  \n\n\
             ############################################################################# \n",
-            self.fn_name, fn_info_string, self.status, status_int,
+            self.fn_name, fn_info_string, device_string, self.status, status_int,
             SDK_DOCS_URL_PRE, self.fn_name, SDK_DOCS_URL_SUF)
     }
 }
@@ -190,13 +232,35 @@ impl fmt::Debug for ApiError {
     }
 }
 
+/// Logs an API call, its details, and its return code.
+#[cfg(feature = "trace")]
+fn trace_call(errcode: cl_int, fn_name: &'static str, details: fmt::Arguments) {
+    let status = Status::from_i32(errcode);
+    if status == Some(Status::CL_SUCCESS) {
+        log::trace!("{}({}) -> CL_SUCCESS", fn_name, details);
+    } else {
+        match status {
+            Some(s) => log::debug!("{}({}) -> {:?}", fn_name, details, s),
+            None => log::debug!("{}({}) -> {}", fn_name, details, errcode),
+        }
+    }
+}
+
 /// Evaluates `errcode` and returns an `Err` with a failure message if it is
 /// not 0 (Status::CL_SUCCESS).
 ///
+/// When the `trace` feature is enabled, every call is logged along with its
+/// return code.
 #[inline(always)]
 fn eval_errcode<T, S>(errcode: cl_int, result: T, fn_name: &'static str, fn_info: Option<S>)
         -> OclCoreResult<T>
         where S: Into<String> {
+    #[cfg(feature = "trace")]
+    let fn_info = fn_info.map(Into::into);
+    #[cfg(feature = "trace")]
+    trace_call(errcode, fn_name, format_args!("{}", fn_info.as_ref().map(|s: &String| &s[..])
+        .unwrap_or("")));
+
     if (Status::CL_SUCCESS as i32) == errcode {
         Ok(result)
     } else {
@@ -205,6 +269,32 @@ fn eval_errcode<T, S>(errcode: cl_int, result: T, fn_name: &'static str, fn_info
 }
 
 
+/// Evaluates `errcode` like `eval_errcode` and, if it is an error, includes
+/// the name of the device associated with `command_queue` in the message.
+///
+/// The device name is only queried when an error has occurred and is
+/// omitted if it cannot be obtained.
+fn eval_errcode_on_queue<T, S>(errcode: cl_int, result: T, fn_name: &'static str,
+        fn_info: Option<S>, command_queue: &CommandQueue) -> OclCoreResult<T>
+        where S: Into<String> {
+    #[cfg(feature = "trace")]
+    let fn_info = fn_info.map(Into::into);
+    #[cfg(feature = "trace")]
+    trace_call(errcode, fn_name, format_args!("{}", fn_info.as_ref().map(|s: &String| &s[..])
+        .unwrap_or("")));
+
+    if (Status::CL_SUCCESS as i32) == errcode {
+        Ok(result)
+    } else {
+        let mut api_err = ApiError::new(errcode, fn_name, fn_info);
+        api_err.info.device_name = command_queue.device().ok()
+            .and_then(|device| get_device_info(device, DeviceInfo::Name).ok())
+            .map(|name| name.to_string());
+        Err(api_err.into())
+    }
+}
+
+
 /// An OpenCL program build error.
 #[derive(Debug, Fail)]
 pub enum ProgramBuildError {
@@ -221,14 +311,66 @@ pub enum ProgramBuildError {
         _0
     )]
     BuildLog(String),
+    /// The combined build log along with the build status and log of each
+    /// device, as `(device, status, log)`.
+    #[fail(display =
+        "\n\n\
+        ###################### OPENCL PROGRAM BUILD DEBUG OUTPUT \
+        ######################\
+        \n\n{}\n\
+        ########################################################\
+        #######################\
+        \n\n",
+        _0
+    )]
+    DeviceBuildLogs(String, Vec<(DeviceId, ProgramBuildStatus, String)>),
     #[fail(display = "{}", _0)]
     InfoResult(Box<OclCoreError>),
 }
 
+impl ProgramBuildError {
+    /// Returns the combined build log of every device if available.
+    pub fn build_log(&self) -> Option<&str> {
+        match *self {
+            ProgramBuildError::BuildLog(ref log) => Some(log),
+            ProgramBuildError::DeviceBuildLogs(ref log, _) => Some(log),
+            _ => None,
+        }
+    }
+
+    /// Returns the build status and build log of each device, as `(device,
+    /// status, log)`, if available.
+    pub fn device_build_logs(&self) -> Option<&[(DeviceId, ProgramBuildStatus, String)]> {
+        match *self {
+            ProgramBuildError::DeviceBuildLogs(_, ref device_logs) => Some(device_logs),
+            _ => None,
+        }
+    }
+}
+
+
+/// Returns the build status and build log of `program` for each device in
+/// `device_ids`, as `(device, status, log)`.
+pub fn get_program_build_statuses<D: ClDeviceIdPtr>(program: &Program, device_ids: &[D])
+        -> OclCoreResult<Vec<(DeviceId, ProgramBuildStatus, String)>> {
+    device_ids.iter().cloned().map(|device_id| {
+        let device = unsafe { DeviceId::from_raw(device_id.as_ptr()) };
+        let status = match get_program_build_info(program, device, ProgramBuildInfo::BuildStatus)? {
+            ProgramBuildInfoResult::BuildStatus(status) => status,
+            _ => panic!("Unexpected 'ProgramBuildInfoResult' variant."),
+        };
+        let log = match get_program_build_info(program, device, ProgramBuildInfo::BuildLog)? {
+            ProgramBuildInfoResult::BuildLog(log) => log,
+            _ => panic!("Unexpected 'ProgramBuildInfoResult' variant."),
+        };
+        Ok((device, status, log))
+    }).collect()
+}
+
 
 /// If the program pointed to by `cl_program` for any of the devices listed in
-/// `device_ids` has a build log of any length, it will be returned as an
-/// errcode result.
+/// `device_ids` has a build log of any length or failed to build, the build
+/// logs (combined and per-device) will be returned as an errcode result.
 ///
 pub fn program_build_err<D: ClDeviceIdPtr>(program: &Program, device_ids: &[D])
         -> Result<(), ProgramBuildError> {
@@ -236,19 +378,22 @@ pub fn program_build_err<D: ClDeviceIdPtr>(program: &Program, device_ids: &[D])
         return Err(ProgramBuildError::DeviceListEmpty);
     }
 
-    for device_id in device_ids.iter().cloned() {
-        match get_program_build_info(program, device_id, ProgramBuildInfo::BuildLog) {
-            Ok(ProgramBuildInfoResult::BuildLog(log)) => {
-                if log.len() > 1 {
-                    return Err(ProgramBuildError::BuildLog(log));
-                }
-            },
-            Err(err) => return Err(ProgramBuildError::InfoResult(Box::new(err))),
-            _ => panic!("Unexpected 'ProgramBuildInfoResult' variant."),
-        }
-    }
+    let device_logs = get_program_build_statuses(program, device_ids)
+        .map_err(|err| ProgramBuildError::InfoResult(Box::new(err)))?;
 
-    Ok(())
+    let failed = device_logs.iter().any(|&(_, status, ref log)| {
+        status == ProgramBuildStatus::Error || log.len() > 1
+    });
+
+    if failed {
+        let log = device_logs.iter().map(|(_, _, log)| log.as_str())
+            .filter(|log| log.len() > 1)
+            .collect::<Vec<_>>()
+            .join("\n");
+        Err(ProgramBuildError::DeviceBuildLogs(log, device_logs))
+    } else {
+        Ok(())
+    }
 }
 
 
@@ -257,6 +402,9 @@ pub fn program_build_err<D: ClDeviceIdPtr>(program: &Program, device_ids: &[D])
 #[derive(Debug)]
 pub(crate) enum ApiFunction {
     None,
+    CreateSubDevices,
+    CreateCommandQueueWithProperties,
+    SetKernelExecInfo,
     RetainDevice,
     ReleaseDevice,
     CreateProgramWithIl,
@@ -271,6 +419,12 @@ pub(crate) enum ApiFunction {
     GetExtensionFunctionAddressForPlatform,
     CompileProgram,
     LinkProgram,
+    GetDeviceAndHostTimer,
+    GetHostTimer,
+    SetDefaultDeviceCommandQueue,
+    GetKernelSubGroupInfo,
+    EnqueueSvmMemcpy,
+    EnqueueSvmMemFill,
 }
 
 
@@ -690,18 +844,31 @@ pub fn get_device_info<D: ClDeviceIdPtr>(device: D, request: DeviceInfo)
     }
 }
 
-/// [UNIMPLEMENTED: Please implement me]
+/// Partitions `device` into sub-devices according to the zero-terminated
+/// partition property list, `properties`, and returns them.
+///
+/// See the [SDK docs] for the format of `properties`. The returned sub-devices
+/// each have a reference count of one.
+///
+/// [SDK docs]: https://www.khronos.org/registry/OpenCL/sdk/1.2/docs/man/xhtml/clCreateSubDevices.html
 ///
 /// [Version Controlled: OpenCL 1.2+] See module docs for more info.
-pub fn create_sub_devices(device_version: Option<&OpenclVersion>) -> OclCoreResult<()> {
-    // clCreateSubDevices(in_device: cl_device_id,
-    //                    properties: *const cl_device_partition_property,
-    //                    num_devices: cl_uint,
-    //                    out_devices: *mut cl_device_id,
-    //                    num_devices_ret: *mut cl_uint) -> cl_int;
-
-    let _ = device_version;
-    unimplemented!();
+pub fn create_sub_devices(device: &DeviceId, properties: &[cl_device_partition_property],
+        device_version: Option<&OpenclVersion>) -> OclCoreResult<Vec<DeviceId>> {
+    verify_device_version(device_version, [1, 2], device, ApiFunction::CreateSubDevices)?;
+    assert!(properties.last() == Some(&0), "ocl::core::create_sub_devices: The partition \
+        property list must be zero-terminated.");
+
+    let mut num_devices: cl_uint = 0;
+    let errcode = unsafe { ffi::clCreateSubDevices(device.as_ptr(), properties.as_ptr(), 0,
+        ptr::null_mut(), &mut num_devices) };
+    eval_errcode(errcode, (), "clCreateSubDevices", None::<String>)?;
+
+    let mut sub_devices: Vec<DeviceId> = (0..num_devices)
+        .map(|_| unsafe { DeviceId::null() }).collect();
+    let errcode = unsafe { ffi::clCreateSubDevices(device.as_ptr(), properties.as_ptr(),
+        num_devices, sub_devices.as_mut_ptr() as *mut cl_device_id, ptr::null_mut()) };
+    eval_errcode(errcode, sub_devices, "clCreateSubDevices", None::<String>)
 }
 
 /// Increments the reference count of a device.
@@ -722,6 +889,37 @@ pub unsafe fn release_device(device: &DeviceId, device_version: Option<&OpenclVe
     eval_errcode(ffi::clReleaseDevice(device.as_ptr()), (), "clReleaseDevice", None::<String>)
 }
 
+/// Returns a reasonably synchronized pair of timestamps from the device timer
+/// and the host timer, as seen by `device`, as `(device_timestamp,
+/// host_timestamp)`.
+///
+/// [Version Controlled: OpenCL 2.1+] See module docs for more info.
+#[cfg(feature = "opencl_version_2_1")]
+pub fn get_device_and_host_timer(device: &DeviceId, device_version: Option<&OpenclVersion>)
+        -> OclCoreResult<(u64, u64)> {
+    verify_device_version(device_version, [2, 1], device, ApiFunction::GetDeviceAndHostTimer)?;
+    let mut device_timestamp: cl_ulong = 0;
+    let mut host_timestamp: cl_ulong = 0;
+
+    let errcode = unsafe { ffi::clGetDeviceAndHostTimer(device.as_ptr(),
+        &mut device_timestamp, &mut host_timestamp) };
+    eval_errcode(errcode, (device_timestamp, host_timestamp), "clGetDeviceAndHostTimer",
+        None::<String>)
+}
+
+/// Returns the current value of the host clock as seen by `device`.
+///
+/// [Version Controlled: OpenCL 2.1+] See module docs for more info.
+#[cfg(feature = "opencl_version_2_1")]
+pub fn get_host_timer(device: &DeviceId, device_version: Option<&OpenclVersion>)
+        -> OclCoreResult<u64> {
+    verify_device_version(device_version, [2, 1], device, ApiFunction::GetHostTimer)?;
+    let mut host_timestamp: cl_ulong = 0;
+
+    let errcode = unsafe { ffi::clGetHostTimer(device.as_ptr(), &mut host_timestamp) };
+    eval_errcode(errcode, host_timestamp, "clGetHostTimer", None::<String>)
+}
+
 //============================================================================
 //============================= Context APIs  ================================
 //============================================================================
@@ -1082,6 +1280,70 @@ pub fn create_command_queue<C, D>(
 
 }
 
+/// Returns a new command queue pointer created using
+/// `clCreateCommandQueueWithProperties`.
+///
+/// `queue_size` (`CL_QUEUE_SIZE`) may only be specified for on-device queues
+/// (`properties` containing `QUEUE_ON_DEVICE`). On-device queues must also be
+/// out-of-order.
+///
+/// [Version Controlled: OpenCL 2.0+] See module docs for more info.
+#[cfg(feature = "opencl_version_2_0")]
+pub fn create_command_queue_with_properties<C>(
+            context: C,
+            device: &DeviceId,
+            properties: Option<CommandQueueProperties>,
+            queue_size: Option<u32>,
+            device_version: Option<&OpenclVersion>,
+        ) -> OclCoreResult<CommandQueue>
+        where C: ClContextPtr
+{
+    verify_context(context)?;
+    verify_device_version(device_version, [2, 0], device,
+        ApiFunction::CreateCommandQueueWithProperties)?;
+
+    let mut props: Vec<cl_queue_properties> = Vec::with_capacity(5);
+    if let Some(p) = properties {
+        props.push(ffi::CL_QUEUE_PROPERTIES as cl_queue_properties);
+        props.push(p.bits);
+    }
+    if let Some(size) = queue_size {
+        props.push(ffi::CL_QUEUE_SIZE as cl_queue_properties);
+        props.push(size as cl_queue_properties);
+    }
+    props.push(0);
+
+    let mut errcode: cl_int = 0;
+
+    let cq_ptr = unsafe { ffi::clCreateCommandQueueWithProperties(
+        context.as_ptr(),
+        device.as_ptr(),
+        props.as_ptr(),
+        &mut errcode
+    ) };
+    eval_errcode(errcode, cq_ptr, "clCreateCommandQueueWithProperties", None::<String>)
+        .map(|cq_ptr| unsafe { CommandQueue::from_raw_create_ptr(cq_ptr) })
+}
+
+/// Replaces the default on-device command queue of `device` within `context`
+/// with `command_queue`, which must be an on-device queue.
+///
+/// [Version Controlled: OpenCL 2.1+] See module docs for more info.
+#[cfg(feature = "opencl_version_2_1")]
+pub fn set_default_device_command_queue<C>(context: C, device: &DeviceId,
+        command_queue: &CommandQueue, device_version: Option<&OpenclVersion>)
+        -> OclCoreResult<()>
+        where C: ClContextPtr
+{
+    verify_context(context)?;
+    verify_device_version(device_version, [2, 1], device,
+        ApiFunction::SetDefaultDeviceCommandQueue)?;
+
+    let errcode = unsafe { ffi::clSetDefaultDeviceCommandQueue(context.as_ptr(),
+        device.as_ptr(), command_queue.as_ptr()) };
+    eval_errcode(errcode, (), "clSetDefaultDeviceCommandQueue", None::<String>)
+}
+
 /// Increments the reference count of a command queue.
 pub unsafe fn retain_command_queue(queue: &CommandQueue) -> OclCoreResult<()> {
     eval_errcode(ffi::clRetainCommandQueue(queue.as_ptr()), (), "clRetainCommandQueue", None::<String>)
@@ -1795,6 +2057,52 @@ impl UserDataPh {
     }
 }
 
+/// Begins building a program and returns without waiting for the build to
+/// complete.
+///
+/// `pfn_notify` is called, with `user_data`, once the build has completed
+/// (successfully or unsuccessfully), possibly from another thread. Check the
+/// build status of each device (`ProgramBuildInfo::BuildStatus`) afterwards
+/// to determine the outcome. Use `_complete_build_user_event` along with a
+/// user event (passed as `user_data`) to be notified using an event.
+///
+/// ## Safety
+///
+/// `user_data` must remain valid until `pfn_notify` is called. `pfn_notify`
+/// is not called if the build could not begin, i.e. if an error other than
+/// `CL_BUILD_PROGRAM_FAILURE` is returned.
+///
+pub unsafe fn build_program_async<D: ClDeviceIdPtr>(
+            program: &Program,
+            devices: Option<&[D]>,
+            options: &CString,
+            pfn_notify: BuildProgramCallbackFn,
+            user_data: UserDataPtr,
+        ) -> OclCoreResult<()>
+{
+    let device_ptrs = DevicePtrList::from(devices);
+
+    let errcode = ffi::clBuildProgram(
+        program.as_ptr() as cl_program,
+        device_ptrs.num(),
+        device_ptrs.as_ptr(),
+        options.as_ptr(),
+        Some(pfn_notify),
+        user_data,
+    );
+
+    if errcode == Status::CL_BUILD_PROGRAM_FAILURE as i32 {
+        if let Some(ds) = devices {
+            program_build_err(program, ds).map_err(|err| err.into())
+        } else {
+            let ds = program.devices()?;
+            program_build_err(program, &ds).map_err(|err| err.into())
+        }
+    } else {
+        eval_errcode(errcode, (), "clBuildProgram", None::<String>)
+    }
+}
+
 /// Builds a program.
 ///
 /// Callback functions are not yet supported. Please file an issue if you have
@@ -2115,6 +2423,12 @@ pub unsafe fn release_kernel(kernel: &Kernel) -> OclCoreResult<()> {
 
 /// Sets the argument value for the kernel argument at `index`.
 ///
+/// ### Thread Safety
+///
+/// `clSetKernelArg` is not safe to call concurrently on the same kernel
+/// object. `Kernel` is therefore not `Sync`. Use one kernel per thread or
+/// synchronize access externally.
+///
 /// ### Example:
 ///
 /// ```rust, ignore
@@ -2136,9 +2450,41 @@ pub fn set_kernel_arg(kernel: &Kernel, index: u32, arg_val: ArgVal)
             value,
     ) };
 
+    // Failures are traced by `eval_errcode`:
     if err != Status::CL_SUCCESS as i32 {
         let name = get_kernel_name(kernel)?;
         eval_errcode(err, (), "clSetKernelArg", Some(name))
+    } else {
+        #[cfg(feature = "trace")]
+        trace_call(err, "clSetKernelArg", format_args!("kernel: {:?}, index: {}, size: {}, \
+            value: {:?}", kernel.as_ptr(), index, size, value));
+        Ok(())
+    }
+}
+
+/// Declares the SVM pointers which `kernel` may access other than through
+/// its arguments (e.g. pointers stored within an SVM allocation) by setting
+/// `CL_KERNEL_EXEC_INFO_SVM_PTRS`.
+///
+/// Each call replaces any previously declared set of pointers.
+///
+/// [Version Controlled: OpenCL 2.0+] See module docs for more info.
+#[cfg(feature = "opencl_version_2_0")]
+pub fn set_kernel_exec_info_svm_ptrs(kernel: &Kernel, ptrs: &[*const c_void],
+        device_versions: Option<&[OpenclVersion]>) -> OclCoreResult<()>
+{
+    verify_device_versions(device_versions, [2, 0], kernel, ApiFunction::SetKernelExecInfo)?;
+
+    let err = unsafe { ffi::clSetKernelExecInfo(
+        kernel.as_ptr(),
+        ffi::CL_KERNEL_EXEC_INFO_SVM_PTRS,
+        mem::size_of_val(ptrs),
+        ptrs.as_ptr() as *const c_void,
+    ) };
+
+    if err != Status::CL_SUCCESS as i32 {
+        let name = get_kernel_name(kernel)?;
+        eval_errcode(err, (), "clSetKernelExecInfo", Some(name))
     } else {
         Ok(())
     }
@@ -2282,6 +2628,32 @@ pub fn get_kernel_work_group_info<D: ClDeviceIdPtr>(obj: &Kernel, device_obj: D,
     KernelWorkGroupInfoResult::from_bytes(request, result)
 }
 
+/// Returns subgroup information about a kernel when enqueued with the local
+/// work size `local_work_size` (one element per dimension).
+///
+/// [Version Controlled: OpenCL 2.1+] See module docs for more info.
+#[cfg(feature = "opencl_version_2_1")]
+pub fn get_kernel_sub_group_info(obj: &Kernel, device: &DeviceId, request: KernelSubGroupInfo,
+        local_work_size: &[usize], device_version: Option<&OpenclVersion>)
+        -> OclCoreResult<usize>
+{
+    verify_device_version(device_version, [2, 1], device, ApiFunction::GetKernelSubGroupInfo)?;
+
+    let mut result: size_t = 0;
+
+    let errcode = unsafe { ffi::clGetKernelSubGroupInfo(
+        obj.as_ptr() as cl_kernel,
+        device.as_ptr() as cl_device_id,
+        request as cl_kernel_sub_group_info,
+        mem::size_of_val(local_work_size) as size_t,
+        local_work_size.as_ptr() as *const c_void,
+        mem::size_of::<size_t>() as size_t,
+        &mut result as *mut size_t as *mut c_void,
+        ptr::null_mut(),
+    ) };
+    eval_errcode(errcode, result, "clGetKernelSubGroupInfo", None::<String>)
+}
+
 //============================================================================
 //========================== Event Object APIs ===============================
 //============================================================================
@@ -2461,7 +2833,10 @@ pub fn get_event_profiling_info<'e, E: ClEventPtrRef<'e>>(event: &'e E, request:
 /// Issues all previously queued OpenCL commands in a command-queue to the
 /// device associated with the command-queue.
 pub fn flush(command_queue: &CommandQueue) -> OclCoreResult<()> {
-    unsafe { eval_errcode(ffi::clFlush(command_queue.as_ptr()), (), "clFlush", None::<String>) }
+    unsafe {
+        let errcode = ffi::clFlush(command_queue.as_ptr());
+        eval_errcode_on_queue(errcode, (), "clFlush", None::<String>, command_queue)
+    }
 }
 
 /// Waits for a queue to finish.
@@ -2471,7 +2846,7 @@ pub fn flush(command_queue: &CommandQueue) -> OclCoreResult<()> {
 pub fn finish(command_queue: &CommandQueue) -> OclCoreResult<()> {
     unsafe {
         let errcode = ffi::clFinish(command_queue.as_ptr());
-        eval_errcode(errcode, (), "clFinish", None::<String>)
+        eval_errcode_on_queue(errcode, (), "clFinish", None::<String>, command_queue)
     }
 }
 
@@ -2519,7 +2894,7 @@ pub unsafe fn enqueue_read_buffer<T, M, En, Ewl>(
         new_event_ptr,
     );
 
-    eval_errcode(errcode, (), "clEnqueueReadBuffer", None::<String>)
+    eval_errcode_on_queue(errcode, (), "clEnqueueReadBuffer", None::<String>, command_queue)
 }
 
 /// Enqueues a command to read from a rectangular region from a buffer object to host memory.
@@ -2589,7 +2964,7 @@ pub unsafe fn enqueue_read_buffer_rect<T, M, En, Ewl>(
         new_event_ptr,
     );
 
-    eval_errcode(errcode, (), "clEnqueueReadBufferRect", None::<String>)
+    eval_errcode_on_queue(errcode, (), "clEnqueueReadBufferRect", None::<String>, command_queue)
 }
 
 /// Enqueues a write from host memory, `data`, to device memory referred to by
@@ -2628,7 +3003,7 @@ pub unsafe fn enqueue_write_buffer<T, M, En, Ewl>(
         wait_list_ptr,
         new_event_ptr,
     );
-    eval_errcode(errcode, (), "clEnqueueWriteBuffer", None::<String>)
+    eval_errcode_on_queue(errcode, (), "clEnqueueWriteBuffer", None::<String>, command_queue)
 }
 
 /// Enqueues a command to write from a rectangular region from host memory to a buffer object.
@@ -2687,7 +3062,7 @@ pub unsafe fn enqueue_write_buffer_rect<T, M, En, Ewl>(
         wait_list_ptr,
         new_event_ptr,
     );
-    eval_errcode(errcode, (), "clEnqueueWriteBufferRect", None::<String>)
+    eval_errcode_on_queue(errcode, (), "clEnqueueWriteBufferRect", None::<String>, command_queue)
 }
 
 /// Enqueues a command to fill a buffer object with a pattern of a given pattern size.
@@ -2729,7 +3104,90 @@ pub fn enqueue_fill_buffer<T, M, En, Ewl>(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    eval_errcode(errcode, (), "clEnqueueFillBuffer", None::<String>)
+    eval_errcode_on_queue(errcode, (), "clEnqueueFillBuffer", None::<String>, command_queue)
+}
+
+/// Enqueues a copy of `len` elements from `src` to `dst`, either of which
+/// may be a shared virtual memory (SVM) pointer or a host pointer.
+///
+/// If `block` is true, does not return until the copy is complete.
+///
+/// ## Safety
+///
+/// Both regions must be valid for `len` elements and must not overlap. If
+/// `block` is false they must remain valid until the command completes.
+///
+/// [Version Controlled: OpenCL 2.0+] See module docs for more info.
+#[cfg(feature = "opencl_version_2_0")]
+pub unsafe fn enqueue_svm_memcpy<T, En, Ewl>(
+            command_queue: &CommandQueue,
+            block: bool,
+            dst: *mut T,
+            src: *const T,
+            len: usize,
+            wait_list: Option<Ewl>,
+            new_event: Option<En>,
+            device_version: Option<&OpenclVersion>
+        ) -> OclCoreResult<()>
+        where T: OclPrm, En: ClNullEventPtr, Ewl: ClWaitListPtr
+{
+    verify_device_version(device_version, [2, 0], command_queue,
+        ApiFunction::EnqueueSvmMemcpy)?;
+
+    let (wait_list_len, wait_list_ptr, new_event_ptr)
+        = resolve_event_ptrs(wait_list, new_event);
+
+    let errcode = ffi::clEnqueueSVMMemcpy(
+        command_queue.as_ptr(),
+        block as cl_uint,
+        dst as *mut c_void,
+        src as *const c_void,
+        len * mem::size_of::<T>(),
+        wait_list_len,
+        wait_list_ptr,
+        new_event_ptr,
+    );
+    eval_errcode_on_queue(errcode, (), "clEnqueueSVMMemcpy", None::<String>, command_queue)
+}
+
+/// Enqueues a command to fill `len` elements of the shared virtual memory
+/// (SVM) region beginning at `svm_ptr` with `pattern`.
+///
+/// ## Safety
+///
+/// `svm_ptr` must point to an SVM allocation, aligned to the size of `T`,
+/// which is valid for `len` elements until the command completes.
+///
+/// [Version Controlled: OpenCL 2.0+] See module docs for more info.
+#[cfg(feature = "opencl_version_2_0")]
+pub unsafe fn enqueue_svm_mem_fill<T, En, Ewl>(
+            command_queue: &CommandQueue,
+            svm_ptr: *mut T,
+            pattern: T,
+            len: usize,
+            wait_list: Option<Ewl>,
+            new_event: Option<En>,
+            device_version: Option<&OpenclVersion>
+        ) -> OclCoreResult<()>
+        where T: OclPrm, En: ClNullEventPtr, Ewl: ClWaitListPtr
+{
+    verify_device_version(device_version, [2, 0], command_queue,
+        ApiFunction::EnqueueSvmMemFill)?;
+
+    let (wait_list_len, wait_list_ptr, new_event_ptr)
+        = resolve_event_ptrs(wait_list, new_event);
+
+    let errcode = ffi::clEnqueueSVMMemFill(
+        command_queue.as_ptr(),
+        svm_ptr as *mut c_void,
+        &pattern as *const _ as *const c_void,
+        mem::size_of::<T>(),
+        len * mem::size_of::<T>(),
+        wait_list_len,
+        wait_list_ptr,
+        new_event_ptr,
+    );
+    eval_errcode_on_queue(errcode, (), "clEnqueueSVMMemFill", None::<String>, command_queue)
 }
 
 /// Copies the contents of one buffer to another.
@@ -2763,7 +3221,7 @@ pub fn enqueue_copy_buffer<T, M, En, Ewl>(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    eval_errcode(errcode, (), "clEnqueueCopyBuffer", None::<String>)
+    eval_errcode_on_queue(errcode, (), "clEnqueueCopyBuffer", None::<String>, command_queue)
 }
 
 /// Enqueues a command to copy a rectangular region from a buffer object to
@@ -2811,7 +3269,7 @@ pub fn enqueue_copy_buffer_rect<T, M, En, Ewl>(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    eval_errcode(errcode, (), "clEnqueueCopyBufferRect", None::<String>)
+    eval_errcode_on_queue(errcode, (), "clEnqueueCopyBufferRect", None::<String>, command_queue)
 }
 
 /// Acquire OpenCL memory objects (buffers and images) that have been created
@@ -2840,7 +3298,7 @@ pub fn enqueue_acquire_gl_objects<En, Ewl>(
         wait_list_ptr,
         new_event_ptr
     ) };
-    eval_errcode(errcode, (), "clEnqueueAcquireGLObjects", None::<String>)
+    eval_errcode_on_queue(errcode, (), "clEnqueueAcquireGLObjects", None::<String>, command_queue)
 }
 
 /// Release OpenCL memory objects (buffers and images) that have been created
@@ -2869,7 +3327,7 @@ pub fn enqueue_release_gl_objects<En, Ewl>(
         wait_list_ptr,
         new_event_ptr
     ) };
-    eval_errcode(errcode, (), "clEnqueueReleaseGLObjects", None::<String>)
+    eval_errcode_on_queue(errcode, (), "clEnqueueReleaseGLObjects", None::<String>, command_queue)
 }
 
 
@@ -2914,7 +3372,7 @@ pub unsafe fn enqueue_read_image<T, M, En, Ewl>(
         wait_list_ptr,
         new_event_ptr,
     );
-    eval_errcode(errcode, (), "clEnqueueReadImage", None::<String>)
+    eval_errcode_on_queue(errcode, (), "clEnqueueReadImage", None::<String>, command_queue)
 }
 
 
@@ -2960,7 +3418,7 @@ pub unsafe fn enqueue_write_image<T, M, En, Ewl>(
         wait_list_ptr,
         new_event_ptr,
     );
-    eval_errcode(errcode, (), "clEnqueueWriteImage", None::<String>)
+    eval_errcode_on_queue(errcode, (), "clEnqueueWriteImage", None::<String>, command_queue)
 }
 
 /// Enqueues a command to fill an image object with a specified color.
@@ -3010,7 +3468,7 @@ pub fn enqueue_fill_image<T, M, En, Ewl>(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    eval_errcode(errcode, (), "clEnqueueFillImage", None::<String>)
+    eval_errcode_on_queue(errcode, (), "clEnqueueFillImage", None::<String>, command_queue)
 }
 
 
@@ -3043,7 +3501,7 @@ pub fn enqueue_copy_image<En, Ewl>(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    eval_errcode(errcode, (), "clEnqueueCopyImage", None::<String>)
+    eval_errcode_on_queue(errcode, (), "clEnqueueCopyImage", None::<String>, command_queue)
 }
 
 /// Enqueues a command to copy an image object to a buffer object.
@@ -3080,7 +3538,7 @@ pub fn enqueue_copy_image_to_buffer<T, M, En, Ewl>(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    eval_errcode(errcode, (), "clEnqueueCopyImageToBuffer", None::<String>)
+    eval_errcode_on_queue(errcode, (), "clEnqueueCopyImageToBuffer", None::<String>, command_queue)
 }
 
 /// Enqueues a command to copy a buffer object to an image object.
@@ -3117,7 +3575,7 @@ pub fn enqueue_copy_buffer_to_image<T, M, En, Ewl>(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    eval_errcode(errcode, (), "clEnqueueCopyBufferToImage", None::<String>)
+    eval_errcode_on_queue(errcode, (), "clEnqueueCopyBufferToImage", None::<String>, command_queue)
 }
 
 #[inline]
@@ -3152,7 +3610,8 @@ unsafe fn _enqueue_map_buffer<T, M>(
         &mut errcode,
     );
 
-    eval_errcode(errcode, mapped_ptr as *mut T, "clEnqueueMapBuffer", None::<String>)
+    eval_errcode_on_queue(errcode, mapped_ptr as *mut T, "clEnqueueMapBuffer", None::<String>,
+        command_queue)
 }
 
 /// Enqueues a command to map a region of the buffer object given
@@ -3255,7 +3714,7 @@ pub unsafe fn enqueue_map_image<T, M, En, Ewl>(
     // eval_errcode(errcode, MemMap::new(mapped_ptr as *mut T, slc_pitch * region[2],
     //     None, image.as_mem().clone(), command_queue.clone()), "clEnqueueMapImage", None::<String>)
 
-    eval_errcode(errcode, mapped_ptr, "clEnqueueMapImage", None::<String>)
+    eval_errcode_on_queue(errcode, mapped_ptr, "clEnqueueMapImage", None::<String>, command_queue)
         .map(|ptr| MemMap::from_raw(ptr as *mut _ as *mut T))
 }
 
@@ -3284,7 +3743,7 @@ pub fn enqueue_unmap_mem_object<T, M, En, Ewl>(
         new_event_ptr,
     ) };
 
-    eval_errcode(errcode, (), "clEnqueueUnmapMemObject", None::<String>)
+    eval_errcode_on_queue(errcode, (), "clEnqueueUnmapMemObject", None::<String>, command_queue)
 }
 
 /// Enqueues a command to indicate which device a set of memory objects should
@@ -3321,7 +3780,7 @@ pub fn enqueue_migrate_mem_objects<En: ClNullEventPtr, Ewl: ClWaitListPtr>(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    eval_errcode(errcode, (), "clEnqueueMigrateMemObjects", None::<String>)
+    eval_errcode_on_queue(errcode, (), "clEnqueueMigrateMemObjects", None::<String>, command_queue)
 }
 
 /// Enqueues a command to execute a kernel on a device.
@@ -3414,10 +3873,16 @@ pub unsafe fn enqueue_kernel<En: ClNullEventPtr, Ewl: ClWaitListPtr> (
         thread::sleep(Duration::from_millis(KERNEL_DEBUG_SLEEP_DURATION_MS));
     }
 
+    // Failures are traced by `eval_errcode_on_queue`:
     if errcode != 0 {
         let name = get_kernel_name(kernel)?;
-        eval_errcode(errcode, (), "clEnqueueNDRangeKernel", Some(name))
+        eval_errcode_on_queue(errcode, (), "clEnqueueNDRangeKernel", Some(name), command_queue)
     } else {
+        #[cfg(feature = "trace")]
+        trace_call(errcode, "clEnqueueNDRangeKernel", format_args!("queue: {:?}, kernel: {:?}, \
+            work_dims: {}, gwo: {:?}, gws: {:?}, lws: {:?}, wait_list_len: {}",
+            command_queue.as_ptr(), kernel.as_ptr(), work_dims, global_work_offset,
+            global_work_dims, local_work_dims, wait_list_len));
         Ok(())
     }
 }
@@ -3458,7 +3923,7 @@ pub unsafe fn enqueue_task<En: ClNullEventPtr, Ewl: ClWaitListPtr>(
         wait_list_ptr,
         new_event_ptr,
     );
-    eval_errcode(errcode, (), "clEnqueueTask", kernel_name)
+    eval_errcode_on_queue(errcode, (), "clEnqueueTask", kernel_name, command_queue)
 }
 
 /// [UNIMPLEMENTED: Please implement me]
@@ -3503,7 +3968,7 @@ pub fn enqueue_marker_with_wait_list<En, Ewl>(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    eval_errcode(errcode, (), "clEnqueueMarkerWithWaitList", None::<String>)
+    eval_errcode_on_queue(errcode, (), "clEnqueueMarkerWithWaitList", None::<String>, command_queue)
 }
 
 /// A synchronization point that enqueues a barrier operation.
@@ -3532,7 +3997,7 @@ pub fn enqueue_barrier_with_wait_list<En, Ewl>(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    eval_errcode(errcode, (), "clEnqueueBarrierWithWaitList", None::<String>)
+    eval_errcode_on_queue(errcode, (), "clEnqueueBarrierWithWaitList", None::<String>, command_queue)
 }
 
 
@@ -112,6 +112,8 @@ extern crate num_complex;
 extern crate ocl_core_vector as vector;
 #[macro_use]
 extern crate failure;
+#[cfg(feature = "trace")]
+extern crate log;
 pub extern crate cl_sys as ffi;
 
 #[cfg(test)] mod tests;
@@ -158,7 +160,8 @@ pub use self::functions::{get_platform_ids, get_platform_info, get_device_ids, g
     enqueue_barrier_with_wait_list, get_extension_function_address_for_platform, wait_for_event,
     event_status, default_platform_idx, program_build_err, verify_context, default_platform,
     default_device_type, device_versions, event_is_complete, _dummy_event_callback,
-    _complete_user_event, get_context_platform, get_device_info_raw};
+    _complete_user_event, get_context_platform, get_device_info_raw, ProgramBuildError,
+    build_program_async, _complete_build_user_event, get_program_build_statuses};
 
 #[cfg(not(feature="opencl_vendor_mesa"))]
 pub use self::functions::{
@@ -167,7 +170,7 @@ pub use self::functions::{
     create_from_gl_texture_2d, create_from_gl_texture_3d, enqueue_fill_buffer,
     enqueue_acquire_gl_objects, enqueue_release_gl_objects};
 
-pub use crate::traits::{OclPrm, OclNum, OclScl};
+pub use crate::traits::{OclPrm, OclNum, OclScl, ScalarType};
 
 #[cfg(feature = "ocl-core-vector")]
 pub use self::vector::{
@@ -187,8 +190,13 @@ pub use self::vector::{
 #[cfg(feature = "ocl-core-vector")]
 pub use crate::traits::OclVec;
 
+#[cfg(feature = "opencl_version_2_0")]
+pub use self::functions::{create_command_queue_with_properties, set_kernel_exec_info_svm_ptrs,
+    enqueue_svm_memcpy, enqueue_svm_mem_fill};
+
 #[cfg(feature = "opencl_version_2_1")]
-pub use self::functions::{create_program_with_il};
+pub use self::functions::{create_program_with_il, get_device_and_host_timer, get_host_timer,
+    set_default_device_command_queue, get_kernel_sub_group_info};
 
 
 
@@ -207,6 +215,8 @@ pub type EventCallbackFn = extern "C" fn (ffi::cl_event, i32, *mut ffi::c_void);
 pub type CreateContextCallbackFn = extern "C" fn (*const ffi::c_char, *const ffi::c_void,
     ffi::size_t, *mut ffi::c_void);
 pub type BuildProgramCallbackFn = extern "C" fn (*mut ffi::c_void, *mut ffi::c_void);
+pub type PrintfCallbackFn = extern "C" fn (*const ffi::c_char, ffi::size_t, ffi::size_t,
+    *mut ffi::c_void);
 pub type UserDataPtr = *mut ffi::c_void;
 
 //=============================================================================
@@ -219,6 +229,7 @@ mod traits {
     use std::iter::{Sum, Product};
     use num_traits::{NumCast, FromPrimitive, ToPrimitive, Zero, One};
     use num_complex::{Complex32, Complex64};
+    use crate::ffi;
 
     // Implements an unsafe trait for a list of types.
     macro_rules! impl_unsafe {
@@ -227,6 +238,71 @@ mod traits {
         }
     }
 
+    // Implements `OclNum` for a list of types with the given scalar type.
+    macro_rules! impl_scalar_type {
+        ($st:ident: $( $ty:ident ),+) => {
+            $( unsafe impl OclNum for $ty {
+                #[inline]
+                fn scalar_type() -> ScalarType { ScalarType::$st }
+            } )+
+        }
+    }
+
+    // Asserts at compile time that each Rust type has the same size and
+    // alignment as the OpenCL type it is passed to kernels as.
+    //
+    // Scalar kernel arguments are set using `size_of::<T>()` (see
+    // `ArgVal::scalar`) so a mismatch would silently pass the wrong number
+    // of bytes to `clSetKernelArg`.
+    macro_rules! assert_cl_layout {
+        ($( $ty:ident: $cl_ty:ident ),+) => {
+            $( const _: () = assert!(
+                std::mem::size_of::<$ty>() == std::mem::size_of::<ffi::$cl_ty>() &&
+                    std::mem::align_of::<$ty>() == std::mem::align_of::<ffi::$cl_ty>(),
+                concat!("`", stringify!($ty), "` does not match the layout of `",
+                    stringify!($cl_ty), "`."));
+            )+
+        }
+    }
+
+    /// The element type of an `OclNum` type, available at runtime.
+    ///
+    /// Corresponds to the OpenCL built-in scalar types (e.g. `ScalarType::F32`
+    /// is `cl_float`).
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum ScalarType {
+        I8,
+        U8,
+        I16,
+        U16,
+        I32,
+        U32,
+        I64,
+        U64,
+        F16,
+        F32,
+        F64,
+        Unknown,
+    }
+
+    impl ScalarType {
+        /// Returns the size of this type in bytes or `None` if unknown.
+        pub fn size(&self) -> Option<usize> {
+            match *self {
+                ScalarType::I8 | ScalarType::U8 => Some(1),
+                ScalarType::I16 | ScalarType::U16 | ScalarType::F16 => Some(2),
+                ScalarType::I32 | ScalarType::U32 | ScalarType::F32 => Some(4),
+                ScalarType::I64 | ScalarType::U64 | ScalarType::F64 => Some(8),
+                ScalarType::Unknown => None,
+            }
+        }
+
+        /// Returns true if this is a floating point type.
+        pub fn is_float(&self) -> bool {
+            *self == ScalarType::F16 || *self == ScalarType::F32 || *self == ScalarType::F64
+        }
+    }
+
     #[cfg(feature = "ocl-core-vector")]
     pub use self::ocl_vec::OclVec;
 
@@ -252,9 +328,42 @@ mod traits {
         Zero<Output=Self> + One<Output=Self> + Add<Self, Output=Self> + Sub<Self, Output=Self> +
         Mul<Self, Output=Self> + Div<Self, Output=Self> + Rem<Self, Output=Self> + PartialEq<Self>
         + AddAssign<Self> + SubAssign<Self> + MulAssign<Self> + DivAssign<Self> + RemAssign<Self> +
-        Sum<Self> + Product<Self> + Send + Sync + 'static {}
+        Sum<Self> + Product<Self> + Send + Sync + 'static
+    {
+        /// Returns the scalar element type of this type (the type of each
+        /// component for vector types).
+        ///
+        /// Returns `ScalarType::Unknown` unless overridden.
+        fn scalar_type() -> ScalarType { ScalarType::Unknown }
+    }
 
-    impl_unsafe!(OclNum: u8, i8, u16, i16, u32, i32, u64, i64, usize, isize, f32, f64);
+    impl_scalar_type!(U8: u8);
+    impl_scalar_type!(I8: i8);
+    impl_scalar_type!(U16: u16);
+    impl_scalar_type!(I16: i16);
+    impl_scalar_type!(U32: u32);
+    impl_scalar_type!(I32: i32);
+    impl_scalar_type!(U64: u64);
+    impl_scalar_type!(I64: i64);
+    impl_scalar_type!(F32: f32);
+    impl_scalar_type!(F64: f64);
+
+    #[cfg(target_pointer_width = "32")]
+    impl_scalar_type!(U32: usize);
+    #[cfg(target_pointer_width = "32")]
+    impl_scalar_type!(I32: isize);
+    #[cfg(target_pointer_width = "64")]
+    impl_scalar_type!(U64: usize);
+    #[cfg(target_pointer_width = "64")]
+    impl_scalar_type!(I64: isize);
+
+    assert_cl_layout!(u8: cl_uchar, i8: cl_char, u16: cl_ushort, i16: cl_short, u32: cl_uint,
+        i32: cl_int, u64: cl_ulong, i64: cl_long, f32: cl_float, f64: cl_double);
+
+    #[cfg(target_pointer_width = "32")]
+    assert_cl_layout!(usize: cl_uint, isize: cl_int);
+    #[cfg(target_pointer_width = "64")]
+    assert_cl_layout!(usize: cl_ulong, isize: cl_long);
 
     /// A scalar type usable within OpenCL kernels.
     ///
@@ -266,7 +375,7 @@ mod traits {
 
     #[cfg(feature = "ocl-core-vector")]
     mod ocl_vec {
-        use crate::traits::{OclPrm, OclNum};
+        use crate::traits::{OclPrm, OclNum, ScalarType};
 
         /// A vector type usable within `OpenCL` kernels.
         ///
@@ -287,18 +396,16 @@ mod traits {
             Double, Double2, Double3, Double4, Double8, Double16
         };
 
-        impl_unsafe!(OclNum:
-            Char, Char2, Char3, Char4, Char8, Char16,
-            Uchar, Uchar2, Uchar3, Uchar4, Uchar8, Uchar16,
-            Short, Short2, Short3, Short4, Short8, Short16,
-            Ushort, Ushort2, Ushort3, Ushort4, Ushort8, Ushort16,
-            Int, Int2, Int3, Int4, Int8, Int16,
-            Uint, Uint2, Uint3, Uint4, Uint8, Uint16,
-            Long, Long2, Long3, Long4, Long8, Long16,
-            Ulong, Ulong2, Ulong3, Ulong4, Ulong8, Ulong16,
-            Float, Float2, Float3, Float4, Float8, Float16,
-            Double, Double2, Double3, Double4, Double8, Double16
-        );
+        impl_scalar_type!(I8: Char, Char2, Char3, Char4, Char8, Char16);
+        impl_scalar_type!(U8: Uchar, Uchar2, Uchar3, Uchar4, Uchar8, Uchar16);
+        impl_scalar_type!(I16: Short, Short2, Short3, Short4, Short8, Short16);
+        impl_scalar_type!(U16: Ushort, Ushort2, Ushort3, Ushort4, Ushort8, Ushort16);
+        impl_scalar_type!(I32: Int, Int2, Int3, Int4, Int8, Int16);
+        impl_scalar_type!(U32: Uint, Uint2, Uint3, Uint4, Uint8, Uint16);
+        impl_scalar_type!(I64: Long, Long2, Long3, Long4, Long8, Long16);
+        impl_scalar_type!(U64: Ulong, Ulong2, Ulong3, Ulong4, Ulong8, Ulong16);
+        impl_scalar_type!(F32: Float, Float2, Float3, Float4, Float8, Float16);
+        impl_scalar_type!(F64: Double, Double2, Double3, Double4, Double8, Double16);
 
         impl_unsafe!(OclPrm:
             Char, Char2, Char3, Char4, Char8, Char16,
@@ -950,6 +1057,10 @@ pub const CL_CGL_SHAREGROUP_KHR_OS_SPECIFIC: isize = ffi::CL_CONTEXT_PROPERTY_US
 #[cfg(not(target_os = "macos"))]
 pub const CL_CGL_SHAREGROUP_KHR_OS_SPECIFIC: isize = ffi::CL_CGL_SHAREGROUP_KHR;
 
+// cl_arm_printf extension:
+pub const CL_PRINTF_CALLBACK_ARM: isize = 0x40B0;
+pub const CL_PRINTF_BUFFERSIZE_ARM: isize = 0x40B1;
+
 enum_from_primitive! {
     /// cl_context_info + cl_context_properties
     #[repr(C)]
@@ -967,6 +1078,8 @@ enum_from_primitive! {
         AdapterD3d9exKhr = ffi::CL_CONTEXT_ADAPTER_D3D9EX_KHR as isize,
         AdapterDxvaKhr = ffi::CL_CONTEXT_ADAPTER_DXVA_KHR as isize,
         D3d11DeviceKhr = ffi::CL_CONTEXT_D3D11_DEVICE_KHR as isize,
+        PrintfCallbackArm = CL_PRINTF_CALLBACK_ARM,
+        PrintfBufferSizeArm = CL_PRINTF_BUFFERSIZE_ARM,
     }
 }
 
@@ -1249,6 +1362,17 @@ enum_from_primitive! {
 }
 
 
+enum_from_primitive! {
+    /// cl_kernel_sub_group_info
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum KernelSubGroupInfo {
+        MaxSubGroupSizeForNdrange = ffi::CL_KERNEL_MAX_SUB_GROUP_SIZE_FOR_NDRANGE as isize,
+        SubGroupCountForNdrange = ffi::CL_KERNEL_SUB_GROUP_COUNT_FOR_NDRANGE as isize,
+    }
+}
+
+
 enum_from_primitive! {
     /// cl_event_info
     #[repr(C)]
@@ -329,3 +329,18 @@ fn test_vector_types() {
         add_char16(context, &queue);
     }
 }
+
+#[test]
+fn scalar_types() {
+    use crate::{OclNum, ScalarType, Char16, Uint2, Float4, Double3};
+
+    assert_eq!(f32::scalar_type(), ScalarType::F32);
+    assert_eq!(u8::scalar_type(), ScalarType::U8);
+    assert_eq!(i64::scalar_type(), ScalarType::I64);
+    assert_eq!(Char16::scalar_type(), ScalarType::I8);
+    assert_eq!(Uint2::scalar_type(), ScalarType::U32);
+    assert_eq!(Float4::scalar_type(), ScalarType::F32);
+    assert_eq!(Double3::scalar_type(), ScalarType::F64);
+    assert_eq!(ScalarType::F16.size(), Some(2));
+    assert!(ScalarType::F64.is_float() && !ScalarType::I32.is_float());
+}
@@ -7,10 +7,10 @@ use std::marker::PhantomData;
 use std::collections::HashMap;
 use num_traits::FromPrimitive;
 use crate::error::{Error as OclCoreError, Result as OclCoreResult};
-use crate::ffi::{self,cl_mem, cl_sampler, cl_buffer_region, cl_context_properties, cl_platform_id,
+use crate::ffi::{self,cl_mem, cl_sampler, cl_command_queue, cl_buffer_region, cl_context_properties, cl_platform_id,
     c_void, size_t};
 use crate::{Mem, MemObjectType, ImageChannelOrder, ImageChannelDataType, ContextProperty,
-    PlatformId, OclPrm, Sampler};
+    PlatformId, OclPrm, Sampler, CommandQueue, PrintfCallbackFn};
 
 
 // Until everything can be implemented:
@@ -77,6 +77,18 @@ impl<'a> ArgVal<'a> {
         }
     }
 
+    /// Returns a new `ArgVal` referring to a `CommandQueue` object.
+    ///
+    /// Used to pass an on-device queue (`queue_t`) to a kernel (OpenCL 2.0+).
+    pub fn queue(queue: &'a CommandQueue) -> ArgVal<'a> {
+        ArgVal {
+            size: mem::size_of::<cl_command_queue>() as size_t,
+            value: queue as *const _ as *const c_void,
+            is_mem: false,
+            _p: PhantomData,
+        }
+    }
+
     /// Returns a new `ArgVal` referring to a scalar or vector primitive.
     //
     // `::scalar` and `::vector` exist in case, at a future time, scalar and
@@ -302,6 +314,9 @@ pub enum ContextPropertyValue {
     AdapterD3d9exKhr(isize),
     AdapterDxvaKhr(isize),
     D3d11DeviceKhr(isize),
+    // cl_arm_printf:
+    PrintfCallbackArm(PrintfCallbackFn),
+    PrintfBufferSizeArm(usize),
 }
 
 unsafe impl Send for ContextPropertyValue {}
@@ -372,6 +387,24 @@ impl ContextProperties {
         self
     }
 
+    /// Specifies a function to receive the output of `printf` calls made by
+    /// kernels (builder-style).
+    ///
+    /// Requires the `cl_arm_printf` extension.
+    pub fn printf_callback_arm(mut self, callback: PrintfCallbackFn) -> ContextProperties {
+        self.set_printf_callback_arm(callback);
+        self
+    }
+
+    /// Specifies the size, in bytes, of the buffer used to store the output
+    /// of `printf` calls made by kernels (builder-style).
+    ///
+    /// Requires the `cl_arm_printf` extension.
+    pub fn printf_buffer_size_arm(mut self, size: usize) -> ContextProperties {
+        self.set_printf_buffer_size_arm(size);
+        self
+    }
+
     /// Pushes a `ContextPropertyValue` onto this list of properties
     /// (builder-style).
     pub fn property_value(mut self, prop: ContextPropertyValue) -> ContextProperties {
@@ -421,6 +454,24 @@ impl ContextProperties {
         self.contains_gl_context_or_sharegroup = true;
     }
 
+    /// Specifies a function to receive the output of `printf` calls made by
+    /// kernels.
+    ///
+    /// Requires the `cl_arm_printf` extension.
+    pub fn set_printf_callback_arm(&mut self, callback: PrintfCallbackFn) {
+        self.props.insert(ContextProperty::PrintfCallbackArm,
+            ContextPropertyValue::PrintfCallbackArm(callback));
+    }
+
+    /// Specifies the size, in bytes, of the buffer used to store the output
+    /// of `printf` calls made by kernels.
+    ///
+    /// Requires the `cl_arm_printf` extension.
+    pub fn set_printf_buffer_size_arm(&mut self, size: usize) {
+        self.props.insert(ContextProperty::PrintfBufferSizeArm,
+            ContextPropertyValue::PrintfBufferSizeArm(size));
+    }
+
     /// Pushes a `ContextPropertyValue` onto this list of properties.
     pub fn set_property_value(&mut self, prop: ContextPropertyValue) {
         match prop {
@@ -456,6 +507,14 @@ impl ContextProperties {
                     ContextPropertyValue::EglDisplayKhr(val));
                 self.contains_gl_context_or_sharegroup = true;
             },
+            ContextPropertyValue::PrintfCallbackArm(val) => {
+                self.props.insert(ContextProperty::PrintfCallbackArm,
+                    ContextPropertyValue::PrintfCallbackArm(val));
+            },
+            ContextPropertyValue::PrintfBufferSizeArm(val) => {
+                self.props.insert(ContextProperty::PrintfBufferSizeArm,
+                    ContextPropertyValue::PrintfBufferSizeArm(val));
+            },
             _ => panic!("'{:?}' is not yet a supported variant.", prop),
         }
     }
@@ -524,6 +583,14 @@ impl ContextProperties {
                     props_raw.push(*key as isize);
                     props_raw.push(sync as isize);
                 },
+                ContextPropertyValue::PrintfCallbackArm(callback) => {
+                    props_raw.push(*key as isize);
+                    props_raw.push(callback as usize as isize);
+                },
+                ContextPropertyValue::PrintfBufferSizeArm(size) => {
+                    props_raw.push(*key as isize);
+                    props_raw.push(size as isize);
+                },
                 _ => panic!("'{:?}' is not yet a supported variant.", key),
             };
         }
@@ -658,6 +725,21 @@ impl ContextProperties {
                             ContextPropertyValue::D3d11DeviceKhr(val_raw),
                         );
                     },
+                    ContextProperty::PrintfCallbackArm => {
+                        if val_raw == 0 {
+                            return Err("ContextProperties::from_raw: The value of \
+                                'ContextProperty::PrintfCallbackArm' is null.".into());
+                        }
+                        context_props.props.insert(ContextProperty::PrintfCallbackArm,
+                            ContextPropertyValue::PrintfCallbackArm(
+                                mem::transmute::<isize, PrintfCallbackFn>(val_raw)),
+                        );
+                    },
+                    ContextProperty::PrintfBufferSizeArm => {
+                        context_props.props.insert(ContextProperty::PrintfBufferSizeArm,
+                            ContextPropertyValue::PrintfBufferSizeArm(val_raw as usize),
+                        );
+                    },
             }
         }
 
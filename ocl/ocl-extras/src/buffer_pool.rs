@@ -0,0 +1,171 @@
+//! A pool which recycles device buffer allocations.
+
+use std::collections::BTreeMap;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, Weak};
+use ocl::{Buffer, Queue, Result as OclResult};
+use ocl::traits::OclPrm;
+use ocl::flags::MemFlags;
+
+
+/// The idle buffers of a pool, keyed by bucket length.
+struct PoolInner<T: OclPrm> {
+    idle: BTreeMap<usize, Vec<Buffer<T>>>,
+    idle_count: usize,
+    max_idle: usize,
+}
+
+impl<T: OclPrm> PoolInner<T> {
+    /// Releases idle buffers, largest first, until no more than `max_idle`
+    /// remain.
+    fn evict(&mut self) {
+        while self.idle_count > self.max_idle {
+            let bucket_len = match self.idle.keys().next_back() {
+                Some(&len) => len,
+                None => break,
+            };
+            let bucket = self.idle.get_mut(&bucket_len).unwrap();
+            bucket.pop();
+            if bucket.is_empty() { self.idle.remove(&bucket_len); }
+            self.idle_count -= 1;
+        }
+    }
+}
+
+
+/// A pool of device buffers which are reused instead of being released and
+/// re-created.
+///
+/// Requested lengths are rounded up to the next power of two (a 'bucket')
+/// and a buffer previously returned to that bucket is handed out if one is
+/// available, otherwise a new one is created. Buffers are returned to the
+/// pool when the `PooledBuffer` wrapping them is dropped. This avoids
+/// repeated `clCreateBuffer` and `clReleaseMemObject` calls when temporary
+/// buffers are needed constantly (e.g. every frame).
+///
+/// At most `max_idle` buffers are kept waiting for reuse. Any beyond that,
+/// starting with the largest, are released.
+///
+/// Note that the contents of a recycled buffer are whatever was last written
+/// to it.
+pub struct BufferPool<T: OclPrm> {
+    inner: Arc<Mutex<PoolInner<T>>>,
+    queue: Queue,
+    flags: MemFlags,
+}
+
+impl<T: OclPrm> BufferPool<T> {
+    /// Returns a new, empty pool which creates buffers on `queue` using
+    /// `flags` and keeps at most `max_idle` idle buffers.
+    pub fn new(queue: Queue, flags: MemFlags, max_idle: usize) -> BufferPool<T> {
+        BufferPool {
+            inner: Arc::new(Mutex::new(PoolInner {
+                idle: BTreeMap::new(),
+                idle_count: 0,
+                max_idle,
+            })),
+            queue,
+            flags,
+        }
+    }
+
+    /// Returns a buffer with at least `len` elements.
+    ///
+    /// The length of the buffer itself is `len` rounded up to the next power
+    /// of two. Use `PooledBuffer::requested_len` to obtain `len`.
+    pub fn get(&self, len: usize) -> OclResult<PooledBuffer<T>> {
+        if len == 0 {
+            return Err("BufferPool::get: The requested length must be greater than zero.".into());
+        }
+        let bucket_len = len.next_power_of_two();
+
+        let recycled = {
+            let mut inner = self.inner.lock().unwrap();
+            let buffer = inner.idle.get_mut(&bucket_len).and_then(|bucket| bucket.pop());
+            if buffer.is_some() {
+                inner.idle_count -= 1;
+                if inner.idle[&bucket_len].is_empty() { inner.idle.remove(&bucket_len); }
+            }
+            buffer
+        };
+
+        let buffer = match recycled {
+            Some(buffer) => buffer,
+            None => Buffer::<T>::builder()
+                .queue(self.queue.clone())
+                .flags(self.flags)
+                .len(bucket_len)
+                .build()?,
+        };
+
+        Ok(PooledBuffer {
+            buffer: Some(buffer),
+            requested_len: len,
+            pool: Arc::downgrade(&self.inner),
+        })
+    }
+
+    /// Returns the number of buffers currently waiting for reuse.
+    pub fn idle_count(&self) -> usize {
+        self.inner.lock().unwrap().idle_count
+    }
+
+    /// Changes the maximum number of idle buffers, releasing any surplus.
+    pub fn set_max_idle(&self, max_idle: usize) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.max_idle = max_idle;
+        inner.evict();
+    }
+
+    /// Releases all idle buffers.
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.idle.clear();
+        inner.idle_count = 0;
+    }
+}
+
+
+/// A buffer borrowed from a `BufferPool` which is returned to it when
+/// dropped.
+///
+/// Dereferences to the underlying `Buffer`. If the pool has already been
+/// dropped the buffer is simply released.
+pub struct PooledBuffer<T: OclPrm> {
+    buffer: Option<Buffer<T>>,
+    requested_len: usize,
+    pool: Weak<Mutex<PoolInner<T>>>,
+}
+
+impl<T: OclPrm> PooledBuffer<T> {
+    /// Returns the length originally passed to `BufferPool::get`, which may
+    /// be less than the length of the buffer.
+    pub fn requested_len(&self) -> usize {
+        self.requested_len
+    }
+
+    /// Detaches and returns the buffer. It will not be returned to the pool.
+    pub fn into_inner(mut self) -> Buffer<T> {
+        self.buffer.take().unwrap()
+    }
+}
+
+impl<T: OclPrm> Deref for PooledBuffer<T> {
+    type Target = Buffer<T>;
+
+    fn deref(&self) -> &Buffer<T> {
+        self.buffer.as_ref().unwrap()
+    }
+}
+
+impl<T: OclPrm> Drop for PooledBuffer<T> {
+    fn drop(&mut self) {
+        if let (Some(buffer), Some(pool)) = (self.buffer.take(), self.pool.upgrade()) {
+            let mut inner = pool.lock().unwrap();
+            if inner.max_idle == 0 { return; }
+            inner.idle.entry(buffer.len()).or_insert_with(Vec::new).push(buffer);
+            inner.idle_count += 1;
+            inner.evict();
+        }
+    }
+}
@@ -8,10 +8,16 @@ pub mod sub_buffer_pool;
 pub mod command_graph;
 pub mod work_pool;
 pub mod full_device_info;
+pub mod reduce;
+pub mod buffer_pool;
+pub mod soa;
 
 pub use self::sub_buffer_pool::SubBufferPool;
 pub use self::command_graph::{CommandGraph, Command, CommandDetails, KernelArgBuffer, RwCmdIdxs};
 pub use self::work_pool::WorkPool;
+pub use self::reduce::{reduce, reduce_to_buffer};
+pub use self::buffer_pool::{BufferPool, PooledBuffer};
+pub use self::soa::{split_field, upload_field};
 
 // use rand;
 use rand::distributions::{IndependentSample, Range as RandRange};
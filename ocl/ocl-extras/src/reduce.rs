@@ -0,0 +1,126 @@
+//! A multi-pass (log-step) parallel reduction.
+
+use ocl::{Buffer, Kernel, Program, Queue, Result as OclResult};
+use ocl::traits::OclPrm;
+use ocl::flags::MemFlags;
+use ocl::core::util;
+
+
+/// Reduces the contents of `input` to a single value using the kernel named
+/// `kernel_name` within `program`, returning the result in a new
+/// one-element buffer.
+///
+/// The kernel is enqueued repeatedly on `queue`, each pass reducing every
+/// group of `wg_size` elements to a single element, until only one element
+/// remains. Intermediate results are ping-ponged between two internally
+/// allocated buffers.
+///
+/// The reduction kernel must have the following signature:
+///
+/// ```text
+/// __kernel void name(__global const T* src, __global T* dst, __local T* scratch, uint len)
+/// ```
+///
+/// Each work-group must reduce its `wg_size` elements of `src` to
+/// `dst[get_group_id(0)]` using `scratch` (which holds `wg_size` elements).
+/// Work-items with a global id greater than or equal to `len` must
+/// contribute the identity value of the operation (e.g. `0` for a sum). For
+/// example, a sum reduction (`wg_size` must be a power of two):
+///
+/// ```text
+/// __kernel void sum(__global const float* src, __global float* dst,
+///         __local float* scratch, uint len)
+/// {
+///     uint gid = get_global_id(0);
+///     uint lid = get_local_id(0);
+///     scratch[lid] = (gid < len) ? src[gid] : 0.0f;
+///     barrier(CLK_LOCAL_MEM_FENCE);
+///
+///     for (uint s = get_local_size(0) / 2; s > 0; s >>= 1) {
+///         if (lid < s) { scratch[lid] += scratch[lid + s]; }
+///         barrier(CLK_LOCAL_MEM_FENCE);
+///     }
+///
+///     if (lid == 0) { dst[get_group_id(0)] = scratch[0]; }
+/// }
+/// ```
+///
+pub fn reduce_to_buffer<T: OclPrm>(program: &Program, kernel_name: &str, queue: &Queue,
+        input: &Buffer<T>, wg_size: usize) -> OclResult<Buffer<T>>
+{
+    if wg_size == 0 {
+        return Err("reduce: The work-group size must be greater than zero.".into());
+    }
+    if input.len() == 0 {
+        return Err("reduce: The input buffer must not be empty.".into());
+    }
+
+    let kernel = Kernel::builder()
+        .program(program)
+        .name(kernel_name)
+        .queue(queue.clone())
+        .arg(None::<&Buffer<T>>)
+        .arg(None::<&Buffer<T>>)
+        .arg_local::<T>(wg_size)
+        .arg(0u32)
+        .build()?;
+
+    let group_count = |len: usize| util::padded_len(len, wg_size) / wg_size;
+
+    let mut len = input.len();
+    let mut groups = group_count(len);
+
+    let new_buffer = |len: usize| Buffer::<T>::builder()
+        .queue(queue.clone())
+        .flags(MemFlags::new().read_write())
+        .len(len)
+        .build();
+
+    // The first pass writes `groups` elements and every subsequent pass
+    // writes fewer, alternating between the two buffers:
+    let bufs = [new_buffer(groups)?, new_buffer(group_count(groups))?];
+    let mut pass = 0;
+
+    loop {
+        if pass == 0 {
+            kernel.set_arg(0, input)?;
+        } else {
+            kernel.set_arg(0, &bufs[(pass - 1) % 2])?;
+        }
+        kernel.set_arg(1, &bufs[pass % 2])?;
+        kernel.set_arg(3, len as u32)?;
+
+        unsafe {
+            kernel.cmd()
+                .global_work_size(groups * wg_size)
+                .local_work_size(wg_size)
+                .enq()?;
+        }
+
+        if groups == 1 { break; }
+
+        len = groups;
+        groups = group_count(len);
+        pass += 1;
+    }
+
+    let output = new_buffer(1)?;
+    bufs[pass % 2].copy(&output, None, Some(1)).enq()?;
+    Ok(output)
+}
+
+/// Reduces the contents of `input` to a single value using the kernel named
+/// `kernel_name` within `program` and returns it.
+///
+/// Blocks until the reduction has completed. See [`reduce_to_buffer`] for
+/// the requirements of the reduction kernel.
+///
+/// [`reduce_to_buffer`]: fn.reduce_to_buffer.html
+pub fn reduce<T: OclPrm>(program: &Program, kernel_name: &str, queue: &Queue,
+        input: &Buffer<T>, wg_size: usize) -> OclResult<T>
+{
+    let output = reduce_to_buffer(program, kernel_name, queue, input, wg_size)?;
+    let mut value = [T::default()];
+    output.read(&mut value[..]).enq()?;
+    Ok(value[0])
+}
@@ -0,0 +1,60 @@
+//! Helpers for uploading an array of structs as a struct of arrays.
+//!
+//! Kernels generally achieve better (coalesced) memory access when each
+//! field of a host-side struct is stored in its own device buffer. Given a
+//! slice of structs, [`split_field`] collects a single field into a `Vec`
+//! and [`upload_field`] does the same directly into a new `Buffer`:
+//!
+//! ```rust,ignore
+//! struct Particle { pos: ocl::prm::Float4, mass: f32 }
+//!
+//! let flags = MemFlags::new().read_only();
+//! let positions = upload_field(&queue, flags, &particles, |p| p.pos)?;
+//! let masses = upload_field(&queue, flags, &particles, |p| p.mass)?;
+//!
+//! let kernel = Kernel::builder()
+//!     .program(&program)
+//!     .name("step")
+//!     .queue(queue.clone())
+//!     .arg(&positions)
+//!     .arg(&masses)
+//!     .build()?;
+//! ```
+//!
+//! [`split_field`]: fn.split_field.html
+//! [`upload_field`]: fn.upload_field.html
+
+use ocl::{Buffer, Queue, Result as OclResult};
+use ocl::traits::OclPrm;
+use ocl::flags::MemFlags;
+
+
+/// Returns a `Vec` containing the value returned by `field` for each element
+/// of `items`, in order.
+pub fn split_field<S, T, F>(items: &[S], field: F) -> Vec<T>
+        where T: OclPrm, F: Fn(&S) -> T
+{
+    items.iter().map(field).collect()
+}
+
+/// Creates a new buffer on `queue` using `flags` and initializes it with the
+/// value returned by `field` for each element of `items`.
+///
+/// The buffer has the same length as `items`, which must not be empty.
+pub fn upload_field<S, T, F>(queue: &Queue, flags: MemFlags, items: &[S], field: F)
+        -> OclResult<Buffer<T>>
+        where T: OclPrm, F: Fn(&S) -> T
+{
+    if items.is_empty() {
+        return Err("upload_field: The slice of items must not be empty.".into());
+    }
+
+    let host_vec = split_field(items, field);
+
+    Buffer::<T>::builder()
+        .queue(queue.clone())
+        .flags(flags)
+        .len(host_vec.len())
+        .copy_host_slice(&host_vec)
+        .build()
+}
@@ -0,0 +1,71 @@
+//! A minimal, single-threaded executor.
+
+use std::sync::Arc;
+use std::thread::{self, Thread};
+use futures::{Future, Async};
+use futures::executor::{self, Notify};
+
+
+/// Unparks the thread driving a future whenever its task is notified.
+struct ThreadNotify(Thread);
+
+impl Notify for ThreadNotify {
+    fn notify(&self, _id: usize) {
+        self.0.unpark();
+    }
+}
+
+
+/// Runs `future` to completion on the current thread, returning its result.
+///
+/// The future is polled once, then the thread is parked until the future's
+/// task is notified (for example by the completion callback an `Event`
+/// registers with `::set_unpark_callback` when polled), at which point it is
+/// polled again. Spurious wake-ups cause nothing more than an extra poll.
+///
+/// This allows futures such as `FutureReadGuard`, `FutureWriteGuard`, and
+/// `FutureMemMap` to be used in simple programs and tests without an async
+/// runtime. It is equivalent to calling `.wait()` on one of these futures.
+///
+/// ### Example
+/// ```rust,ignore
+/// let guard = ocl::block_on(rw_vec.read())?;
+/// ```
+pub fn block_on<F: Future>(future: F) -> Result<F::Item, F::Error> {
+    let notify = Arc::new(ThreadNotify(thread::current()));
+    let mut task = executor::spawn(future);
+
+    loop {
+        match task.poll_future_notify(&notify, 0)? {
+            Async::Ready(item) => return Ok(item),
+            Async::NotReady => thread::park(),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use std::time::Duration;
+    use futures::future;
+    use futures::sync::oneshot;
+    use super::block_on;
+
+    #[test]
+    fn block_on_ready() {
+        assert_eq!(block_on(future::ok::<u32, ()>(5)), Ok(5));
+        assert_eq!(block_on(future::err::<u32, u32>(7)), Err(7));
+    }
+
+    #[test]
+    fn block_on_notified() {
+        let (tx, rx) = oneshot::channel::<u32>();
+        let sender = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            tx.send(3).unwrap();
+        });
+        assert_eq!(block_on(rx).unwrap(), 3);
+        sender.join().unwrap();
+    }
+}
@@ -3,7 +3,7 @@
 use std::ops::{Deref, DerefMut};
 use crate::core::{self, OclPrm, ClWaitListPtr, ClNullEventPtr, MemMap as MemMapCore, Mem as MemCore, AsMem};
 use crate::standard::{ClWaitListPtrEnum, ClNullEventPtrEnum, Event, EventList, Queue};
-use crate::error::{Result as OclResult};
+use crate::error::{Error as OclError, Result as OclResult};
 
 
 /// An unmap command builder.
@@ -16,6 +16,7 @@ pub struct MemUnmapCmd<'c, T> where T: 'c + OclPrm {
     mem_map: &'c mut MemMap<T>,
     ewait: Option<ClWaitListPtrEnum<'c>>,
     enew: Option<ClNullEventPtrEnum<'c>>,
+    block: bool,
 }
 
 impl<'c, T> MemUnmapCmd<'c, T> where T: OclPrm {
@@ -27,6 +28,7 @@ impl<'c, T> MemUnmapCmd<'c, T> where T: OclPrm {
             mem_map,
             ewait: None,
             enew: None,
+            block: false,
         }
     }
 
@@ -109,10 +111,34 @@ impl<'c, T> MemUnmapCmd<'c, T> where T: OclPrm {
         self
     }
 
+    /// Specifies whether or not `::enq` blocks the current thread until the
+    /// unmap command completes.
+    ///
+    /// Default is `block = false`. A non-blocking unmap only enqueues the
+    /// command: host writes to the mapped region are not guaranteed to be
+    /// visible to the device until the unmap completes. Commands which read
+    /// the buffer must therefore either be enqueued after the unmap on the
+    /// same (in-order) queue or wait on the event passed to `::enew`. When
+    /// blocking, all such writes are visible once `::enq` returns.
+    pub fn block(mut self, block: bool) -> MemUnmapCmd<'c, T> {
+        self.block = block;
+        self
+    }
+
     /// Enqueues this command.
     ///
     pub fn enq(self) -> OclResult<()> {
-        self.mem_map.enqueue_unmap(self.queue, self.ewait, self.enew)
+        if !self.block {
+            return self.mem_map.enqueue_unmap(self.queue, self.ewait, self.enew);
+        }
+
+        let mut event = Event::empty();
+        self.mem_map.enqueue_unmap(self.queue, self.ewait, Some(&mut event))?;
+
+        if let Some(mut enew) = self.enew {
+            unsafe { enew.clone_from(&event) }
+        }
+        event.wait_for().map_err(OclError::from)
     }
 }
 
@@ -134,6 +160,7 @@ pub struct MemMap<T> where T: OclPrm {
     unmap_wait_events: Option<EventList>,
     unmap_event: Option<Event>,
     is_unmapped: bool,
+    block_on_drop: bool,
     // buffer_is_mapped: Arc<AtomicBool>
 }
 
@@ -149,6 +176,7 @@ impl<T> MemMap<T>  where T: OclPrm {
             unmap_wait_events,
             unmap_event,
             is_unmapped: false,
+            block_on_drop: false,
             // buffer_is_mapped,
         }
     }
@@ -206,6 +234,19 @@ impl<T> MemMap<T>  where T: OclPrm {
         }
     }
 
+    /// Specifies whether or not the unmap command enqueued automatically when
+    /// this `MemMap` is dropped (if it has not already been unmapped) blocks
+    /// the current thread until it completes.
+    ///
+    /// Default is `block = false`, in which case dropping never blocks but
+    /// host writes to the mapped region only become visible to commands
+    /// enqueued afterwards on the same (in-order) queue. To obtain an event
+    /// for a non-blocking unmap instead, unmap explicitly using
+    /// `::unmap().enew(..).enq()`. See `MemUnmapCmd::block`.
+    pub fn set_block_on_drop(&mut self, block: bool) {
+        self.block_on_drop = block;
+    }
+
     /// Returns a reference to the unmap target event if it has been set.
     pub fn unmap_event(&self) -> Option<&Event> {
         self.unmap_event.as_ref()
@@ -249,7 +290,14 @@ impl<T> DerefMut for MemMap<T> where T: OclPrm {
 impl<T: OclPrm> Drop for MemMap<T> {
     fn drop(&mut self) {
         if !self.is_unmapped {
-            self.enqueue_unmap::<&Event, &mut Event>(None, None, None).ok();
+            if self.block_on_drop {
+                let mut event = Event::empty();
+                if self.enqueue_unmap::<&Event, _>(None, None, Some(&mut event)).is_ok() {
+                    event.wait_for().ok();
+                }
+            } else {
+                self.enqueue_unmap::<&Event, &mut Event>(None, None, None).ok();
+            }
         }
     }
 }
@@ -1,21 +1,42 @@
 //! Types related to futures and asynchrony.
+//!
+//! All futures here resolve to `ocl::Result` (their `Error` type is
+//! `ocl::Error`), the same type returned by synchronous calls.
+//!
+//! Everything except `MemMap` (also returned by synchronous map commands)
+//! requires the `async` feature (enabled by default).
 
+#[cfg(feature = "async")]
 extern crate qutex;
 
+#[cfg(feature = "async")]
 mod future_mem_map;
+#[cfg(feature = "async")]
 mod order_lock;
+#[cfg(feature = "async")]
 mod rw_vec;
 mod mem_map;
+#[cfg(feature = "async")]
 mod buffer_sink;
+#[cfg(feature = "async")]
 mod buffer_stream;
+#[cfg(feature = "async")]
+mod executor;
 
+#[cfg(feature = "async")]
 pub use self::order_lock::{OrderLock, ReadGuard, WriteGuard, FutureGuard, FutureReadGuard,
     FutureWriteGuard, OrderGuard};
+#[cfg(feature = "async")]
 pub use self::rw_vec::RwVec;
 pub use self::mem_map::MemMap;
+#[cfg(feature = "async")]
 pub use self::future_mem_map::FutureMemMap;
+#[cfg(feature = "async")]
 pub use self::buffer_sink::{BufferSink, FutureFlush, Inner as BufferSinkInner};
+#[cfg(feature = "async")]
 pub use self::buffer_stream::{BufferStream, FutureFlood, Inner as BufferStreamInner};
+#[cfg(feature = "async")]
+pub use self::executor::block_on;
 
 
 // * TODO: Implement this:
@@ -12,8 +12,10 @@ use futures::{Future, Poll, Async};
 use futures::sync::oneshot::{self, Receiver};
 use crate::core::{ClContextPtr, ClNullEventPtr};
 use crate::error::{Error as OclError, Result as OclResult};
-use crate::{Event, EventList};
+use crate::{Event, EventList, Buffer, OclPrm};
 use crate::r#async::qutex::{QrwLock, QrwRequest, RequestKind};
+#[cfg(feature = "ndarray")]
+use ndarray::{ArrayView2, ArrayViewMut2};
 
 
 const PRINT_DEBUG: bool = false;
@@ -105,6 +107,21 @@ impl<V> ReadGuard<V> {
         guard.release_event.as_ref()
     }
 
+    /// Sets the release event (see `::release_event`) complete without
+    /// releasing the lock, allowing any commands waiting on it to proceed.
+    ///
+    /// Call this once finished reading the contained data if the guard must
+    /// be kept alive for longer. Commands waiting on the release event may
+    /// begin executing immediately so the data must not be accessed through
+    /// this guard afterwards. Does nothing if no release event was created
+    /// or if it has already been completed.
+    pub fn set_release_complete(guard: &ReadGuard<V>) -> OclResult<()> {
+        match guard.release_event {
+            Some(ref e) if !e.is_complete()? => e.set_complete().map_err(OclError::from),
+            _ => Ok(()),
+        }
+    }
+
     /// Triggers the release event and releases the lock held by this `ReadGuard`
     /// before returning the original `OrderLock`.
     pub fn release(mut guard: ReadGuard<V>) -> OrderLock<V> {
@@ -126,6 +143,12 @@ impl<V> ReadGuard<V> {
         }
     }
 
+    /// Moves the guarded data out, leaving its default value in its place,
+    /// if no other handles to the lock exist. Returns `None` otherwise.
+    pub(crate) fn take_if_sole_owner(guard: &mut ReadGuard<V>) -> Option<V> where V: Default {
+        guard.order_lock.take_if_sole_owner()
+    }
+
     /// Releases the lock and completes the release event.
     unsafe fn release_components(guard: &mut ReadGuard<V>) {
         guard.order_lock.lock.release_read_lock();
@@ -186,6 +209,21 @@ impl<V> WriteGuard<V> {
         guard.release_event.as_ref()
     }
 
+    /// Sets the release event (see `::release_event`) complete without
+    /// releasing the lock, allowing any commands waiting on it to proceed.
+    ///
+    /// Call this once finished modifying the contained data if the guard must
+    /// be kept alive for longer. Commands waiting on the release event may
+    /// begin executing immediately so the data must not be accessed through
+    /// this guard afterwards. Does nothing if no release event was created
+    /// or if it has already been completed.
+    pub fn set_release_complete(guard: &WriteGuard<V>) -> OclResult<()> {
+        match guard.release_event {
+            Some(ref e) if !e.is_complete()? => e.set_complete().map_err(OclError::from),
+            _ => Ok(()),
+        }
+    }
+
     /// Triggers the release event and releases the lock held by this `WriteGuard`
     /// before returning the original `OrderLock`.
     pub fn release(mut guard: WriteGuard<V>) -> OrderLock<V> {
@@ -207,6 +245,12 @@ impl<V> WriteGuard<V> {
         }
     }
 
+    /// Moves the guarded data out, leaving its default value in its place,
+    /// if no other handles to the lock exist. Returns `None` otherwise.
+    pub(crate) fn take_if_sole_owner(guard: &mut WriteGuard<V>) -> Option<V> where V: Default {
+        guard.order_lock.take_if_sole_owner()
+    }
+
     /// Releases the lock and completes the release event.
     unsafe fn release_components(guard: &mut WriteGuard<V>) {
         guard.order_lock.lock.release_write_lock();
@@ -246,6 +290,85 @@ impl<V> OrderGuard<V> for WriteGuard<V> {
 }
 
 
+/// Returns an error unless `rows * cols` equals `len`.
+#[cfg(feature = "ndarray")]
+fn check_array2_shape(len: usize, rows: usize, cols: usize) -> OclResult<()> {
+    if rows.checked_mul(cols) == Some(len) {
+        Ok(())
+    } else {
+        Err(format!("Invalid array shape: {} rows * {} columns does not equal the length of \
+            the guarded data ({}).", rows, cols, len).into())
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl<T> ReadGuard<Vec<T>> {
+    /// Returns a two dimensional (row-major) view of the guarded `Vec` with
+    /// `rows` rows and `cols` columns, without copying.
+    ///
+    /// Returns an error if `rows * cols` does not equal the length of the
+    /// `Vec`.
+    pub fn as_array2(guard: &ReadGuard<Vec<T>>, rows: usize, cols: usize)
+            -> OclResult<ArrayView2<T>> {
+        check_array2_shape(guard.len(), rows, cols)?;
+        ArrayView2::from_shape((rows, cols), guard.as_slice())
+            .map_err(|err| OclError::from(err.to_string()))
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl<T> WriteGuard<Vec<T>> {
+    /// Returns a two dimensional (row-major) view of the guarded `Vec` with
+    /// `rows` rows and `cols` columns, without copying.
+    ///
+    /// Returns an error if `rows * cols` does not equal the length of the
+    /// `Vec`.
+    pub fn as_array2(guard: &WriteGuard<Vec<T>>, rows: usize, cols: usize)
+            -> OclResult<ArrayView2<T>> {
+        check_array2_shape(guard.len(), rows, cols)?;
+        ArrayView2::from_shape((rows, cols), guard.as_slice())
+            .map_err(|err| OclError::from(err.to_string()))
+    }
+
+    /// Returns a two dimensional (row-major) mutable view of the guarded
+    /// `Vec` with `rows` rows and `cols` columns, without copying.
+    ///
+    /// Returns an error if `rows * cols` does not equal the length of the
+    /// `Vec`.
+    pub fn as_array2_mut(guard: &mut WriteGuard<Vec<T>>, rows: usize, cols: usize)
+            -> OclResult<ArrayViewMut2<T>> {
+        check_array2_shape(guard.len(), rows, cols)?;
+        ArrayViewMut2::from_shape((rows, cols), guard.as_mut_slice())
+            .map_err(|err| OclError::from(err.to_string()))
+    }
+}
+
+
+impl<T: OclPrm> ReadGuard<Vec<T>> {
+    /// Writes the guarded data to `buffer` using its default queue, blocking
+    /// until the write has completed.
+    ///
+    /// See `WriteGuard::sync`.
+    pub fn sync(guard: &ReadGuard<Vec<T>>, buffer: &Buffer<T>) -> OclResult<()> {
+        buffer.write(guard.as_slice()).enq()
+    }
+}
+
+impl<T: OclPrm> WriteGuard<Vec<T>> {
+    /// Writes the guarded data to `buffer` using its default queue, blocking
+    /// until the write has completed.
+    ///
+    /// Releasing a guard only makes host-side changes visible to the next
+    /// holder of the lock, never to a device (see the 'Memory Visibility'
+    /// section of the `RwVec` docs). Call this after mutating the data and
+    /// before dropping the guard to ensure that commands subsequently
+    /// enqueued on the same (in-order) queue, or waiting on the queue, see
+    /// the updated contents of `buffer`.
+    pub fn sync(guard: &WriteGuard<Vec<T>>, buffer: &Buffer<T>) -> OclResult<()> {
+        buffer.write(guard.as_slice()).enq()
+    }
+}
+
 /// The polling stage of a `FutureGuard`.
 #[derive(Debug, PartialEq)]
 enum Stage {
@@ -396,6 +519,25 @@ impl<V, G> FutureGuard<V, G> where G: OrderGuard<V> {
     /// status set to complete, causing those commands to execute. This can be
     /// used to inject host side code in amongst OpenCL commands without
     /// thread blocking or extra delays of any kind.
+    ///
+    /// The event is a user event associated with `context`. It may be passed
+    /// directly to the wait list of any command (e.g. `KernelCmd::ewait`)
+    /// enqueued on a queue belonging to that same context. Using it with a
+    /// queue from another context is an error (`CL_INVALID_CONTEXT`).
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// let mut future_guard = rw_vec.clone().write();
+    /// let release_event = future_guard.create_release_event(&queue.context())?.clone();
+    ///
+    /// // The kernel will not run until the host is done with `rw_vec`:
+    /// unsafe { kernel.cmd().queue(&queue).ewait(&release_event).enq()?; }
+    ///
+    /// let mut guard = future_guard.wait()?;
+    /// guard[0] = 1;
+    /// // Dropping (or releasing) the guard triggers the release event:
+    /// drop(guard);
+    /// ```
     pub fn create_release_event<C: ClContextPtr>(&mut self, context: C) -> OclResult<&Event> {
         assert!(self.release_event.is_none(), "Release event has already been created.");
         self.release_event = Some(Event::user(context)?);
@@ -439,6 +581,8 @@ impl<V, G> FutureGuard<V, G> where G: OrderGuard<V> {
 
     /// Blocks the current thread until the OpenCL command is complete and an
     /// appropriate lock can be obtained on the underlying data.
+    ///
+    /// Equivalent to `ocl::block_on(self)`.
     pub fn wait(self) -> OclResult<G> {
         <Self as Future>::wait(self)
     }
@@ -773,6 +917,32 @@ impl<V> FutureGuard<V, ReadGuard<V>> {
     }
 }
 
+impl<T> FutureGuard<Vec<T>, ReadGuard<Vec<T>>> where T: Clone {
+    /// Returns a future which resolves to the guarded `Vec` itself rather
+    /// than a guard, releasing the lock as soon as the data is obtained.
+    ///
+    /// If no other references to the lock (such as clones of the `RwVec`)
+    /// exist, the `Vec` is moved out without copying, leaving an empty `Vec`
+    /// in its place. Otherwise it is cloned.
+    pub fn into_data_future(self) -> impl Future<Item = Vec<T>, Error = OclError> {
+        // Dropping the guard releases the lock and completes any release
+        // event:
+        self.map(|mut guard| ReadGuard::take_if_sole_owner(&mut guard)
+            .unwrap_or_else(|| (*guard).clone()))
+    }
+}
+
+impl<T> FutureGuard<Vec<T>, WriteGuard<Vec<T>>> where T: Clone {
+    /// Returns a future which resolves to the guarded `Vec` itself rather
+    /// than a guard, releasing the lock as soon as the data is obtained.
+    ///
+    /// See `FutureReadGuard::into_data_future`.
+    pub fn into_data_future(self) -> impl Future<Item = Vec<T>, Error = OclError> {
+        self.map(|mut guard| WriteGuard::take_if_sole_owner(&mut guard)
+            .unwrap_or_else(|| (*guard).clone()))
+    }
+}
+
 
 /// A lock with conserved global order which interoperates with OpenCL events
 /// and Rust futures to provide exclusive access to data.
@@ -819,6 +989,55 @@ impl<V> OrderLock<V> {
         FutureGuard::new(self.into(), rx)
     }
 
+    /// Attempts to obtain a read lock immediately, returning `None` if the
+    /// lock is currently write-locked or if other requests are already
+    /// queued ahead of this one.
+    ///
+    /// Never blocks and does not involve any OpenCL events. The returned
+    /// guard has no release event.
+    pub fn try_read(&self) -> Option<ReadGuard<V>> {
+        print_debug(self.id(), "OrderLock::try_read: Read lock requested");
+        self.try_lock(RequestKind::Read).map(|ol| ReadGuard::new(ol, None))
+    }
+
+    /// Attempts to obtain a write lock immediately, returning `None` if the
+    /// lock is currently locked (for reading or writing) or if other requests
+    /// are already queued ahead of this one.
+    ///
+    /// Never blocks and does not involve any OpenCL events. The returned
+    /// guard has no release event.
+    pub fn try_write(&self) -> Option<WriteGuard<V>> {
+        print_debug(self.id(), "OrderLock::try_write: Write lock requested");
+        self.try_lock(RequestKind::Write).map(|ol| WriteGuard::new(ol, None))
+    }
+
+    /// Moves the contained data out, leaving its default value in its place,
+    /// if this is the only handle to the lock. Returns `None` otherwise.
+    fn take_if_sole_owner(&mut self) -> Option<V> where V: Default {
+        self.lock.get_mut().map(::std::mem::take)
+    }
+
+    /// Enqueues a lock request, processes the queue once, then withdraws the
+    /// request if it was not granted.
+    fn try_lock(&self, kind: RequestKind) -> Option<OrderLock<V>> {
+        let (tx, mut rx) = oneshot::channel();
+
+        unsafe {
+            self.lock.enqueue_lock_request(QrwRequest::new(tx, kind));
+            self.lock.process_queues();
+        }
+
+        // Closing the receiver before checking it ensures that the request
+        // can not be fulfilled after we have given up on it (the queue skips
+        // requests whose receiver has been dropped).
+        rx.close();
+
+        match rx.try_recv() {
+            Ok(Some(())) => Some(self.clone()),
+            _ => None,
+        }
+    }
+
     /// Returns a reference to the inner value.
     ///
     #[inline]
@@ -8,7 +8,9 @@
 
 
 use std::ops::{Deref, DerefMut};
-use crate::r#async::{OrderLock, FutureGuard, ReadGuard, WriteGuard};
+use crate::{Buffer, OclPrm};
+use crate::error::{Result as OclResult};
+use crate::r#async::{OrderLock, FutureGuard, FutureWriteGuard, ReadGuard, WriteGuard};
 use crate::r#async::qutex::QrwLock;
 
 
@@ -23,6 +25,22 @@ use crate::r#async::qutex::QrwLock;
 /// Calling `::read` or `::write` returns a future which will resolve into a
 /// `RwGuard`.
 ///
+/// ## Memory Visibility
+///
+/// The data of an `RwVec` lives in host memory only and is never implicitly
+/// copied to or from a device. Dropping a guard makes host-side changes
+/// visible to the next holder of the lock but has no effect on any buffer.
+/// Changes reach a device only through a command which reads from the
+/// `RwVec`, either:
+///
+/// * a future write (`buffer.write(rw_vec.read()).enq_async()`), which holds
+///   the lock until the write command has completed, or
+/// * `WriteGuard::sync` (or `ReadGuard::sync`), which performs a blocking
+///   write while a guard is already held.
+///
+/// Likewise, device-side changes are only visible in the `RwVec` after a
+/// read command into it has completed.
+///
 /// ## Platform Compatibility
 ///
 /// Some CPU device/platform combinations have synchronization problems when
@@ -57,6 +75,18 @@ impl<T> RwVec<T> {
         self.lock.write()
     }
 
+    /// Returns a `WriteGuard` if the lock can be obtained immediately or
+    /// `None` if it is contended.
+    ///
+    /// Unlike `::write`, this never blocks, waits on events, or returns a
+    /// future. Useful for opportunistic updates (e.g. 'update the data if it
+    /// is free right now, otherwise skip this frame').
+    ///
+    /// See `OrderLock::try_write`.
+    pub fn try_lock(&self) -> Option<WriteGuard<Vec<T>>> {
+        self.lock.try_write()
+    }
+
     /// Returns a mutable slice into the contained `Vec`.
     ///
     /// Used by buffer command builders when preparing future read and write
@@ -94,6 +124,22 @@ impl<T> RwVec<T> {
     }
 }
 
+impl<T: OclPrm> RwVec<T> {
+    /// Returns a new `RwVec` with the same length as `buffer` along with a
+    /// future write guard representing a non-blocking read of the contents
+    /// of `buffer` into it.
+    ///
+    /// The read is enqueued on the default queue of `buffer`.
+    ///
+    /// The returned `RwVec` remains write-locked until the read has completed
+    /// and the guard (obtained by polling the returned future) is dropped.
+    pub fn from_buffer(buffer: &Buffer<T>) -> OclResult<(RwVec<T>, FutureWriteGuard<Vec<T>>)> {
+        let rw_vec = RwVec::from(vec![T::default(); buffer.len()]);
+        let future_read = buffer.cmd().read(&rw_vec).enq_async()?;
+        Ok((rw_vec, future_read))
+    }
+}
+
 impl<T> From<QrwLock<Vec<T>>> for RwVec<T> {
     fn from(q: QrwLock<Vec<T>>) -> RwVec<T> {
         RwVec { lock: OrderLock::from(q) }
@@ -1,17 +1,27 @@
-//! Standard error type for ocl futures.
+//! Standard error and result types for ocl.
 //!
+//! The same `Error` and `Result` are used throughout the crate, by both
+//! synchronous calls and the futures in the `async` module (whose `Error`
+//! associated type is always `Error`). Errors from `ocl-core`, from
+//! cancelled futures channels and from the various builders convert into
+//! `Error` via `From`, so `?` can be used freely when mixing the two.
 
 use std;
 // use std::sync::mpsc::{SendError as StdMpscSendError, RecvError as StdMpscRecvError};
 use failure::{Context, Fail, Backtrace};
+#[cfg(feature = "async")]
 use futures::sync::oneshot::Canceled as OneshotCanceled;
+#[cfg(feature = "async")]
 use futures::sync::mpsc::SendError;
 use crate::core::error::{Error as OclCoreError};
-use crate::core::Status;
-use crate::standard::{DeviceError, PlatformError, KernelError};
+use crate::core::{Status, ErrorKind as OclCoreErrorKind, ProgramBuildError, ProgramBuildStatus};
+use crate::standard::{DeviceError, PlatformError, KernelError, Diagnostic, parse_build_log,
+    Device};
 
-use crate::BufferCmdError;
+use crate::{BufferCmdError, BufferError};
 
+/// The crate-wide result type, used by both synchronous and asynchronous
+/// operations.
 pub type Result<T> = std::result::Result<T, Error>;
 
 
@@ -28,11 +38,14 @@ pub enum ErrorKind {
     // StdMpscSend(String),
     // #[fail(display = "{}", _0)]
     // StdMpscRecv(StdMpscRecvError),
+    #[cfg(feature = "async")]
     #[fail(display = "{}", _0)]
     OneshotCanceled(#[cause] OneshotCanceled),
     #[fail(display = "{}", _0)]
     BufferCmd(BufferCmdError),
     #[fail(display = "{}", _0)]
+    Buffer(BufferError),
+    #[fail(display = "{}", _0)]
     Device(DeviceError),
     #[fail(display = "{}", _0)]
     Platform(PlatformError),
@@ -55,6 +68,44 @@ impl Error {
         }
     }
 
+    /// Returns the program build log if this error was caused by a failed
+    /// program build.
+    pub fn build_log(&self) -> Option<&str> {
+        self.program_build_error().and_then(ProgramBuildError::build_log)
+    }
+
+    /// Returns the build status and build log of each device, as `(device,
+    /// status, log)`, if this error was caused by a failed program build.
+    ///
+    /// Useful in heterogeneous contexts, where a program may build
+    /// successfully for some devices and fail for others, to determine
+    /// exactly which devices rejected it.
+    pub fn build_statuses(&self) -> Option<Vec<(Device, ProgramBuildStatus, String)>> {
+        self.program_build_error().and_then(ProgramBuildError::device_build_logs)
+            .map(|device_logs| device_logs.iter()
+                .map(|&(device, status, ref log)| (Device::from(device), status, log.clone()))
+                .collect())
+    }
+
+    /// Returns the contained program build error, if any.
+    fn program_build_error(&self) -> Option<&ProgramBuildError> {
+        match *self.kind() {
+            ErrorKind::OclCore(ref err) => match *err.kind() {
+                OclCoreErrorKind::ProgramBuild(ref err) => Some(err),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the diagnostics parsed from the program build log if this
+    /// error was caused by a failed program build.
+    ///
+    /// See [`parse_build_log`](../fn.parse_build_log.html).
+    pub fn build_diagnostics(&self) -> Option<Vec<Diagnostic>> {
+        self.build_log().map(parse_build_log)
+    }
+
     /// Returns the error variant and contents.
     pub fn kind(&self) -> &ErrorKind {
         self.inner.get_context()
@@ -95,6 +146,7 @@ impl From<OclCoreError> for Error {
     }
 }
 
+#[cfg(feature = "async")]
 impl<T> From<SendError<T>> for Error {
     fn from(err: SendError<T>) -> Error {
         let debug = format!("{:?}", err);
@@ -119,6 +171,7 @@ impl<T> From<SendError<T>> for Error {
 //     }
 // }
 
+#[cfg(feature = "async")]
 impl From<OneshotCanceled> for Error {
     fn from(err: OneshotCanceled) -> Error {
         Error { inner: Context::new(ErrorKind::OneshotCanceled(err)) }
@@ -157,6 +210,12 @@ impl From<BufferCmdError> for Error {
     }
 }
 
+impl From<BufferError> for Error {
+    fn from(err: BufferError) -> Error {
+        Error { inner: Context::new(ErrorKind::Buffer(err)) }
+    }
+}
+
 impl From<DeviceError> for Error {
     fn from(err: DeviceError) -> Error {
         Error { inner: Context::new(ErrorKind::Device(err)) }
@@ -49,10 +49,13 @@
 // #![warn(missing_docs)]
 
 extern crate num_traits;
+#[cfg(feature = "async")]
 extern crate futures;
 #[macro_use]
 extern crate failure;
 pub extern crate ocl_core as core;
+#[cfg(feature = "ndarray")]
+pub extern crate ndarray;
 
 
 #[cfg(test)]
@@ -62,9 +65,15 @@ pub mod error;
 pub mod r#async;
 
 pub use self::standard::{Platform, Extensions, Device, Context, Program, Queue, Kernel, Buffer, Image, Event,
-    EventList, EventArray, Sampler, SpatialDims, ProQue, BufferCmdError};
-pub use self::r#async::{MemMap, FutureMemMap, RwVec, ReadGuard, WriteGuard,
-    FutureReadGuard, FutureWriteGuard};
+    EventList, EventArray, Sampler, SpatialDims, ProQue, BufferCmdError, BufferError, Diagnostic,
+    DiagnosticSeverity, parse_build_log, DeviceDescription, Partition, SubDevice, with_default,
+    LwsStrategy, ArgDescriptor, KernelTimingStats, WeakBuffer, ProfilingSummary};
+#[cfg(feature = "async")]
+pub use self::standard::FutureBuild;
+pub use self::r#async::MemMap;
+#[cfg(feature = "async")]
+pub use self::r#async::{FutureMemMap, RwVec, ReadGuard, WriteGuard, FutureReadGuard,
+    FutureWriteGuard, block_on};
 pub use crate::error::{Error, Result};
 pub use crate::{core::Error as OclCoreError};
 #[doc(no_inline)]
@@ -72,7 +81,8 @@ pub use crate::core::ffi;
 #[doc(no_inline)]
 pub use crate::core::util;
 #[doc(no_inline)]
-pub use crate::core::{OclPrm, OclScl, OclVec, DeviceType, CommandQueueProperties, MemFlags, MapFlags};
+pub use crate::core::{OclPrm, OclScl, OclVec, ScalarType, DeviceType, CommandQueueProperties,
+    MemFlags, MapFlags, OpenclVersion};
 
 
 pub mod prm {
@@ -125,8 +135,8 @@ pub mod prm {
 pub mod traits {
     //! Commonly used traits.
 
-    pub use crate::standard::{WorkDims, MemLen, IntoMarker, IntoRawEventArray};
-    pub use crate::core::{OclPrm, OclScl, OclVec};
+    pub use crate::standard::{WorkDims, MemLen, IntoMarker, IntoRawEventArray, KernelArgs};
+    pub use crate::core::{OclPrm, OclNum, OclScl, OclVec};
 }
 
 pub mod builders {
@@ -3,13 +3,18 @@
 use std;
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut, Range};
-use crate::core::{self, Error as OclCoreError, Result as OclCoreResult, OclPrm, Mem as MemCore,
-    MemFlags, MemInfo, MemInfoResult, BufferRegion, MapFlags, AsMem, MemCmdRw, MemCmdAll,
-    ClNullEventPtr};
-use crate::{Context, Queue, FutureMemMap, MemMap, Event, RwVec, FutureReadGuard, FutureWriteGuard,
-    SpatialDims};
+use std::sync::{Arc, Mutex, Weak};
+use crate::core::{self, Error as OclCoreError, Result as OclCoreResult, OclPrm, OclScl,
+    Mem as MemCore, MemFlags, MemInfo, MemInfoResult, BufferRegion, MapFlags, AsMem, MemCmdRw,
+    MemCmdAll, Status};
+#[cfg(feature = "async")]
+use crate::core::ClNullEventPtr;
+use crate::{Context, Queue, MemMap, Event, SpatialDims};
+#[cfg(feature = "async")]
+use crate::{FutureMemMap, RwVec, FutureReadGuard, FutureWriteGuard};
 use crate::standard::{ClNullEventPtrEnum, ClWaitListPtrEnum, HostSlice};
 use crate::error::{Error as OclError, Result as OclResult};
+use crate::ffi::cl_mem;
 
 #[cfg(not(feature="opencl_vendor_mesa"))]
 use crate::ffi::cl_GLuint;
@@ -27,6 +32,97 @@ fn check_len(mem_len: usize, data_len: usize, offset: usize) -> OclResult<()> {
 }
 
 
+/// Checks that a rectangular region, with an origin and region expressed in
+/// elements of `elem_size` bytes (x) and rows/slices (y, z), lies entirely
+/// within a buffer of `buffer_size` bytes.
+///
+/// Pitches of zero are resolved as OpenCL does: a row pitch of zero is
+/// `region[0]` elements and a slice pitch of zero is `region[1]` rows.
+///
+/// An empty region (containing a zero dimension) or one whose end can not be
+/// represented is also out of bounds, with an `end_bytes` of `usize::MAX`.
+fn check_rect_bounds(which: &'static str, origin: [usize; 3], region: [usize; 3],
+        row_pitch_bytes: usize, slc_pitch_bytes: usize, elem_size: usize, buffer_size: usize)
+        -> Result<(), BufferCmdError> {
+    // One past the last byte touched:
+    let checked_end = || -> Option<usize> {
+        let row_pitch = if row_pitch_bytes == 0 {
+            region[0].checked_mul(elem_size)?
+        } else {
+            row_pitch_bytes
+        };
+        let slc_pitch = if slc_pitch_bytes == 0 {
+            region[1].checked_mul(row_pitch)?
+        } else {
+            slc_pitch_bytes
+        };
+
+        let x_end = origin[0].checked_add(region[0])?.checked_mul(elem_size)?;
+        let y_last = origin[1].checked_add(region[1])?.checked_sub(1)?.checked_mul(row_pitch)?;
+        let z_last = origin[2].checked_add(region[2])?.checked_sub(1)?.checked_mul(slc_pitch)?;
+        x_end.checked_add(y_last)?.checked_add(z_last)
+    };
+
+    let end_bytes = if region.contains(&0) { None } else { checked_end() };
+
+    match end_bytes {
+        Some(end_bytes) if end_bytes <= buffer_size => Ok(()),
+        end_bytes => Err(BufferCmdError::RectOutOfBounds { which, origin, region,
+            end_bytes: end_bytes.unwrap_or(usize::MAX), buffer_size }),
+    }
+}
+
+
+/// Returns an error if an explicit (non-zero) source or destination row or
+/// slice pitch is too small to contain a row or slice of `region`.
+///
+/// `pitches` contains, in order, the source row and slice pitches and the
+/// destination row and slice pitches, in bytes.
+fn check_rect_pitches<T>(region: [usize; 3], pitches: [usize; 4]) -> Result<(), BufferCmdError> {
+    let sides = [("source", pitches[0], pitches[1]), ("destination", pitches[2], pitches[3])];
+    let row_bytes = region[0] * ::std::mem::size_of::<T>();
+
+    for &(which, row_pitch_bytes, slc_pitch_bytes) in sides.iter() {
+        if row_pitch_bytes != 0 && row_pitch_bytes < row_bytes {
+            return Err(BufferCmdError::RectPitchTooSmall { which, pitch: "row",
+                pitch_bytes: row_pitch_bytes, min_bytes: row_bytes });
+        }
+
+        let row_pitch = if row_pitch_bytes == 0 { row_bytes } else { row_pitch_bytes };
+        let slc_bytes = region[1] * row_pitch;
+        if slc_pitch_bytes != 0 && slc_pitch_bytes < slc_bytes {
+            return Err(BufferCmdError::RectPitchTooSmall { which, pitch: "slice",
+                pitch_bytes: slc_pitch_bytes, min_bytes: slc_bytes });
+        }
+    }
+    Ok(())
+}
+
+/// Converts the opaque `CL_INVALID_OPERATION` error returned when the host
+/// reads, writes, or maps a buffer created with `MEM_HOST_NO_ACCESS`,
+/// `MEM_HOST_READ_ONLY`, or `MEM_HOST_WRITE_ONLY` into a
+/// `BufferCmdError::HostAccessDenied`. Other errors are returned unchanged.
+fn host_access_err<T: OclPrm>(buffer: &Buffer<T>, op: &'static str, err: OclError) -> OclError {
+    if err.api_status() != Some(Status::CL_INVALID_OPERATION) { return err; }
+
+    let flags = match buffer.flags() {
+        Ok(f) => f & (MemFlags::HOST_NO_ACCESS | MemFlags::HOST_READ_ONLY
+            | MemFlags::HOST_WRITE_ONLY),
+        Err(_) => return err,
+    };
+
+    let denied = flags.contains(MemFlags::HOST_NO_ACCESS)
+        || (op == "read" && flags.contains(MemFlags::HOST_WRITE_ONLY))
+        || (op == "write" && flags.contains(MemFlags::HOST_READ_ONLY));
+
+    if denied {
+        BufferCmdError::HostAccessDenied { op, flags }.into()
+    } else {
+        err
+    }
+}
+
+
 /// A buffer command error.
 #[derive(Debug, Fail)]
 pub enum BufferCmdError {
@@ -40,6 +136,22 @@ pub enum BufferCmdError {
     #[fail(display = "Unable to map this buffer. Must create with either the \
         MEM_USE_HOST_PTR or MEM_ALLOC_HOST_PTR flag.")]
     MapUnavailable,
+    #[fail(display = "Map range out of bounds: [{}, {} + {}) exceeds the buffer length ({}).",
+        offset, offset, len, buffer_len)]
+    RangeOutOfBounds { offset: usize, len: usize, buffer_len: usize },
+    #[fail(display = "Host {} access denied: The buffer was created with the following flags \
+        which restrict host access: {:?}.", op, flags)]
+    HostAccessDenied { op: &'static str, flags: MemFlags },
+    #[fail(display = "Rectangular region out of bounds: The {} region (origin: {:?}, region: \
+        {:?}) ends at byte {} which exceeds the buffer size ({} bytes).", which, origin, region,
+        end_bytes, buffer_size)]
+    RectOutOfBounds { which: &'static str, origin: [usize; 3], region: [usize; 3],
+        end_bytes: usize, buffer_size: usize },
+    #[fail(display = "Rectangular pitch too small: The {} {} pitch ({} bytes) is smaller than \
+        the {} it must contain ({} bytes). Use 'None' for the tightly packed default.", which,
+        pitch, pitch_bytes, pitch, min_bytes)]
+    RectPitchTooSmall { which: &'static str, pitch: &'static str, pitch_bytes: usize,
+        min_bytes: usize },
     #[fail(display = "ocl-core error: {}", _0)]
     Ocl(#[cause] OclCoreError)
 }
@@ -52,6 +164,18 @@ impl From<OclCoreError> for BufferCmdError {
 }
 
 
+/// A buffer creation error.
+#[derive(Debug, Fail)]
+pub enum BufferError {
+    #[fail(display = "Buffer too large: The requested size ({} bytes) exceeds the maximum \
+        memory allocation size of device '{}' ({} bytes).", size, device_name, max_alloc_size)]
+    TooLarge { size: u64, max_alloc_size: u64, device_name: String },
+    #[fail(display = "Buffer shape mismatch: The shape {:?} ({} elements) does not match the \
+        length of the buffer ({} elements).", shape, shape_len, len)]
+    ShapeMismatch { shape: SpatialDims, shape_len: usize, len: usize },
+}
+
+
 /// A queue or context reference.
 #[derive(Debug, Clone)]
 pub enum QueCtx<'o> {
@@ -243,8 +367,9 @@ impl<'c, T> BufferCmd<'c, T> where T: 'c + OclPrm {
     /// access to this mapped region. Accessing memory via a [`MemMap`] is
     /// exactly like using a [slice].
     ///
-    /// If `.block(..)` has been set it will be ignored. Non-blocking map
-    /// commands are enqueued using `::enq_async`.
+    /// If `.block(..)` has been set it will be ignored. Use
+    /// `BufferMapCmd::block` or `::enq_async` to enqueue a non-blocking map
+    /// command.
     ///
     /// ## Panics
     ///
@@ -262,6 +387,7 @@ impl<'c, T> BufferCmd<'c, T> where T: 'c + OclPrm {
         assert!(self.kind.is_unspec(), "ocl::BufferCmd::write(): Operation kind \
             already set for this command.");
         self.kind = BufferCmdKind::Map;
+        self.block = true;
         BufferMapCmd { cmd: self, flags: None, len: None }
     }
 
@@ -359,6 +485,10 @@ impl<'c, T> BufferCmd<'c, T> where T: 'c + OclPrm {
     /// elements of a buffer, `pattern` would be a `cl_float4` and `len` would
     /// be 400.
     ///
+    /// On devices which do not support OpenCL 1.2 (`clEnqueueFillBuffer`),
+    /// the pattern is instead repeated into a temporary host vector which is
+    /// written to the buffer with a blocking write.
+    ///
     /// ## Panics
     ///
     /// The command operation kind must not have already been specified
@@ -426,20 +556,33 @@ impl<'c, T> BufferCmd<'c, T> where T: 'c + OclPrm {
     /// Specifies that this will be a rectangularly shaped operation
     /// (the default being linear).
     ///
-    /// Row and slice pitches must all be expressed in bytes.
+    /// The x components of the origins and `region` are expressed in
+    /// elements, the y and z components in rows and slices. Row and slice
+    /// pitches must all be expressed in bytes. A pitch of `None` (or
+    /// `Some(0)`) selects the driver default, a tightly packed layout where
+    /// the row pitch is `region[0] * size_of::<T>()` and the slice pitch is
+    /// `region[1]` times the row pitch.
+    ///
+    /// Enqueuing returns `BufferCmdError::RectPitchTooSmall` if an explicit
+    /// row pitch is smaller than a row of `region` or an explicit slice pitch
+    /// is smaller than `region[1]` rows.
     ///
     /// Only valid for 'read', 'write', and 'copy' modes. Will error if used
     /// with any other mode.
-    pub fn rect(mut self, src_origin: [usize; 3], dst_origin: [usize; 3], region: [usize; 3],
-                src_row_pitch_bytes: usize, src_slc_pitch_bytes: usize, dst_row_pitch_bytes: usize,
-                dst_slc_pitch_bytes: usize) -> BufferCmd<'c, T> {
+    pub fn rect<P0, P1, P2, P3>(mut self, src_origin: [usize; 3], dst_origin: [usize; 3], region: [usize; 3],
+                src_row_pitch_bytes: P0, src_slc_pitch_bytes: P1, dst_row_pitch_bytes: P2,
+                dst_slc_pitch_bytes: P3) -> BufferCmd<'c, T>
+            where P0: Into<Option<usize>>, P1: Into<Option<usize>>, P2: Into<Option<usize>>,
+                P3: Into<Option<usize>> {
         if let BufferCmdDataShape::Lin { offset } = self.shape {
             assert!(offset == 0, "ocl::BufferCmd::rect(): This command builder has already been \
                 set to linear mode with '::offset`. You cannot call both '::offset' and '::rect'.");
         }
         self.shape = BufferCmdDataShape::Rect { src_origin, dst_origin, region,
-            src_row_pitch_bytes, src_slc_pitch_bytes,
-            dst_row_pitch_bytes, dst_slc_pitch_bytes };
+            src_row_pitch_bytes: src_row_pitch_bytes.into().unwrap_or(0),
+            src_slc_pitch_bytes: src_slc_pitch_bytes.into().unwrap_or(0),
+            dst_row_pitch_bytes: dst_row_pitch_bytes.into().unwrap_or(0),
+            dst_slc_pitch_bytes: dst_slc_pitch_bytes.into().unwrap_or(0) };
         self
     }
 
@@ -537,6 +680,8 @@ impl<'c, T> BufferCmd<'c, T> where T: 'c + OclPrm {
                         src_row_pitch_bytes, src_slc_pitch_bytes, dst_row_pitch_bytes,
                         dst_slc_pitch_bytes } =>
                     {
+                        check_rect_pitches::<T>(region, [src_row_pitch_bytes, src_slc_pitch_bytes,
+                            dst_row_pitch_bytes, dst_slc_pitch_bytes])?;
                         if dst_offset.is_some() || len.is_some() { return Err(
                             "ocl::BufferCmd::enq(): For 'rect' shaped copies, destination \
                             offset and length must be 'None'. Ex.: \
@@ -562,6 +707,18 @@ impl<'c, T> BufferCmd<'c, T> where T: 'c + OclPrm {
 
                         check_len(self.mem_len, len, offset)?;
 
+                        // `clEnqueueFillBuffer` requires OpenCL 1.2. Write a
+                        // host-side copy of the pattern instead on older
+                        // devices, blocking since the copy is temporary:
+                        if queue.device_version() < core::OpenclVersion::new(1, 2) {
+                            let src = vec![pattern; len];
+                            return unsafe {
+                                core::enqueue_write_buffer(queue, &self.buffer.obj_core, true,
+                                    offset, &src, self.ewait, self.enew)
+                                    .map_err(OclError::from)
+                            };
+                        }
+
                         core::enqueue_fill_buffer(queue, &self.buffer.obj_core, pattern,
                             offset, len, self.ewait, self.enew, Some(&queue.device_version()))
                             .map_err(OclError::from)
@@ -596,7 +753,9 @@ impl<'c, T> BufferCmd<'c, T> where T: 'c + OclPrm {
 /// The data destination for a buffer read command.
 pub enum ReadDst<'d, T> where T: 'd {
     Slice(&'d mut [T]),
+    #[cfg(feature = "async")]
     RwVec(RwVec<T>),
+    #[cfg(feature = "async")]
     Writer(FutureWriteGuard<Vec<T>>),
     None,
 }
@@ -608,7 +767,9 @@ impl<'d, T> ReadDst<'d, T> {
 
     pub fn len(&self) -> usize {
         match *self {
+            #[cfg(feature = "async")]
             ReadDst::RwVec(ref rw_vec) => rw_vec.len_stale(),
+            #[cfg(feature = "async")]
             ReadDst::Writer(ref writer) => unsafe { (*writer.as_ptr()).len() },
             ReadDst::Slice(ref slice) => slice.len(),
             ReadDst::None => 0,
@@ -628,18 +789,21 @@ impl<'d, T> From<&'d mut Vec<T>> for ReadDst<'d, T>  where T: OclPrm {
     }
 }
 
+#[cfg(feature = "async")]
 impl<'d, T> From<RwVec<T>> for ReadDst<'d, T> where T: OclPrm {
     fn from(rw_vec: RwVec<T>) -> ReadDst<'d, T> {
         ReadDst::RwVec(rw_vec)
     }
 }
 
+#[cfg(feature = "async")]
 impl<'a, 'd, T> From<&'a RwVec<T>> for ReadDst<'d, T> where T: OclPrm {
     fn from(rw_vec: &'a RwVec<T>) -> ReadDst<'d, T> {
         ReadDst::RwVec(rw_vec.clone())
     }
 }
 
+#[cfg(feature = "async")]
 impl<'d, T> From<FutureWriteGuard<Vec<T>>> for ReadDst<'d, T> where T: OclPrm {
     fn from(writer: FutureWriteGuard<Vec<T>>) -> ReadDst<'d, T> {
         ReadDst::Writer(writer)
@@ -741,12 +905,17 @@ impl<'c, 'd, T> BufferReadCmd<'c, 'd, T> where T: OclPrm {
     /// Specifies that this will be a rectangularly shaped operation
     /// (the default being linear).
     ///
-    /// Row and slice pitches must all be expressed in bytes.
+    /// Row and slice pitches must all be expressed in bytes. `None` selects
+    /// the tightly packed default (see [`BufferCmd::rect`]).
     ///
     /// Panics if `:offset`, `dst_offset`, or `::len` have been called.
-    pub fn rect(mut self, src_origin: [usize; 3], dst_origin: [usize; 3], region: [usize; 3],
-                src_row_pitch_bytes: usize, src_slc_pitch_bytes: usize, dst_row_pitch_bytes: usize,
-                dst_slc_pitch_bytes: usize) -> BufferReadCmd<'c, 'd, T> {
+    ///
+    /// [`BufferCmd::rect`]: struct.BufferCmd.html#method.rect
+    pub fn rect<P0, P1, P2, P3>(mut self, src_origin: [usize; 3], dst_origin: [usize; 3], region: [usize; 3],
+                src_row_pitch_bytes: P0, src_slc_pitch_bytes: P1, dst_row_pitch_bytes: P2,
+                dst_slc_pitch_bytes: P3) -> BufferReadCmd<'c, 'd, T>
+            where P0: Into<Option<usize>>, P1: Into<Option<usize>>, P2: Into<Option<usize>>,
+                P3: Into<Option<usize>> {
         if let BufferCmdDataShape::Lin { offset } = self.cmd.shape {
             assert!(offset == 0, "ocl::BufferCmd::rect(): This command builder has already been \
                 set to linear mode with '::offset`. You cannot call both '::offset' and '::rect'.");
@@ -756,8 +925,10 @@ impl<'c, 'd, T> BufferReadCmd<'c, 'd, T> where T: OclPrm {
         }
 
         self.cmd.shape = BufferCmdDataShape::Rect { src_origin, dst_origin, region,
-            src_row_pitch_bytes, src_slc_pitch_bytes,
-            dst_row_pitch_bytes, dst_slc_pitch_bytes };
+            src_row_pitch_bytes: src_row_pitch_bytes.into().unwrap_or(0),
+            src_slc_pitch_bytes: src_slc_pitch_bytes.into().unwrap_or(0),
+            dst_row_pitch_bytes: dst_row_pitch_bytes.into().unwrap_or(0),
+            dst_slc_pitch_bytes: dst_slc_pitch_bytes.into().unwrap_or(0) };
 
         self
     }
@@ -841,6 +1012,7 @@ impl<'c, 'd, T> BufferReadCmd<'c, 'd, T> where T: OclPrm {
     //
     // NOTE: Could use deferred initialization for the guard slice instead of closure.
     pub fn enq(mut self) -> OclResult<()> {
+        let buffer = self.cmd.buffer;
         let read_dst = self.dst.take();
         let range = self.range.clone();
         if range.end > read_dst.len() { return Err(OclError::from(
@@ -866,7 +1038,8 @@ impl<'c, 'd, T> BufferReadCmd<'c, 'd, T> where T: OclPrm {
                         BufferCmdDataShape::Rect { src_origin, dst_origin, region, src_row_pitch_bytes,
                                 src_slc_pitch_bytes, dst_row_pitch_bytes, dst_slc_pitch_bytes } =>
                         {
-                            // TODO: Verify dims given (like `::check_len`).
+                            check_rect_pitches::<T>(region, [src_row_pitch_bytes, src_slc_pitch_bytes,
+                                dst_row_pitch_bytes, dst_slc_pitch_bytes])?;
                             unsafe {
                                 core::enqueue_read_buffer_rect(queue, &self.cmd.buffer.obj_core,
                                     self.cmd.block, src_origin, dst_origin, region,
@@ -881,22 +1054,26 @@ impl<'c, 'd, T> BufferReadCmd<'c, 'd, T> where T: OclPrm {
             }
         };
 
-        match read_dst {
+        let result = match read_dst {
             ReadDst::Slice(slice) => {
                 enqueue_with_data(&mut slice[range])
             },
+            #[cfg(feature = "async")]
             ReadDst::RwVec(rw_vec) => {
                 let mut guard = rw_vec.write().wait()
                     .map_err(|_| OclError::from("Unable to obtain lock."))?;
                 enqueue_with_data(&mut guard.as_mut_slice()[range])
             },
+            #[cfg(feature = "async")]
             ReadDst::Writer(writer) => {
                 let mut guard = writer.wait()
                     .map_err(|_| OclError::from("Unable to obtain lock."))?;
                 enqueue_with_data(&mut guard.as_mut_slice()[range])
             }
             ReadDst::None => panic!("Invalid read destination."),
-        }
+        };
+
+        result.map_err(|err| host_access_err(buffer, "read", err))
     }
 
     /// Enqueues this command and returns a future representing its completion
@@ -906,6 +1083,8 @@ impl<'c, 'd, T> BufferReadCmd<'c, 'd, T> where T: OclPrm {
     /// A data destination container appropriate for an asynchronous operation
     /// (such as `RwVec`) must have been passed to `::read`.
     ///
+    /// Requires the `async` feature.
+    #[cfg(feature = "async")]
     pub fn enq_async(mut self) -> OclResult<FutureWriteGuard<Vec<T>>> {
         let queue = match self.cmd.queue {
             Some(q) => q,
@@ -952,6 +1131,8 @@ impl<'c, 'd, T> BufferReadCmd<'c, 'd, T> where T: OclPrm {
                         src_row_pitch_bytes, src_slc_pitch_bytes,
                             dst_row_pitch_bytes, dst_slc_pitch_bytes } =>
                     {
+                        check_rect_pitches::<T>(region, [src_row_pitch_bytes, src_slc_pitch_bytes,
+                            dst_row_pitch_bytes, dst_slc_pitch_bytes])?;
                         unsafe { core::enqueue_read_buffer_rect(queue, &self.cmd.buffer.obj_core,
                             false, src_origin, dst_origin, region, src_row_pitch_bytes,
                             src_slc_pitch_bytes, dst_row_pitch_bytes, dst_slc_pitch_bytes,
@@ -975,7 +1156,9 @@ impl<'c, 'd, T> BufferReadCmd<'c, 'd, T> where T: OclPrm {
 /// The data destination for a buffer read command.
 pub enum WriteSrc<'d, T> where T: 'd {
     Slice(&'d [T]),
+    #[cfg(feature = "async")]
     RwVec(RwVec<T>),
+    #[cfg(feature = "async")]
     Reader(FutureReadGuard<Vec<T>>),
     None,
 }
@@ -987,7 +1170,9 @@ impl<'d, T> WriteSrc<'d, T> {
 
     pub fn len(&self) -> usize {
         match *self {
+            #[cfg(feature = "async")]
             WriteSrc::RwVec(ref rw_vec) => rw_vec.len_stale(),
+            #[cfg(feature = "async")]
             WriteSrc::Reader(ref writer) => unsafe { (*writer.as_ptr()).len() },
             WriteSrc::Slice(slice) => slice.len(),
             WriteSrc::None => 0,
@@ -1007,18 +1192,21 @@ impl<'d, T> From<&'d Vec<T>> for WriteSrc<'d, T>  where T: OclPrm {
     }
 }
 
+#[cfg(feature = "async")]
 impl<'d, T> From<RwVec<T>> for WriteSrc<'d, T> where T: OclPrm {
     fn from(rw_vec: RwVec<T>) -> WriteSrc<'d, T> {
         WriteSrc::RwVec(rw_vec)
     }
 }
 
+#[cfg(feature = "async")]
 impl<'a, 'd, T> From<&'a RwVec<T>> for WriteSrc<'d, T> where T: OclPrm {
     fn from(rw_vec: &'a RwVec<T>) -> WriteSrc<'d, T> {
         WriteSrc::RwVec(rw_vec.clone())
     }
 }
 
+#[cfg(feature = "async")]
 impl<'d, T> From<FutureReadGuard<Vec<T>>> for WriteSrc<'d, T> where T: OclPrm {
     fn from(reader: FutureReadGuard<Vec<T>>) -> WriteSrc<'d, T> {
         WriteSrc::Reader(reader)
@@ -1120,12 +1308,17 @@ impl<'c, 'd, T> BufferWriteCmd<'c, 'd, T> where T: OclPrm {
     /// Specifies that this will be a rectangularly shaped operation
     /// (the default being linear).
     ///
-    /// Row and slice pitches must all be expressed in bytes.
+    /// Row and slice pitches must all be expressed in bytes. `None` selects
+    /// the tightly packed default (see [`BufferCmd::rect`]).
     ///
     /// Panics if `:offset`, `src_offset`, or `::len` have been called.
-    pub fn rect(mut self, src_origin: [usize; 3], dst_origin: [usize; 3], region: [usize; 3],
-                src_row_pitch_bytes: usize, src_slc_pitch_bytes: usize, dst_row_pitch_bytes: usize,
-                dst_slc_pitch_bytes: usize) -> BufferWriteCmd<'c, 'd, T> {
+    ///
+    /// [`BufferCmd::rect`]: struct.BufferCmd.html#method.rect
+    pub fn rect<P0, P1, P2, P3>(mut self, src_origin: [usize; 3], dst_origin: [usize; 3], region: [usize; 3],
+                src_row_pitch_bytes: P0, src_slc_pitch_bytes: P1, dst_row_pitch_bytes: P2,
+                dst_slc_pitch_bytes: P3) -> BufferWriteCmd<'c, 'd, T>
+            where P0: Into<Option<usize>>, P1: Into<Option<usize>>, P2: Into<Option<usize>>,
+                P3: Into<Option<usize>> {
         if let BufferCmdDataShape::Lin { offset } = self.cmd.shape {
             assert!(offset == 0, "ocl::BufferCmd::rect(): This command builder has already been \
                 set to linear mode with '::offset`. You cannot call both '::offset' and '::rect'.");
@@ -1135,8 +1328,10 @@ impl<'c, 'd, T> BufferWriteCmd<'c, 'd, T> where T: OclPrm {
         }
 
         self.cmd.shape = BufferCmdDataShape::Rect { src_origin, dst_origin, region,
-            src_row_pitch_bytes, src_slc_pitch_bytes,
-            dst_row_pitch_bytes, dst_slc_pitch_bytes };
+            src_row_pitch_bytes: src_row_pitch_bytes.into().unwrap_or(0),
+            src_slc_pitch_bytes: src_slc_pitch_bytes.into().unwrap_or(0),
+            dst_row_pitch_bytes: dst_row_pitch_bytes.into().unwrap_or(0),
+            dst_slc_pitch_bytes: dst_slc_pitch_bytes.into().unwrap_or(0) };
 
         self
     }
@@ -1220,6 +1415,7 @@ impl<'c, 'd, T> BufferWriteCmd<'c, 'd, T> where T: OclPrm {
     //
     // NOTE: Could use deferred initialization for the guard slice instead of closure.
     pub fn enq(mut self) -> OclResult<()> {
+        let buffer = self.cmd.buffer;
         let write_src = self.src.take();
         let range = self.range.clone();
         if range.end > write_src.len() { return Err(OclError::from(
@@ -1247,6 +1443,8 @@ impl<'c, 'd, T> BufferWriteCmd<'c, 'd, T> where T: OclPrm {
                             src_row_pitch_bytes, src_slc_pitch_bytes, dst_row_pitch_bytes,
                             dst_slc_pitch_bytes } =>
                         {
+                            check_rect_pitches::<T>(region, [src_row_pitch_bytes, src_slc_pitch_bytes,
+                                dst_row_pitch_bytes, dst_slc_pitch_bytes])?;
                             unsafe {
                                 core::enqueue_write_buffer_rect(queue, &self.cmd.buffer.obj_core,
                                     self.cmd.block, src_origin, dst_origin, region, src_row_pitch_bytes,
@@ -1261,22 +1459,26 @@ impl<'c, 'd, T> BufferWriteCmd<'c, 'd, T> where T: OclPrm {
             }
         };
 
-        match write_src {
+        let result = match write_src {
             WriteSrc::Slice(slice) => {
                 enqueue_with_data(&slice[range])
             },
+            #[cfg(feature = "async")]
             WriteSrc::RwVec(rw_vec) => {
                 let guard = rw_vec.read().wait()
                     .map_err(|_| OclError::from("Unable to obtain lock."))?;
                 enqueue_with_data(&guard.as_slice()[range])
             },
+            #[cfg(feature = "async")]
             WriteSrc::Reader(reader) => {
                 let guard = reader.wait()
                     .map_err(|_| OclError::from("Unable to obtain lock."))?;
                 enqueue_with_data(&guard.as_slice()[range])
             },
             WriteSrc::None => panic!("Invalid read destination."),
-        }
+        };
+
+        result.map_err(|err| host_access_err(buffer, "write", err))
     }
 
     /// Enqueues this command and returns a future representing its completion
@@ -1287,6 +1489,8 @@ impl<'c, 'd, T> BufferWriteCmd<'c, 'd, T> where T: OclPrm {
     ///
     /// The returned future must be resolved.
     ///
+    /// Requires the `async` feature.
+    #[cfg(feature = "async")]
     pub fn enq_async(mut self) -> OclResult<FutureReadGuard<Vec<T>>> {
         match self.cmd.kind {
             BufferCmdKind::Write => {
@@ -1329,6 +1533,8 @@ impl<'c, 'd, T> BufferWriteCmd<'c, 'd, T> where T: OclPrm {
                             src_row_pitch_bytes, src_slc_pitch_bytes,
                                 dst_row_pitch_bytes, dst_slc_pitch_bytes } =>
                     {
+                        check_rect_pitches::<T>(region, [src_row_pitch_bytes, src_slc_pitch_bytes,
+                            dst_row_pitch_bytes, dst_slc_pitch_bytes])?;
                         unsafe {
                             core::enqueue_write_buffer_rect(queue, &self.cmd.buffer.obj_core,
                                 false, src_origin, dst_origin, region, src_row_pitch_bytes,
@@ -1361,6 +1567,8 @@ impl<'c, 'd, T> BufferWriteCmd<'c, 'd, T> where T: OclPrm {
     ///
     /// The returned future must be resolved.
     ///
+    /// Requires the `async` feature.
+    #[cfg(feature = "async")]
     pub fn enq_async_then_write(self) -> OclResult<FutureWriteGuard<Vec<T>>> {
         // NOTE: The precise point in time at which `::upgrade_after_command`
         // is called does not matter since a read request will have already
@@ -1489,6 +1697,24 @@ impl<'c, T> BufferMapCmd<'c, T> where T: OclPrm {
         BufferMapCmd { cmd: self.cmd.offset(offset), ..self }
     }
 
+    /// Specifies whether or not `::enq` blocks the current thread until the
+    /// map command completes.
+    ///
+    /// Default is `block = true`, in which case the mapped memory is valid
+    /// as soon as `::enq` returns. Ignored by `::enq_async`, which never
+    /// blocks.
+    ///
+    /// ## Safety
+    ///
+    /// When performing a non-blocking map, the memory referenced by the
+    /// returned `MemMap` must not be accessed until the map command
+    /// completes. Pass an event to `::enew` and wait on it (or call
+    /// `Queue::finish`) before doing so.
+    pub unsafe fn block(mut self, block: bool) -> BufferMapCmd<'c, T> {
+        self.cmd.block = block;
+        self
+    }
+
     /// Specifies an event or list of events to wait on before the command
     /// will run.
     ///
@@ -1590,7 +1816,8 @@ impl<'c, T> BufferMapCmd<'c, T> where T: OclPrm {
     }
 
     /// Enqueues a map command, blocking the current thread until it
-    /// completes and returns a reference to the mapped memory.
+    /// completes (unless `::block(false)` has been set) and returns a
+    /// reference to the mapped memory.
     ///
     /// ## Safety
     ///
@@ -1603,7 +1830,8 @@ impl<'c, T> BufferMapCmd<'c, T> where T: OclPrm {
         let (offset, len, queue, flags, ewait, enew, /*is_mapped*/) = self.enq_details()?;
 
         let mm_core = core::enqueue_map_buffer::<T, _, _, _>(&queue,
-            &self.cmd.buffer.obj_core, true, flags, offset, len, ewait, enew)?;
+            &self.cmd.buffer.obj_core, self.cmd.block, flags, offset, len, ewait, enew)
+            .map_err(|err| host_access_err(self.cmd.buffer, "map", err.into()))?;
 
         let unmap_event = None;
 
@@ -1623,6 +1851,9 @@ impl<'c, T> BufferMapCmd<'c, T> where T: OclPrm {
     /// sub-region access or whole-buffer aliasing, no two mappings will allow
     /// writes to the same memory region at the same time. Use atomics or some
     /// other synchronization mechanism to ensure this.
+    ///
+    /// Requires the `async` feature.
+    #[cfg(feature = "async")]
     pub unsafe fn enq_async(mut self) -> OclResult<FutureMemMap<T>> {
         let (offset, len, queue, flags, ewait, enew, /*is_mapped*/) = self.enq_details()?;
 
@@ -1652,12 +1883,26 @@ impl<'c, T> BufferMapCmd<'c, T> where T: OclPrm {
 /// Data is stored remotely in a memory buffer on the device associated with
 /// `queue`.
 ///
+/// ## Destruction
+///
+/// Handled automatically. Cloning a `Buffer` increments the reference count
+/// of the underlying memory object (`clRetainMemObject`) and dropping one
+/// decrements it (`clReleaseMemObject`). Device memory is freed once the last
+/// clone (including any held by kernels as arguments) is dropped.
+///
+/// Use `::downgrade` to obtain a [`WeakBuffer`] which does not keep the
+/// memory object alive.
+///
+/// [`WeakBuffer`]: struct.WeakBuffer.html
+///
 #[derive(Debug, Clone)]
 pub struct Buffer<T: OclPrm> {
     obj_core: MemCore,
+    weak_anchor: WeakAnchor,
     queue: Option<Queue>,
     len: usize,
     offset: Option<usize>,
+    shape: Option<SpatialDims>,
     _data: PhantomData<T>,
 }
 
@@ -1712,23 +1957,80 @@ impl<T: OclPrm> Buffer<T> {
 
         let buf = Buffer {
             obj_core,
+            weak_anchor: WeakAnchor::default(),
             queue: que_ctx.into(),
             len,
             offset: None,
+            shape: None,
             _data: PhantomData,
         };
 
         Ok(buf)
     }
 
+    /// Creates a new read/write buffer of `len` elements without initializing
+    /// its contents.
+    ///
+    /// No host memory is used or copied (`MEM_COPY_HOST_PTR` is not set), so
+    /// no host to device transfer takes place. Useful for output buffers
+    /// which will be completely overwritten by a kernel.
+    ///
+    /// The contents of the buffer are undefined until written (by a kernel,
+    /// write, fill, copy, etc.). Reading before then returns garbage.
+    pub fn new_uninit<'o, Q, D>(que_ctx: Q, len: D) -> OclResult<Buffer<T>>
+            where Q: Into<QueCtx<'o>>, D: Into<SpatialDims> {
+        unsafe { Buffer::new(que_ctx, core::MEM_READ_WRITE, len, None) }
+    }
+
+    /// Creates a new read/write buffer initialized with a copy of `data`
+    /// (`MEM_COPY_HOST_PTR`).
+    ///
+    /// Accepts anything which can be viewed as a slice, such as a slice,
+    /// array, or `Vec`. The length of the buffer is the length of `data`.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// let a = Buffer::from_data(&queue, [1.0f32, 2.0, 3.0, 4.0])?;
+    /// let b = Buffer::from_data(&queue, vec![0u32; 1024])?;
+    /// let c = Buffer::from_data(&queue, &host_vec[16..32])?;
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `data` is empty.
+    pub fn from_data<'o, Q, A>(que_ctx: Q, data: A) -> OclResult<Buffer<T>>
+            where Q: Into<QueCtx<'o>>, A: AsRef<[T]> {
+        let data = data.as_ref();
+        if data.is_empty() {
+            return Err("Buffer::from_data: The data slice must not be empty.".into());
+        }
+        unsafe { Buffer::new(que_ctx, core::MEM_READ_WRITE | core::MEM_COPY_HOST_PTR, data.len(),
+            Some(data)) }
+    }
+
+    /// Creates a new read/write buffer of `len` elements which the host will
+    /// never access (`MEM_HOST_NO_ACCESS`), without initializing its
+    /// contents.
+    ///
+    /// Intended for intermediate (scratch) buffers used only by kernels and
+    /// device-side copies. This is a hint which may allow the driver to
+    /// optimize the allocation. Reading, writing, or mapping the buffer from
+    /// the host returns `BufferCmdError::HostAccessDenied`.
+    pub fn new_device_private<'o, Q, D>(que_ctx: Q, len: D) -> OclResult<Buffer<T>>
+            where Q: Into<QueCtx<'o>>, D: Into<SpatialDims> {
+        unsafe { Buffer::new(que_ctx, core::MEM_READ_WRITE | core::MEM_HOST_NO_ACCESS, len, None) }
+    }
+
     /// Creates a buffer linked to a previously created OpenGL buffer object.
     ///
     /// [UNTESTED]
     ///
     /// ### Errors
     ///
-    /// Don't forget to `.cmd().gl_acquire().enq()` before using it and
-    /// `.cmd().gl_release().enq()` after.
+    /// Don't forget to `.gl_acquire().enq()` before using it and
+    /// `.gl_release().enq()` after. The context must have been created with
+    /// OpenGL sharing enabled (see `ContextBuilder::gl_context` or
+    /// `Context::from_raw`).
     ///
     /// See the [`BufferCmd` docs](builders/struct.BufferCmd.html)
     /// for more info.
@@ -1752,9 +2054,11 @@ impl<T: OclPrm> Buffer<T> {
 
         let buf = Buffer {
             obj_core,
+            weak_anchor: WeakAnchor::default(),
             queue: que_ctx.into(),
             len,
             offset: None,
+            shape: None,
             _data: PhantomData,
         };
 
@@ -1781,6 +2085,14 @@ impl<T: OclPrm> Buffer<T> {
     /// See the [command builder documentation](builders/struct.BufferCmd#method.read)
     /// for more details.
     ///
+    /// ### Blocking
+    ///
+    /// By default `.enq()` blocks the current thread until the read is
+    /// complete (`blocking_read` is passed as `CL_TRUE`). Call
+    /// `.block(false)` on the returned command to enqueue a non-blocking
+    /// read (`CL_FALSE`) instead and synchronize manually, for example with
+    /// an event obtained using `.enew(...)`.
+    ///
     #[inline]
     pub fn read<'c, 'd, R>(&'c self, dst: R) -> BufferReadCmd<'c, 'd, T>
             where 'd: 'c, R: Into<ReadDst<'d, T>>
@@ -1788,6 +2100,39 @@ impl<T: OclPrm> Buffer<T> {
         self.cmd().read(dst)
     }
 
+    /// Reads into `dst` after every event in `wait` has completed, blocking
+    /// until the read is complete. Returns the event associated with the
+    /// read command.
+    ///
+    /// Use this to order a read after the command which produces the data
+    /// (e.g. a kernel) when that ordering is not already guaranteed by an
+    /// in-order queue, such as when using out-of-order queues or multiple
+    /// queues.
+    ///
+    /// Equivalent to `buffer.read(dst).ewait(wait).enew(&mut event).enq()`.
+    pub fn read_into_wait(&self, dst: &mut [T], wait: &[Event]) -> OclResult<Event> {
+        let mut event = Event::empty();
+        let cmd = self.read(dst).enew(&mut event);
+
+        if wait.is_empty() {
+            cmd.enq()?;
+        } else {
+            cmd.ewait(wait).enq()?;
+        }
+
+        Ok(event)
+    }
+
+    /// Reads the entire contents of this buffer into a newly allocated `Vec`,
+    /// blocking until the read is complete.
+    ///
+    /// Equivalent to allocating a `Vec` of `self.len()` elements and passing
+    /// it to `::read_into_wait` with an empty wait list.
+    pub fn read_all(&self) -> OclResult<Vec<T>> {
+        let mut vec = vec![T::default(); self.len];
+        self.read_into_wait(&mut vec, &[]).map(|_| vec)
+    }
+
     /// Returns a command builder used to write data.
     ///
     /// Call `.enq()` to enqueue the command.
@@ -1795,6 +2140,14 @@ impl<T: OclPrm> Buffer<T> {
     /// See the [command builder documentation](builders/struct.BufferCmd#method.write)
     /// for more details.
     ///
+    /// ### Blocking
+    ///
+    /// By default `.enq()` blocks the current thread until the write is
+    /// complete (`blocking_write` is passed as `CL_TRUE`). Call
+    /// `.block(false)` on the returned command to enqueue a non-blocking
+    /// write (`CL_FALSE`) instead and synchronize manually, for example with
+    /// an event obtained using `.enew(...)`.
+    ///
     #[inline]
     pub fn write<'c, 'd, W>(&'c self, src: W) -> BufferWriteCmd<'c, 'd, T>
             where 'd: 'c, W: Into<WriteSrc<'d, T>>
@@ -1802,6 +2155,42 @@ impl<T: OclPrm> Buffer<T> {
         self.cmd().write(src)
     }
 
+    /// Reads the contents of this buffer, blocking until complete, and
+    /// asserts that they are equal to `expected`, element by element.
+    ///
+    /// Intended for use in tests.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the read fails, if the length of `expected` differs from
+    /// the length of this buffer, or upon the first mismatching element
+    /// (reporting its index and both values).
+    pub fn assert_eq(&self, expected: &[T]) {
+        let actual = self.read_to_assert("Buffer::assert_eq", expected.len());
+
+        for (idx, (act, exp)) in actual.iter().zip(expected.iter()).enumerate() {
+            if act != exp {
+                panic!("Buffer::assert_eq: Element mismatch at index {}: (buffer: {:?}, \
+                    expected: {:?}).", idx, act, exp);
+            }
+        }
+    }
+
+    /// Reads the entire buffer into a new `Vec`, panicking if the read fails
+    /// or if its length differs from `expected_len`.
+    fn read_to_assert(&self, fn_name: &str, expected_len: usize) -> Vec<T> {
+        if expected_len != self.len {
+            panic!("{}: Length mismatch: (buffer: {}, expected: {}).", fn_name, self.len,
+                expected_len);
+        }
+
+        let mut actual = vec![T::default(); self.len];
+        if let Err(err) = self.read(&mut actual).enq() {
+            panic!("{}: Unable to read buffer: {}", fn_name, err);
+        }
+        actual
+    }
+
     /// Returns a command builder used to map data for reading or writing.
     ///
     /// Enqueuing a map command will map a region of a buffer into the host
@@ -1826,6 +2215,130 @@ impl<T: OclPrm> Buffer<T> {
         self.cmd().map()
     }
 
+    /// Maps the region `[offset, offset + len)` of this buffer, blocking
+    /// until the map completes.
+    ///
+    /// The returned [`MemMap`] dereferences to a slice of length `len` and
+    /// unmaps the region from the original buffer when unmapped or dropped.
+    /// Useful when only a small window of a large buffer needs to be touched
+    /// on the host.
+    ///
+    /// Equivalent to `buffer.map().flags(flags).offset(offset).len(len).enq()`
+    /// except that the range is validated against the buffer length up front.
+    ///
+    /// ## Errors
+    ///
+    /// Returns `BufferCmdError::RangeOutOfBounds` if `len` is zero or the
+    /// range extends past the end of the buffer.
+    ///
+    /// ## Safety
+    ///
+    /// See [`BufferMapCmd::enq`].
+    ///
+    /// [`MemMap`]: struct.MemMap.html
+    /// [`BufferMapCmd::enq`]: builders/struct.BufferMapCmd.html#method.enq
+    pub unsafe fn map_range(&self, flags: MapFlags, offset: usize, len: usize)
+            -> OclResult<MemMap<T>> {
+        self.check_map_range(offset, len)?;
+        self.map().flags(flags).offset(offset).len(len).enq()
+    }
+
+    /// Begins mapping the region `[offset, offset + len)` of this buffer
+    /// without blocking, returning a future of the mapping along with the
+    /// event of the map command.
+    ///
+    /// The mapped memory may not be accessed until the map command has
+    /// completed, so the [`FutureMemMap`] only resolves into a [`MemMap`]
+    /// once it has. Meanwhile the host is free to do other work. The event
+    /// can be passed as a wait event to other commands. The region is
+    /// unmapped on the queue used to map it when the `MemMap` is unmapped or
+    /// dropped.
+    ///
+    /// Equivalent to
+    /// `buffer.map().flags(flags).offset(offset).len(len).enew(..).enq_async()`
+    /// except that the range is validated against the buffer length up front.
+    ///
+    /// Requires the `async` feature.
+    ///
+    /// ## Errors
+    ///
+    /// Returns `BufferCmdError::RangeOutOfBounds` if `len` is zero or the
+    /// range extends past the end of the buffer.
+    ///
+    /// ## Safety
+    ///
+    /// See [`BufferMapCmd::enq_async`].
+    ///
+    /// [`MemMap`]: struct.MemMap.html
+    /// [`FutureMemMap`]: async/struct.FutureMemMap.html
+    /// [`BufferMapCmd::enq_async`]: builders/struct.BufferMapCmd.html#method.enq_async
+    #[cfg(feature = "async")]
+    pub unsafe fn map_async(&self, flags: MapFlags, offset: usize, len: usize)
+            -> OclResult<(FutureMemMap<T>, Event)> {
+        self.check_map_range(offset, len)?;
+        let mut map_event = Event::empty();
+        let future_map = self.map().flags(flags).offset(offset).len(len)
+            .enew(&mut map_event).enq_async()?;
+        Ok((future_map, map_event))
+    }
+
+    /// Returns an error unless `[offset, offset + len)` is a non-empty range
+    /// within this buffer.
+    fn check_map_range(&self, offset: usize, len: usize) -> OclResult<()> {
+        match offset.checked_add(len) {
+            Some(end) if len > 0 && end <= self.len => Ok(()),
+            _ => Err(BufferCmdError::RangeOutOfBounds { offset, len,
+                buffer_len: self.len }.into()),
+        }
+    }
+
+    /// Maps the entire buffer for reading and writing, blocking until the map
+    /// completes, and returns a [`MemMap`] which remains mapped across any
+    /// number of subsequent enqueues until it is unmapped or dropped.
+    ///
+    /// Mapping once and reusing the mapping avoids the cost of repeatedly
+    /// mapping and unmapping when streaming data (e.g. video frames) to a
+    /// device. The buffer must have been created with `MEM_ALLOC_HOST_PTR`
+    /// or `MEM_USE_HOST_PTR`.
+    ///
+    /// ### Coherence
+    ///
+    /// OpenCL (prior to 2.0 shared virtual memory) does not define the
+    /// result of a kernel or other command accessing a buffer region while
+    /// it is mapped. In practice, persistent mappings work as expected on
+    /// devices which share physical memory with the host (CPUs and most
+    /// integrated GPUs), where such buffers are zero-copy. Even then:
+    ///
+    /// * Finish all host writes to the mapped memory before enqueuing a
+    ///   command which reads it.
+    /// * Wait for every command which writes to the buffer to complete
+    ///   (`Queue::finish` or `Event::wait_for`) before reading the mapped
+    ///   memory on the host.
+    /// * Flush the queue (`Queue::flush`) after enqueuing commands which the
+    ///   host will subsequently wait on through other means.
+    ///
+    /// For portable code, unmap before device access and map again
+    /// afterwards instead.
+    ///
+    /// ## Errors
+    ///
+    /// Returns `BufferCmdError::MapUnavailable` if the buffer was created
+    /// with neither `MEM_ALLOC_HOST_PTR` nor `MEM_USE_HOST_PTR`.
+    ///
+    /// ## Safety
+    ///
+    /// See [`BufferMapCmd::enq`] and the coherence rules above.
+    ///
+    /// [`MemMap`]: struct.MemMap.html
+    /// [`BufferMapCmd::enq`]: builders/struct.BufferMapCmd.html#method.enq
+    pub unsafe fn persistent_map(&self) -> OclResult<MemMap<T>> {
+        if !self.flags()?.intersects(core::MEM_ALLOC_HOST_PTR | core::MEM_USE_HOST_PTR) {
+            return Err(BufferCmdError::MapUnavailable.into());
+        }
+
+        self.map().flags(MapFlags::new().read().write()).enq()
+    }
+
     /// Specifies that this command will be a copy operation.
     ///
     /// Call `.enq()` to enqueue the command.
@@ -1840,6 +2353,77 @@ impl<T: OclPrm> Buffer<T> {
         self.cmd().copy(dst_buffer, dst_offset, len)
     }
 
+    /// Enqueues a device-side rectangular (2D or 3D) copy of `region` from
+    /// this buffer to `dst` after every event in `wait` has completed and
+    /// returns the event associated with the copy command. The host is not
+    /// involved and this call does not block.
+    ///
+    /// The x components of `src_origin`, `dst_origin`, and `region` are
+    /// expressed in elements, the y and z components in rows and slices.
+    /// `pitches` contains, in order, the source row pitch, source slice
+    /// pitch, destination row pitch, and destination slice pitch, all
+    /// expressed in bytes (zero selects the tightly packed default, as with
+    /// `clEnqueueCopyBufferRect`).
+    ///
+    /// Useful for blitting tiles between buffers (e.g. assembling a mosaic).
+    ///
+    /// ## Errors
+    ///
+    /// Returns `BufferCmdError::RectOutOfBounds` if either the source or
+    /// destination origin and region extend past the end of their buffer.
+    /// Nothing is enqueued in that case.
+    pub fn copy_rect_to(&self, dst: &Buffer<T>, src_origin: [usize; 3], dst_origin: [usize; 3],
+            region: [usize; 3], pitches: [usize; 4], wait: &[Event]) -> OclResult<Event> {
+        let elem_size = ::std::mem::size_of::<T>();
+        let [src_row_pitch, src_slc_pitch, dst_row_pitch, dst_slc_pitch] = pitches;
+
+        check_rect_bounds("source", src_origin, region, src_row_pitch, src_slc_pitch,
+            elem_size, self.len * elem_size)?;
+        check_rect_bounds("destination", dst_origin, region, dst_row_pitch, dst_slc_pitch,
+            elem_size, dst.len * elem_size)?;
+
+        let mut event = Event::empty();
+        let cmd = self.cmd()
+            .copy(dst, None, None)
+            .rect(src_origin, dst_origin, region, src_row_pitch, src_slc_pitch, dst_row_pitch,
+                dst_slc_pitch)
+            .enew(&mut event);
+
+        if wait.is_empty() {
+            cmd.enq()?;
+        } else {
+            cmd.ewait(wait).enq()?;
+        }
+
+        Ok(event)
+    }
+
+    /// Returns a command builder used to acquire this buffer from OpenGL
+    /// (`clEnqueueAcquireGLObjects`) before use by OpenCL commands.
+    ///
+    /// Only valid for buffers created with `::from_gl_buffer`. Call `.enq()`
+    /// to enqueue the command.
+    ///
+    /// See the [command builder documentation](builders/struct.BufferCmd.html#method.gl_acquire)
+    /// for more details.
+    #[inline]
+    pub fn gl_acquire<'c>(&'c self) -> BufferCmd<'c, T> {
+        self.cmd().gl_acquire()
+    }
+
+    /// Returns a command builder used to release this buffer back to OpenGL
+    /// (`clEnqueueReleaseGLObjects`) after use by OpenCL commands.
+    ///
+    /// Only valid for buffers created with `::from_gl_buffer`. Call `.enq()`
+    /// to enqueue the command.
+    ///
+    /// See the [command builder documentation](builders/struct.BufferCmd.html#method.gl_release)
+    /// for more details.
+    #[inline]
+    pub fn gl_release<'c>(&'c self) -> BufferCmd<'c, T> {
+        self.cmd().gl_release()
+    }
+
     // /// Returns the origin of the sub-buffer within its buffer if this is a
     // /// sub-buffer.
     // #[inline]
@@ -1868,6 +2452,38 @@ impl<T: OclPrm> Buffer<T> {
         self.len
     }
 
+    /// Attaches host-side shape metadata (e.g. `(256, 256)` for an image) to
+    /// this buffer and returns it.
+    ///
+    /// The shape does not affect the allocation in any way. It is used by
+    /// `Kernel::enq_over` to derive a global work size which matches the
+    /// buffer. Sub-buffers do not inherit the shape of their parent.
+    ///
+    /// Returns an error if the product of the shape dimensions does not
+    /// equal the length of the buffer.
+    pub fn with_shape<D: Into<SpatialDims>>(mut self, shape: D) -> OclResult<Buffer<T>> {
+        let shape = shape.into();
+        let shape_len = shape.to_len();
+        if shape_len != self.len {
+            return Err(BufferError::ShapeMismatch { shape, shape_len, len: self.len }.into());
+        }
+        self.shape = Some(shape);
+        Ok(self)
+    }
+
+    /// Returns the shape metadata attached with `::with_shape`, if any.
+    #[inline]
+    pub fn shape(&self) -> Option<SpatialDims> {
+        self.shape
+    }
+
+    /// Returns the shape of this buffer if one has been attached with
+    /// `::with_shape`, otherwise a one-dimensional shape of `::len` elements.
+    #[inline]
+    pub fn work_dims(&self) -> SpatialDims {
+        self.shape.unwrap_or(SpatialDims::One(self.len))
+    }
+
     // /// Returns the length of the buffer.
     // ///
     // /// Equivalent to `::dims().to_len()`.
@@ -1959,6 +2575,99 @@ impl<T: OclPrm> Buffer<T> {
         &self.obj_core
     }
 
+    /// Returns a `Buffer` wrapping a raw `cl_mem` created elsewhere, using
+    /// `queue` (if any) as its default queue.
+    ///
+    /// The length is determined from the size of the memory object unless
+    /// `len` is specified, in which case it may be less than that size (e.g.
+    /// when the allocation was padded by whoever created it). An error is
+    /// returned if `len` elements of `T` would exceed the size of the memory
+    /// object.
+    ///
+    /// Every `Buffer` (and every clone of one) owns exactly one reference to
+    /// the underlying memory object and releases it when dropped.
+    ///
+    /// ## Ownership
+    ///
+    /// * `retain: true`: The reference count is incremented before wrapping.
+    ///   The caller keeps its own reference and remains responsible for
+    ///   releasing it.
+    /// * `retain: false`: The caller's reference is transferred to the
+    ///   returned `Buffer`. The caller must **not** release `ptr` afterwards,
+    ///   even if an error is returned.
+    ///
+    /// ## Safety
+    ///
+    /// `ptr` must be a valid `cl_mem` buffer object containing elements of
+    /// type `T`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `ptr` is null.
+    pub unsafe fn from_raw(ptr: cl_mem, retain: bool, queue: Option<Queue>, len: Option<usize>)
+            -> OclResult<Buffer<T>> {
+        let obj_core = if retain {
+            MemCore::from_raw_copied_ptr(ptr)
+        } else {
+            MemCore::from_raw_create_ptr(ptr)
+        };
+
+        let max_len = match core::get_mem_object_info(&obj_core, MemInfo::Size)? {
+            MemInfoResult::Size(len_bytes) => len_bytes / ::std::mem::size_of::<T>(),
+            _ => unreachable!(),
+        };
+
+        let len = match len {
+            Some(len) if len > max_len => {
+                return Err(format!("Buffer::from_raw: The requested length ({}) exceeds the \
+                    number of elements the memory object can hold ({}).", len, max_len).into());
+            },
+            Some(len) => len,
+            None => max_len,
+        };
+
+        Ok(Buffer {
+            obj_core,
+            weak_anchor: WeakAnchor::default(),
+            queue,
+            len,
+            offset: None,
+            shape: None,
+            _data: PhantomData,
+        })
+    }
+
+    /// Returns the raw `cl_mem` pointer.
+    ///
+    /// The pointer is owned by this `Buffer` and is only valid for as long as
+    /// it (or a clone) exists. Do not release it.
+    #[inline]
+    pub fn as_ptr(&self) -> cl_mem {
+        self.obj_core.as_ptr()
+    }
+
+    /// Returns a weak reference to this buffer which does not keep the
+    /// underlying memory object alive.
+    ///
+    /// See [`WeakBuffer`] for details.
+    ///
+    /// [`WeakBuffer`]: struct.WeakBuffer.html
+    pub fn downgrade(&self) -> WeakBuffer<T> {
+        let mut anchored = self.weak_anchor.lock().unwrap();
+        if anchored.is_none() {
+            *anchored = Some(self.obj_core.clone());
+        }
+
+        WeakBuffer {
+            weak_anchor: Arc::downgrade(&self.weak_anchor),
+            queue: self.queue.clone(),
+            len: self.len,
+            offset: self.offset,
+            shape: self.shape,
+            _data: PhantomData,
+        }
+    }
+
     /// Returns the memory flags used during the creation of this buffer.
     ///
     #[inline]
@@ -2051,11 +2760,13 @@ impl<T: OclPrm> Buffer<T> {
 
         Ok(Buffer {
             obj_core,
+            weak_anchor: WeakAnchor::default(),
             queue: self.default_queue().cloned(),
             len,
             // Share mapped status with super-buffer:
             // is_mapped: self.is_mapped.clone(),
             offset: Some(offset),
+            shape: None,
             _data: PhantomData,
         })
     }
@@ -2124,6 +2835,90 @@ unsafe impl<'a, T> MemCmdAll for &'a Buffer<T> where T: OclPrm {}
 unsafe impl<'a, T> MemCmdAll for &'a mut Buffer<T> where T: OclPrm {}
 
 
+impl<T: OclScl> Buffer<T> {
+    /// Reads the contents of this buffer, blocking until complete, and
+    /// asserts that each element differs from the corresponding element of
+    /// `expected` by no more than `epsilon`.
+    ///
+    /// Intended for use in tests of numerical kernels. `NaN` values never
+    /// compare as equal.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the read fails, if the length of `expected` differs from
+    /// the length of this buffer, or upon the first mismatching element
+    /// (reporting its index and both values).
+    pub fn assert_approx_eq(&self, expected: &[T], epsilon: f64) {
+        let actual = self.read_to_assert("Buffer::assert_approx_eq", expected.len());
+
+        for (idx, (act, exp)) in actual.iter().zip(expected.iter()).enumerate() {
+            let diff = match (act.to_f64(), exp.to_f64()) {
+                (Some(a), Some(e)) => (a - e).abs(),
+                _ => f64::NAN,
+            };
+
+            if diff.is_nan() || diff > epsilon {
+                panic!("Buffer::assert_approx_eq: Element mismatch at index {}: (buffer: {:?}, \
+                    expected: {:?}, epsilon: {}).", idx, act, exp, epsilon);
+            }
+        }
+    }
+}
+
+
+/// The state shared by a buffer and all of its clones which allows weak
+/// references to be upgraded.
+///
+/// Holds a reference to the memory object once `Buffer::downgrade` has been
+/// called, guaranteeing that it is still alive whenever the anchor can be
+/// upgraded.
+type WeakAnchor = Arc<Mutex<Option<MemCore>>>;
+
+
+/// A weak reference to a [`Buffer`].
+///
+/// A `WeakBuffer` does not keep the underlying memory object alive. Once the
+/// last `Buffer` sharing it (the original and its clones) has been dropped,
+/// `::upgrade` returns `None` and the memory object is released (unless it
+/// is still referenced elsewhere, such as by a kernel argument). This is
+/// useful for caches which should not prevent buffers from being freed.
+///
+/// The first call to `Buffer::downgrade` for a buffer increments the
+/// reference count of its memory object by one until the last `Buffer`
+/// sharing it is dropped.
+///
+/// The default queue of the buffer, if any, is kept alive by a `WeakBuffer`.
+///
+/// [`Buffer`]: struct.Buffer.html
+#[derive(Debug, Clone)]
+pub struct WeakBuffer<T: OclPrm> {
+    weak_anchor: Weak<Mutex<Option<MemCore>>>,
+    queue: Option<Queue>,
+    len: usize,
+    offset: Option<usize>,
+    shape: Option<SpatialDims>,
+    _data: PhantomData<T>,
+}
+
+impl<T: OclPrm> WeakBuffer<T> {
+    /// Returns a new `Buffer` referring to the same memory object if any
+    /// `Buffer` referring to it still exists.
+    pub fn upgrade(&self) -> Option<Buffer<T>> {
+        let weak_anchor = self.weak_anchor.upgrade()?;
+        let obj_core = weak_anchor.lock().unwrap().clone()?;
+
+        Some(Buffer {
+            obj_core,
+            weak_anchor,
+            queue: self.queue.clone(),
+            len: self.len,
+            offset: self.offset,
+            shape: self.shape,
+            _data: PhantomData,
+        })
+    }
+}
+
 /// A buffer builder.
 ///
 // * TODO: Add examples and details. For now see project examples folder.
@@ -2136,7 +2931,8 @@ pub struct BufferBuilder<'a, T> where T: OclPrm {
     flags: Option<MemFlags>,
     host_slice: HostSlice<'a, T>,
     len: usize,
-    fill_val: Option<(T, Option<ClNullEventPtrEnum<'a>>)>
+    fill_val: Option<(T, Option<ClNullEventPtrEnum<'a>>)>,
+    check_alloc_size: bool,
 }
 
 impl<'a, T> BufferBuilder<'a, T> where T: 'a + OclPrm {
@@ -2148,6 +2944,7 @@ impl<'a, T> BufferBuilder<'a, T> where T: 'a + OclPrm {
             host_slice: HostSlice::None,
             len: 0,
             fill_val: None,
+            check_alloc_size: false,
         }
     }
 
@@ -2330,10 +3127,25 @@ impl<'a, T> BufferBuilder<'a, T> where T: 'a + OclPrm {
         self
     }
 
+    /// Causes `::build` to check the requested size of the buffer against the
+    /// maximum memory allocation size (`CL_DEVICE_MAX_MEM_ALLOC_SIZE`) of
+    /// the device associated with the default queue or, if only a context
+    /// has been specified, of every device in that context.
+    ///
+    /// Requesting a buffer which is too large will then return a
+    /// `BufferError::TooLarge` error instead of the less informative
+    /// `CL_INVALID_BUFFER_SIZE` error.
+    pub fn check_alloc_size(mut self) -> BufferBuilder<'a, T> {
+        self.check_alloc_size = true;
+        self
+    }
+
     /// Creates a buffer and returns it.
     ///
     /// Dimensions and either a context or default queue must be specified
-    /// before calling `::build`.
+    /// before calling `::build` unless called from within a closure passed
+    /// to `ocl::with_default`, in which case the scoped default queue is
+    /// used.
     pub fn build(self) -> OclResult<Buffer<T>> {
         let mut flags = match self.flags {
             Some(f) => f,
@@ -2357,10 +3169,10 @@ impl<'a, T> BufferBuilder<'a, T> where T: 'a + OclPrm {
             HostSlice::None => None,
         };
 
-        let qc = match self.queue_option {
+        let qc = match self.queue_option.or_else(|| Queue::scoped_default().map(QueCtx::Queue)) {
             Some(qc) => qc,
             None => panic!("ocl::BufferBuilder::build: A context or default queue must be set \
-                with '.context(...)' or '.queue(...)'."),
+                with '.context(...)', '.queue(...)', or 'ocl::with_default'."),
         };
 
         let len = match self.len {
@@ -2374,6 +3186,22 @@ impl<'a, T> BufferBuilder<'a, T> where T: 'a + OclPrm {
             QueCtx::Context(_) => None,
         };
 
+        if self.check_alloc_size {
+            let devices = match qc {
+                QueCtx::Queue(ref queue) => vec![queue.device()],
+                QueCtx::Context(context) => context.devices(),
+            };
+            let size = (len * std::mem::size_of::<T>()) as u64;
+
+            for device in devices {
+                let max_alloc_size = device.max_mem_alloc_size()?;
+                if size > max_alloc_size {
+                    return Err(BufferError::TooLarge { size, max_alloc_size,
+                        device_name: device.name()? }.into());
+                }
+            }
+        }
+
         let buf = unsafe { Buffer::new(qc, flags, len, host_slice)? };
 
         // Fill buffer if `fill_val` and a queue have been specified,
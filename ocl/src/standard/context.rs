@@ -2,13 +2,16 @@
 
 use std;
 use std::ops::{Deref, DerefMut};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use crate::ffi::cl_context;
 use crate::core::{self, Context as ContextCore, ContextProperties, ContextPropertyValue, ContextInfo,
     ContextInfoResult, DeviceInfo, DeviceInfoResult, PlatformInfo, PlatformInfoResult,
-    CreateContextCallbackFn, UserDataPtr, OpenclVersion, ClContextPtr, ClVersions};
+    CreateContextCallbackFn, UserDataPtr, OpenclVersion, ClContextPtr, ClVersions,
+    PrintfCallbackFn};
 use crate::core::error::{Result as OclCoreResult};
 use crate::error::{Error as OclError, Result as OclResult};
-use crate::standard::{Platform, Device, DeviceSpecifier};
+use crate::standard::{Platform, Device, DeviceSpecifier, Queue};
 
 
 
@@ -24,7 +27,10 @@ use crate::standard::{Platform, Device, DeviceSpecifier};
 //   `ContextInfoResult::Properties` before we can eliminate `platform`.
 //
 #[derive(Debug, Clone)]
-pub struct Context(ContextCore);
+pub struct Context {
+    obj_core: ContextCore,
+    queues: Arc<Mutex<HashMap<Device, Queue>>>,
+}
 
 impl Context {
     /// Returns a [`ContextBuilder`](/ocl/ocl/struct.ContextBuilder.html).
@@ -77,7 +83,7 @@ impl Context {
 
         let obj_core = core::create_context(properties.as_ref(), &device_list, pfn_notify, user_data)?;
 
-        Ok(Context(obj_core))
+        Ok(Context::from(obj_core))
     }
 
     /// Resolves a list of zero-based device indices into a list of Devices.
@@ -122,32 +128,100 @@ impl Context {
 
     /// Returns info about the context.
     pub fn info(&self, info_kind: ContextInfo) -> OclResult<ContextInfoResult> {
-        core::get_context_info(&self.0, info_kind).map_err(OclError::from)
+        core::get_context_info(&self.obj_core, info_kind).map_err(OclError::from)
+    }
+
+    /// Returns a `Context` wrapping a raw `cl_context` created elsewhere, for
+    /// example by another library sharing a context with OpenGL.
+    ///
+    /// ## Ownership
+    ///
+    /// Every `Context` (and every clone of one) owns exactly one reference to
+    /// the underlying context and releases it (`clReleaseContext`) when
+    /// dropped.
+    ///
+    /// * `retain: true`: The reference count is incremented
+    ///   (`clRetainContext`) before wrapping. The caller keeps its own
+    ///   reference and remains responsible for releasing it. Use this when
+    ///   the other library will also release the context.
+    /// * `retain: false`: The caller's reference is transferred to the
+    ///   returned `Context`. The caller must **not** release `ptr` afterwards
+    ///   or it will be released twice.
+    ///
+    /// ## Safety
+    ///
+    /// `ptr` must be a valid `cl_context`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `ptr` is null.
+    pub unsafe fn from_raw(ptr: cl_context, retain: bool) -> Context {
+        if retain {
+            Context::from(ContextCore::from_raw_copied_ptr(ptr))
+        } else {
+            Context::from(ContextCore::from_raw_create_ptr(ptr))
+        }
     }
 
     /// Returns a reference to the core pointer wrapper, usable by functions in
     /// the `core` module.
     #[inline]
     pub fn as_core(&self) -> &ContextCore {
-        &self.0
+        &self.obj_core
+    }
+
+    /// Returns the raw `cl_context` pointer.
+    ///
+    /// The pointer is owned by this `Context` and is only valid for as long as
+    /// it (or a clone) exists. Do not release it; call `clRetainContext`
+    /// if it must outlive this `Context`.
+    #[inline]
+    pub fn as_ptr(&self) -> cl_context {
+        self.obj_core.as_ptr()
     }
 
     /// Returns the list of devices associated with this context.
     ///
     /// Panics upon any OpenCL error.
     pub fn devices(&self) -> Vec<Device> {
-        Device::list_from_core(self.0.devices().unwrap())
+        Device::list_from_core(self.obj_core.devices().unwrap())
     }
 
     /// Returns the list of device versions associated with this context.
     pub fn device_versions(&self) -> OclResult<Vec<OpenclVersion>> {
-        Device::list_from_core(self.0.devices().map_err(OclError::from)?).into_iter()
-            .map(|d| d.version().map_err(OclError::from)).collect()
+        Device::list_from_core(self.obj_core.devices().map_err(OclError::from)?).into_iter()
+            .map(|d| d.version()).collect()
+    }
+
+    /// Returns a queue on `device`, creating one (with default properties)
+    /// the first time each device is requested.
+    ///
+    /// The queue for each device is cached and shared between this context
+    /// and its clones, providing one queue per device without having to
+    /// manage them separately (e.g. when distributing work across several
+    /// GPUs in the same context). Contexts obtained from elsewhere, such as
+    /// `Queue::context`, keep their own separate cache.
+    ///
+    /// Returns an error if `device` is not associated with this context.
+    pub fn queue_for(&self, device: Device) -> OclResult<Queue> {
+        let mut queues = self.queues.lock().unwrap();
+        if let Some(queue) = queues.get(&device) {
+            return Ok(queue.clone());
+        }
+
+        if !self.devices().contains(&device) {
+            return Err(format!("Context::queue_for: The device ('{}') is not associated \
+                with this context.", device.name().unwrap_or_else(|_| "<unknown>".into())).into());
+        }
+
+        let queue = Queue::new(self, device, None)?;
+        queues.insert(device, queue.clone());
+        Ok(queue)
     }
 
     /// Returns the platform this context is associated with.
     pub fn platform(&self) -> OclResult<Option<Platform>> {
-        self.0.platform().map(|opt| opt.map(Platform::from)).map_err(OclError::from)
+        self.obj_core.platform().map(|opt| opt.map(Platform::from)).map_err(OclError::from)
     }
 
     fn fmt_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -162,7 +236,10 @@ impl Context {
 
 impl From<ContextCore> for Context {
     fn from(c: ContextCore) -> Context {
-        Context(c)
+        Context {
+            obj_core: c,
+            queues: Arc::new(Mutex::new(HashMap::new())),
+        }
     }
 }
 
@@ -176,36 +253,52 @@ impl Deref for Context {
     type Target = ContextCore;
 
     fn deref(&self) -> &ContextCore {
-        &self.0
+        &self.obj_core
     }
 }
 
 impl DerefMut for Context {
     fn deref_mut(&mut self) -> &mut ContextCore {
-        &mut self.0
+        &mut self.obj_core
     }
 }
 
 unsafe impl<'a> ClContextPtr for &'a Context {
     fn as_ptr(&self) -> cl_context {
-        self.0.as_ptr()
+        self.obj_core.as_ptr()
     }
 }
 
 impl ClVersions for Context {
-    fn device_versions(&self) -> OclCoreResult<Vec<OpenclVersion>> { self.0.device_versions() }
-    fn platform_version(&self) -> OclCoreResult<OpenclVersion> { self.0.platform_version() }
+    fn device_versions(&self) -> OclCoreResult<Vec<OpenclVersion>> { self.obj_core.device_versions() }
+    fn platform_version(&self) -> OclCoreResult<OpenclVersion> { self.obj_core.platform_version() }
 }
 
 impl<'a> ClVersions for &'a Context {
-    fn device_versions(&self) -> OclCoreResult<Vec<OpenclVersion>> { self.0.device_versions() }
-    fn platform_version(&self) -> OclCoreResult<OpenclVersion> { self.0.platform_version() }
+    fn device_versions(&self) -> OclCoreResult<Vec<OpenclVersion>> { self.obj_core.device_versions() }
+    fn platform_version(&self) -> OclCoreResult<OpenclVersion> { self.obj_core.platform_version() }
 }
 
 
 
 /// A builder for `Context`.
 ///
+/// ## Kernel `printf` Output
+///
+/// `printf` may be called from any kernel on OpenCL 1.2+ devices without
+/// any additional setup. Output is buffered on the device and written to the
+/// standard output of the host process once the kernel has completed (call
+/// `Queue::finish` or wait on the kernel's event to be sure it has been
+/// written). The size of that buffer is fixed by the implementation and can
+/// be queried with `DeviceInfo::PrintfBufferSize`; output beyond it is lost.
+///
+/// Platforms supporting the `cl_arm_printf` extension additionally allow the
+/// buffer size to be set (`::printf_buffer_size`) and the output to be
+/// delivered to a callback instead of standard output (`::printf_callback`),
+/// which makes it possible to capture it from tests. On other platforms,
+/// output can only be captured by redirecting the standard output of the
+/// process.
+///
 // * TODO:
 //   - Handle context creation callbacks.
 //
@@ -283,6 +376,33 @@ impl ContextBuilder {
         self
     }
 
+    /// Specifies the size, in bytes, of the buffer which stores the output
+    /// of kernel `printf` calls (`CL_PRINTF_BUFFERSIZE_ARM`).
+    ///
+    /// Requires the `cl_arm_printf` extension. Context creation will fail on
+    /// platforms which do not support it.
+    ///
+    pub fn printf_buffer_size(&mut self, size: usize) -> &mut ContextBuilder {
+        self.properties.set_printf_buffer_size_arm(size);
+        self
+    }
+
+    /// Specifies a function which receives the output of kernel `printf`
+    /// calls instead of it being written to standard output
+    /// (`CL_PRINTF_CALLBACK_ARM`).
+    ///
+    /// The callback is passed a pointer to the (not necessarily
+    /// nul-terminated) output, its length in bytes, a flag which is non-zero
+    /// if the output is complete, and a user data pointer (always null).
+    ///
+    /// Requires the `cl_arm_printf` extension. Context creation will fail on
+    /// platforms which do not support it.
+    ///
+    pub fn printf_callback(&mut self, callback: PrintfCallbackFn) -> &mut ContextBuilder {
+        self.properties.set_printf_callback_arm(callback);
+        self
+    }
+
     /// Specifies a list of devices with which to associate the context.
     ///
     /// Devices may be specified in any number of ways including simply
@@ -3,8 +3,9 @@
 use std;
 use std::ops::{Deref, DerefMut};
 use std::borrow::Borrow;
-use crate::ffi::cl_device_id;
-use crate::core::{self, util, DeviceId as DeviceIdCore, DeviceType, DeviceInfo, DeviceInfoResult, ClDeviceIdPtr};
+use crate::ffi::{cl_device_id, cl_device_partition_property};
+use crate::core::{self, util, DeviceId as DeviceIdCore, DeviceType, DeviceInfo, DeviceInfoResult, ClDeviceIdPtr,
+    OpenclVersion, DevicePartitionProperty, DeviceAffinityDomain};
 use crate::error::{Error as OclError, Result as OclResult};
 use crate::standard::Platform;
 
@@ -21,6 +22,36 @@ pub enum DeviceError {
     ResolveIdxsInvalidIndex { idx: usize, max: usize },
 }
 
+/// A summary of the most commonly needed properties of a device.
+///
+/// Returned by [`Device::describe`]. Useful for logging environment details
+/// (crash reports, benchmark headers, etc.).
+///
+/// [`Device::describe`]: struct.Device.html#method.describe
+#[derive(Debug, Clone)]
+pub struct DeviceDescription {
+    pub name: String,
+    pub vendor: String,
+    pub version: OpenclVersion,
+    pub driver_version: String,
+    pub device_type: DeviceType,
+    pub max_compute_units: u32,
+    pub max_clock_frequency_mhz: u32,
+    pub global_mem_size: u64,
+    pub local_mem_size: u64,
+}
+
+impl std::fmt::Display for DeviceDescription {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} ({}): OpenCL {}, driver {}, {:?}, {} compute units @ {} MHz, \
+            {} MiB global memory, {} KiB local memory", self.name, self.vendor, self.version,
+            self.driver_version, self.device_type, self.max_compute_units,
+            self.max_clock_frequency_mhz, self.global_mem_size / (1 << 20),
+            self.local_mem_size / (1 << 10))
+    }
+}
+
+
 // Perhaps add something like this to the `DeviceSpecifier`.
 //
 // Copied from `https://github.com/TyOverby/ocl-repro/blob/master/src/main.rs`:
@@ -245,6 +276,81 @@ impl From<DeviceType> for DeviceSpecifier {
 }
 
 
+/// Specifies how a device is partitioned into sub-devices by
+/// `Device::create_sub_devices`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Partition {
+    /// As many sub-devices as possible, each containing the specified
+    /// number of compute units (`CL_DEVICE_PARTITION_EQUALLY`).
+    Equally(u32),
+    /// One sub-device for each of the specified compute unit counts
+    /// (`CL_DEVICE_PARTITION_BY_COUNTS`).
+    ByCounts(Vec<u32>),
+    /// Sub-devices sharing the specified level of the cache hierarchy or
+    /// NUMA node (`CL_DEVICE_PARTITION_BY_AFFINITY_DOMAIN`).
+    ByAffinityDomain(DeviceAffinityDomain),
+}
+
+impl Partition {
+    /// Returns the zero-terminated partition property list passed to
+    /// `clCreateSubDevices`.
+    fn to_properties(&self) -> Vec<cl_device_partition_property> {
+        match *self {
+            Partition::Equally(compute_units) => vec![
+                DevicePartitionProperty::Equally as cl_device_partition_property,
+                compute_units as cl_device_partition_property,
+                0,
+            ],
+            Partition::ByCounts(ref counts) => {
+                let mut props = vec![
+                    DevicePartitionProperty::ByCounts as cl_device_partition_property];
+                props.extend(counts.iter().map(|&c| c as cl_device_partition_property));
+                props.push(DevicePartitionProperty::ByCountsListEnd as cl_device_partition_property);
+                props.push(0);
+                props
+            },
+            Partition::ByAffinityDomain(domain) => vec![
+                DevicePartitionProperty::ByAffinityDomain as cl_device_partition_property,
+                domain.bits() as cl_device_partition_property,
+                0,
+            ],
+        }
+    }
+}
+
+
+/// A sub-device created by `Device::create_sub_devices` which is released
+/// when dropped.
+///
+/// Dereferences to `Device` and can be used anywhere one is accepted.
+/// Contexts created using a sub-device retain it, but copies of the `Device`
+/// itself must not be used after the `SubDevice` has been dropped unless
+/// such a context still exists.
+#[derive(Debug)]
+pub struct SubDevice(Device);
+
+impl SubDevice {
+    /// Returns the sub-device as a `Device`.
+    pub fn device(&self) -> Device {
+        self.0
+    }
+}
+
+impl Deref for SubDevice {
+    type Target = Device;
+
+    fn deref(&self) -> &Device {
+        &self.0
+    }
+}
+
+impl Drop for SubDevice {
+    fn drop(&mut self) {
+        unsafe { core::release_device(&(self.0).0, None).unwrap(); }
+    }
+}
+
+
 /// An individual device identifier (an OpenCL device_id).
 ///
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -407,6 +513,79 @@ impl Device {
         }
     }
 
+    /// Returns the number of parallel compute units of the device
+    /// (`CL_DEVICE_MAX_COMPUTE_UNITS`) or an error.
+    pub fn max_compute_units(&self) -> OclResult<u32> {
+        match self.info(DeviceInfo::MaxComputeUnits) {
+            Ok(DeviceInfoResult::MaxComputeUnits(r)) => Ok(r),
+            Err(err) => Err(err),
+            _ => panic!("Device::max_compute_units: Unexpected 'DeviceInfoResult' variant."),
+        }
+    }
+
+    /// Returns the maximum configured clock frequency of the device in MHz
+    /// (`CL_DEVICE_MAX_CLOCK_FREQUENCY`) or an error.
+    pub fn max_clock_frequency(&self) -> OclResult<u32> {
+        match self.info(DeviceInfo::MaxClockFrequency) {
+            Ok(DeviceInfoResult::MaxClockFrequency(r)) => Ok(r),
+            Err(err) => Err(err),
+            _ => panic!("Device::max_clock_frequency: Unexpected 'DeviceInfoResult' variant."),
+        }
+    }
+
+    /// Returns the maximum number of work-items in each dimension of a
+    /// work-group or an error.
+    pub fn max_work_item_sizes(&self) -> OclResult<Vec<usize>> {
+        match self.info(DeviceInfo::MaxWorkItemSizes) {
+            Ok(DeviceInfoResult::MaxWorkItemSizes(r)) => Ok(r),
+            Err(err) => Err(err),
+            _ => panic!("Device::max_work_item_sizes: Unexpected 'DeviceInfoResult' variant."),
+        }
+    }
+
+    /// Partitions this device into sub-devices (device fission) and returns
+    /// them.
+    ///
+    /// Sub-devices can be used anywhere a `Device` is accepted, for example
+    /// to create a context and queue restricted to a subset of the compute
+    /// units of a device (useful for isolating latency-sensitive work).
+    /// Check `DeviceInfo::PartitionProperties` for the partition types
+    /// supported by this device.
+    ///
+    /// Each returned `SubDevice` releases its sub-device when dropped.
+    ///
+    /// [Version Controlled: OpenCL 1.2+]
+    pub fn create_sub_devices(&self, partition: Partition) -> OclResult<Vec<SubDevice>> {
+        if let Partition::ByCounts(ref counts) = partition {
+            if counts.is_empty() || counts.contains(&0) {
+                return Err("Device::create_sub_devices: Partition counts must be non-empty \
+                    and non-zero.".into());
+            }
+        }
+
+        let sub_devices = core::create_sub_devices(&self.0, &partition.to_properties(), None)?;
+        Ok(sub_devices.into_iter().map(|d| SubDevice(Device(d))).collect())
+    }
+
+    /// Returns the size of the global device memory in bytes or an error.
+    pub fn global_mem_size(&self) -> OclResult<u64> {
+        match self.info(DeviceInfo::GlobalMemSize) {
+            Ok(DeviceInfoResult::GlobalMemSize(r)) => Ok(r),
+            Err(err) => Err(err),
+            _ => panic!("Device::global_mem_size: Unexpected 'DeviceInfoResult' variant."),
+        }
+    }
+
+    /// Returns the maximum size, in bytes, of a single memory object
+    /// allocation or an error.
+    pub fn max_mem_alloc_size(&self) -> OclResult<u64> {
+        match self.info(DeviceInfo::MaxMemAllocSize) {
+            Ok(DeviceInfoResult::MaxMemAllocSize(r)) => Ok(r),
+            Err(err) => Err(err),
+            _ => panic!("Device::max_mem_alloc_size: Unexpected 'DeviceInfoResult' variant."),
+        }
+    }
+
     /// Returns the memory base address alignment offset or an error.
     pub fn mem_base_addr_align(&self) -> OclResult<u32> {
         match self.info(DeviceInfo::MemBaseAddrAlign) {
@@ -416,6 +595,14 @@ impl Device {
         }
     }
 
+    /// Returns the OpenCL version supported by the device.
+    ///
+    /// The version is parsed from the `CL_DEVICE_VERSION` string and can be
+    /// compared directly, e.g.: `device.version()? >= OpenclVersion::new(2, 0)`.
+    pub fn version(&self) -> OclResult<OpenclVersion> {
+        self.0.version().map_err(OclError::from)
+    }
+
     /// Returns whether or not the device is available for use.
     pub fn is_available(&self) -> OclResult<bool> {
         match self.info(DeviceInfo::Available) {
@@ -425,6 +612,91 @@ impl Device {
         }
     }
 
+    /// Returns a summary of the most commonly needed properties of this
+    /// device in a single call.
+    pub fn describe(&self) -> OclResult<DeviceDescription> {
+        let version = self.version()?;
+        let driver_version = match self.info(DeviceInfo::DriverVersion)? {
+            DeviceInfoResult::DriverVersion(r) => r,
+            _ => panic!("Device::describe: Unexpected 'DeviceInfoResult' variant."),
+        };
+        let device_type = match self.info(DeviceInfo::Type)? {
+            DeviceInfoResult::Type(r) => r,
+            _ => panic!("Device::describe: Unexpected 'DeviceInfoResult' variant."),
+        };
+        let local_mem_size = match self.info(DeviceInfo::LocalMemSize)? {
+            DeviceInfoResult::LocalMemSize(r) => r,
+            _ => panic!("Device::describe: Unexpected 'DeviceInfoResult' variant."),
+        };
+
+        Ok(DeviceDescription {
+            name: self.name()?,
+            vendor: self.vendor()?,
+            version,
+            driver_version,
+            device_type,
+            max_compute_units: self.max_compute_units()?,
+            max_clock_frequency_mhz: self.max_clock_frequency()?,
+            global_mem_size: self.global_mem_size()?,
+            local_mem_size,
+        })
+    }
+
+    /// Returns whether or not the device supports the extension named
+    /// `extension` (e.g. "cl_khr_fp64").
+    pub fn has_extension(&self, extension: &str) -> OclResult<bool> {
+        match self.info(DeviceInfo::Extensions) {
+            Ok(DeviceInfoResult::Extensions(r)) => Ok(r.split_whitespace().any(|e| e == extension)),
+            Err(err) => Err(err),
+            _ => panic!("Device::has_extension: Unexpected 'DeviceInfoResult' variant."),
+        }
+    }
+
+    /// Returns whether or not the device supports double precision floating
+    /// point (`double` / `f64`) operations (the `cl_khr_fp64` extension).
+    ///
+    /// Kernels using `double` will fail to build or produce incorrect
+    /// results on devices lacking support (common on consumer GPUs). Check
+    /// this before using `f64` kernels or buffers.
+    pub fn supports_fp64(&self) -> OclResult<bool> {
+        self.has_extension("cl_khr_fp64")
+    }
+
+    /// Returns whether or not the device supports shared virtual memory (SVM)
+    /// of any kind (`CL_DEVICE_SVM_CAPABILITIES`).
+    ///
+    /// Always false for devices below OpenCL 2.0.
+    ///
+    /// [Version Controlled: OpenCL 2.0+]
+    #[cfg(feature = "opencl_version_2_0")]
+    pub fn supports_svm(&self) -> OclResult<bool> {
+        if self.version()? < OpenclVersion::new(2, 0) { return Ok(false); }
+        let capabilities = self.info_raw(crate::ffi::CL_DEVICE_SVM_CAPABILITIES)?;
+        Ok(capabilities.iter().any(|&b| b != 0))
+    }
+
+    /// Returns a reasonably synchronized pair of timestamps from the device
+    /// timer and the host timer, as `(device_timestamp, host_timestamp)`, in
+    /// nanoseconds.
+    ///
+    /// Use this to correlate event profiling timestamps (which are in device
+    /// time) from multiple queues with each other and with the host clock.
+    ///
+    /// [Version Controlled: OpenCL 2.1+]
+    #[cfg(feature = "opencl_version_2_1")]
+    pub fn device_and_host_timer(&self) -> OclResult<(u64, u64)> {
+        core::get_device_and_host_timer(&self.0, None).map_err(OclError::from)
+    }
+
+    /// Returns the current value of the host clock, in nanoseconds, as seen
+    /// by this device.
+    ///
+    /// [Version Controlled: OpenCL 2.1+]
+    #[cfg(feature = "opencl_version_2_1")]
+    pub fn host_timer(&self) -> OclResult<u64> {
+        core::get_host_timer(&self.0, None).map_err(OclError::from)
+    }
+
     /// Returns raw info about the device, as a vector of bytes. Intended for use with non-standard
     /// OpenCL extensions.
     pub fn info_raw(&self, info_kind: u32) -> OclResult<Vec<u8>> {
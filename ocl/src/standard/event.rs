@@ -20,19 +20,21 @@ use std::{mem, ptr, fmt};
 use std::borrow::Borrow;
 use std::ops::{Deref, DerefMut};
 use std::cell::Ref;
+#[cfg(feature = "async")]
 use futures::{Future, Poll, Async};
-#[cfg(not(feature = "async_block"))]
+#[cfg(all(feature = "async", not(feature = "async_block")))]
 use futures::task;
 use crate::ffi::cl_event;
 use crate::core::{self, Event as EventCore, EventInfo, EventInfoResult, ProfilingInfo,
     ProfilingInfoResult, ClNullEventPtr, ClWaitListPtr, ClEventPtrRef,
-    CommandQueue as CommandQueueCore, ClContextPtr};
+    CommandQueue as CommandQueueCore, ClContextPtr, CommandExecutionStatus, CommandType};
 use crate::error::{Error as OclError, Result as OclResult};
 use crate::standard::{Queue, ClWaitListPtrEnum};
-#[cfg(not(feature = "async_block"))]
+#[cfg(all(feature = "async", not(feature = "async_block")))]
 use crate::standard::{_unpark_task, box_raw_void};
 use self::nodrop::NoDrop;
 
+#[cfg(feature = "async")]
 const PRINT_DEBUG: bool = false;
 
 /// An event representing a command or user created event.
@@ -49,12 +51,67 @@ impl Event {
         Event(EventCore::null())
     }
 
-    /// Creates a new, empty event which must be filled by a newly initiated
-    /// command, associating the event with it.
+    /// Creates a new, incomplete user event associated with `context`.
+    ///
+    /// User events allow the host to control when commands run. Pass a user
+    /// event as a wait event (e.g. `.ewait(&event)`) to commands enqueued on
+    /// any queue of the same context and they will not begin executing until
+    /// the event is set complete with `::set_complete` or `::set_status`.
+    ///
+    /// Commands waiting on a user event which is never completed never run
+    /// (and `Queue::finish` will block indefinitely).
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// let gate = Event::user(&context)?;
+    /// unsafe { kernel.cmd().ewait(&gate).enq()?; }
+    /// // ... prepare host-side state ...
+    /// gate.set_complete()?;
+    /// ```
     pub fn user<C: ClContextPtr>(context: C) -> OclResult<Event> {
         EventCore::user(context).map(Event).map_err(OclError::from)
     }
 
+    /// Sets the execution status of this user event (`clSetUserEventStatus`).
+    ///
+    /// `CommandExecutionStatus::Complete` is the only status which may be set
+    /// (equivalent to `::set_complete`). Returns an error if this is not a
+    /// user event (created with `::user`) or if its status has already been
+    /// set.
+    pub fn set_status(&self, status: CommandExecutionStatus) -> OclResult<()> {
+        core::set_user_event_status(&self.0, status).map_err(OclError::from)
+    }
+
+    /// Replaces this user event with a new, incomplete user event associated
+    /// with the same context.
+    ///
+    /// User events cannot be returned to an incomplete state once their
+    /// status has been set. This releases the old event and creates a fresh
+    /// one in its place, allowing the same `Event` to be reused as a
+    /// recurring synchronization point. Clones of this event made before
+    /// the reset continue to refer to the old event.
+    ///
+    /// Returns an error if this event is empty or is not a user event
+    /// (created with `::user`).
+    pub fn reset_user(&mut self) -> OclResult<()> {
+        if self.0.is_null() {
+            return Err("Event::reset_user: Cannot reset an empty (null) event.".into());
+        }
+        match self.info(EventInfo::CommandType)? {
+            EventInfoResult::CommandType(CommandType::User) => (),
+            EventInfoResult::CommandType(_) => {
+                return Err("Event::reset_user: This event is not a user event.".into());
+            },
+            _ => unreachable!(),
+        }
+        let context = match self.info(EventInfo::Context)? {
+            EventInfoResult::Context(context) => context,
+            _ => unreachable!(),
+        };
+        self.0 = EventCore::user(&context)?;
+        Ok(())
+    }
+
     /// Returns true if this event is 'empty' and has not yet been associated
     /// with a command.
     ///
@@ -73,7 +130,7 @@ impl Event {
     /// This function will panic if a task is not currently being executed.
     /// That is, this method can be dangerous to call outside of an
     /// implementation of poll.
-    #[cfg(not(feature = "async_block"))]
+    #[cfg(all(feature = "async", not(feature = "async_block")))]
     pub fn set_unpark_callback(&self) -> OclResult<()> {
         let task_ptr = box_raw_void(task::current());
         unsafe { self.set_callback(_unpark_task, task_ptr).map_err(OclError::from) }
@@ -123,6 +180,38 @@ impl Event {
         }
     }
 
+    /// Increments the reference count of the underlying event object.
+    ///
+    /// Cloning an `Event` already does this (and dropping it decrements the
+    /// count). Only use this when the event must outlive every `Event`
+    /// referring to it, such as when it is being handed off through FFI.
+    /// Each call must eventually be balanced by a call to `::release` or
+    /// the event will leak.
+    ///
+    /// Returns an error if this event is empty (null).
+    pub fn retain(&self) -> OclResult<()> {
+        if self.0.is_null() {
+            return Err("Event::retain: Cannot retain an empty (null) event.".into());
+        }
+        unsafe { core::retain_event(&self.0).map_err(OclError::from) }
+    }
+
+    /// Decrements the reference count of the underlying event object.
+    ///
+    /// Returns an error if this event is empty (null).
+    ///
+    /// ### Safety
+    ///
+    /// Must only be used to balance a previous call to `::retain`. Releasing
+    /// an event more times than it has been retained will cause the event
+    /// to be destroyed while still in use (by this `Event` or its clones).
+    pub unsafe fn release(&self) -> OclResult<()> {
+        if self.0.is_null() {
+            return Err("Event::release: Cannot release an empty (null) event.".into());
+        }
+        core::release_event(&self.0).map_err(OclError::from)
+    }
+
     /// Returns a reference to the core pointer wrapper, usable by functions in
     /// the `core` module.
     ///
@@ -131,6 +220,19 @@ impl Event {
         &self.0
     }
 
+    /// Returns the raw `cl_event` pointer (which will be null if this event
+    /// is empty).
+    ///
+    /// The pointer is owned by this `Event` and is only valid for as long as
+    /// it (or a clone) exists. Do not release it. To take ownership of the
+    /// pointer, use [`Event::into_raw`][into_raw] instead.
+    ///
+    /// [into_raw]: struct.Event.html#method.into_raw
+    #[inline]
+    pub fn as_ptr(&self) -> cl_event {
+        unsafe { *self.0.as_ptr_ref() }
+    }
+
     /// Consumes the `Event`, returning the wrapped `cl_event` pointer.
     ///
     /// To avoid a memory leak the pointer must be converted back to an `Event` using
@@ -249,6 +351,7 @@ unsafe impl<'a> ClWaitListPtr for  &'a Event {
     #[inline] fn count(&self) -> u32 { self._count() }
 }
 
+#[cfg(feature = "async")]
 impl Future for Event {
     type Item = ();
     type Error = OclError;
@@ -308,6 +411,7 @@ fn take(event: &mut Event) -> Event {
 
 
 /// Polls events for `EventArray` and `EventList`
+#[cfg(feature = "async")]
 fn poll_events(events: &[Event]) -> Poll<(), OclError> {
     if PRINT_DEBUG { println!("####### EventList/Array::poll: Polling Event list (thread: '{}')",
         ::std::thread::current().name().unwrap_or("<unnamed>")); }
@@ -594,6 +698,7 @@ impl fmt::Debug for EventArray {
     }
 }
 
+#[cfg(feature = "async")]
 impl Future for EventArray {
     type Item = ();
     type Error = OclError;
@@ -628,6 +733,33 @@ unsafe impl<'a> ClWaitListPtr for &'a EventArray {
 
 
 
+/// Aggregate device timing for the commands of an `EventList`.
+///
+/// Returned by `EventList::profiling_summary`. All values are in
+/// nanoseconds and are measured using the `Start` and `End` profiling
+/// counters of each command.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProfilingSummary {
+    /// The earliest `Start` time of any event (zero if the list is empty).
+    pub start_ns: u64,
+    /// The latest `End` time of any event.
+    pub end_ns: u64,
+    /// The sum of the execution times of every event. This exceeds
+    /// `::span_ns` when commands overlap.
+    pub total_ns: u64,
+    /// The execution time of each event, in list order.
+    pub durations_ns: Vec<u64>,
+}
+
+impl ProfilingSummary {
+    /// Returns the time from the earliest start to the latest end, the
+    /// wall-clock time during which the device was busy with these commands.
+    pub fn span_ns(&self) -> u64 {
+        self.end_ns.saturating_sub(self.start_ns)
+    }
+}
+
+
 /// The guts of an EventList.
 #[derive(Debug, Clone)]
 enum Inner {
@@ -766,6 +898,51 @@ impl EventList {
         }
     }
 
+    /// Returns a new list containing the events of this list suitable for use
+    /// as a wait list: empty events and duplicates are removed and, if
+    /// `skip_completed` is `true`, so are events which have already
+    /// completed.
+    ///
+    /// Skipping completed events requires querying the status of each
+    /// event. Some drivers reject wait lists containing duplicate events.
+    pub fn to_wait_list(&self, skip_completed: bool) -> OclResult<EventList> {
+        let mut wait_list = EventList::with_capacity(self.len());
+
+        for event in self.iter() {
+            if event.is_empty() || wait_list.iter().any(|e| e.as_ptr() == event.as_ptr()) {
+                continue;
+            }
+            if skip_completed && event.is_complete()? {
+                continue;
+            }
+            wait_list.push(event.clone());
+        }
+
+        Ok(wait_list)
+    }
+
+    /// Returns an error if any (non-empty) event in this list is not
+    /// associated with `context`.
+    ///
+    /// Waiting on events from another context causes enqueue functions to
+    /// fail with `CL_INVALID_CONTEXT` or `CL_INVALID_EVENT_WAIT_LIST`.
+    pub fn verify_context<C: ClContextPtr>(&self, context: C) -> OclResult<()> {
+        for (idx, event) in self.iter().enumerate() {
+            if event.is_empty() { continue; }
+
+            match event.info(EventInfo::Context)? {
+                EventInfoResult::Context(ref event_context) => {
+                    if event_context.as_ptr() != context.as_ptr() {
+                        return Err(format!("EventList::verify_context: The event at index {} \
+                            is associated with a different context.", idx).into());
+                    }
+                },
+                _ => unreachable!(),
+            }
+        }
+        Ok(())
+    }
+
     /// Blocks the host thread until all events in this list are complete.
     pub fn wait_for(&self) -> OclResult<()> {
         match self.inner {
@@ -779,6 +956,43 @@ impl EventList {
         }
     }
 
+    /// Blocks until all events in this list are complete then returns their
+    /// combined device timing.
+    ///
+    /// Every event must have been created by a command enqueued on a queue
+    /// with profiling enabled (`CommandQueueProperties::PROFILING_ENABLE`).
+    /// Returns an error, without waiting, if any event is empty or is a user
+    /// event.
+    pub fn profiling_summary(&self) -> OclResult<ProfilingSummary> {
+        // Validate every event before waiting so that an incomplete user
+        // event causes an error rather than blocking forever:
+        for (idx, event) in self.iter().enumerate() {
+            if event.is_empty() {
+                return Err(format!("EventList::profiling_summary: The event at index {} is \
+                    empty.", idx).into());
+            }
+            if let EventInfoResult::CommandType(CommandType::User) =
+                    event.info(EventInfo::CommandType)? {
+                return Err(format!("EventList::profiling_summary: The event at index {} is a \
+                    user event and has no profiling information.", idx).into());
+            }
+        }
+
+        self.wait_for()?;
+
+        let mut summary = ProfilingSummary::default();
+        for (idx, event) in self.iter().enumerate() {
+            let start = event.profiling_info(ProfilingInfo::Start)?.time()?;
+            let end = event.profiling_info(ProfilingInfo::End)?.time()?;
+
+            summary.start_ns = if idx == 0 { start } else { summary.start_ns.min(start) };
+            summary.end_ns = summary.end_ns.max(end);
+            summary.total_ns += end.saturating_sub(start);
+            summary.durations_ns.push(end.saturating_sub(start));
+        }
+        Ok(summary)
+    }
+
     /// Enqueue a marker event representing the completion of each and every
     /// event in this list.
     ///
@@ -1196,6 +1410,7 @@ impl IntoIterator for EventList {
     }
 }
 
+#[cfg(feature = "async")]
 impl Future for EventList {
     type Item = ();
     type Error = OclError;
@@ -15,7 +15,7 @@ use crate::core::{self, OclPrm, Mem as MemCore, MemFlags, MemObjectType, ImageFo
     ImageChannelOrder, ImageChannelDataType, AsMem, MemCmdRw, MemCmdAll,
     MapFlags};
 use crate::standard::{Context, Queue, SpatialDims, ClNullEventPtrEnum, ClWaitListPtrEnum,
-    QueCtx, HostSlice};
+    QueCtx, HostSlice, Event};
 use crate::MemMap;
 
 #[cfg(not(feature="opencl_vendor_mesa"))]
@@ -69,7 +69,7 @@ impl<'c, T: 'c> ImageCmdKind<'c, T> {
 ///
 /// ```
 ///
-/// [FIXME]: Fills not yet implemented.
+/// [FIXME]: Fills not yet implemented (use `Image::fill` instead).
 #[must_use = "commands do nothing unless enqueued"]
 #[allow(dead_code)]
 pub struct ImageCmd<'c, T: 'c> {
@@ -830,6 +830,82 @@ impl<T: OclPrm> Image<T> {
         self.cmd().write(data)
     }
 
+    /// Fills the region of this image starting at `origin` and spanning
+    /// `region` pixels with `color` after every event in `wait` has
+    /// completed, returning the event associated with the fill command.
+    ///
+    /// The fill is enqueued on the default queue and does not block. `color`
+    /// is an RGBA value which is converted to the channel order and data
+    /// type of the image. For unnormalized integer channel data types
+    /// (`SignedInt*` and `UnsignedInt*`) each component is first converted to
+    /// an `i32` or `u32` (rounding toward zero and saturating), as
+    /// `clEnqueueFillImage` expects. Unused dimensions of `origin` and
+    /// `region` must be `0` and `1` respectively (as with
+    /// `clEnqueueFillImage`).
+    ///
+    /// Useful for clearing a frame between passes without uploading data
+    /// from the host.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if no default queue is set, if `region` contains a
+    /// zero dimension, or if `origin` and `region` extend past the
+    /// dimensions of this image. Nothing is enqueued in those cases.
+    ///
+    /// [Version Controlled: OpenCL 1.2+]
+    pub fn fill(&self, color: [f32; 4], origin: [usize; 3], region: [usize; 3], wait: &[Event])
+            -> OclResult<Event> {
+        let queue = match self.queue {
+            Some(ref q) => q,
+            None => return Err("Image::fill: No default queue set.".into()),
+        };
+        let dims = self.dims.to_lens().expect("ocl::Image::fill");
+
+        for i in 0..3 {
+            let in_bounds = match origin[i].checked_add(region[i]) {
+                Some(end) => end <= dims[i],
+                None => false,
+            };
+            if region[i] == 0 || !in_bounds {
+                return Err(format!("Image::fill: The fill region (origin: {:?}, region: {:?}) \
+                    must be non-empty and lie within the image dimensions ({:?}).",
+                    origin, region, dims).into());
+            }
+        }
+
+        let channel_data_type = match self.info(ImageInfo::Format)? {
+            ImageInfoResult::Format(Ok(format)) => format.channel_data_type,
+            ImageInfoResult::Format(Err(err)) => return Err(format!("Image::fill: Unable to \
+                determine the image format: {}", err).into()),
+            _ => unreachable!(),
+        };
+
+        let mut event = Event::empty();
+        let ewait = if wait.is_empty() { None } else { Some(ClWaitListPtrEnum::from(wait)) };
+        let device_version = queue.device_version();
+
+        match channel_data_type {
+            ImageChannelDataType::SignedInt8 | ImageChannelDataType::SignedInt16 |
+                    ImageChannelDataType::SignedInt32 => {
+                let color = [color[0] as i32, color[1] as i32, color[2] as i32, color[3] as i32];
+                core::enqueue_fill_image(queue, &self.obj_core, &color[..], origin, region,
+                    ewait, Some(&mut event), Some(&device_version))?;
+            },
+            ImageChannelDataType::UnsignedInt8 | ImageChannelDataType::UnsignedInt16 |
+                    ImageChannelDataType::UnsignedInt32 => {
+                let color = [color[0] as u32, color[1] as u32, color[2] as u32, color[3] as u32];
+                core::enqueue_fill_image(queue, &self.obj_core, &color[..], origin, region,
+                    ewait, Some(&mut event), Some(&device_version))?;
+            },
+            _ => {
+                core::enqueue_fill_image(queue, &self.obj_core, &color[..], origin, region,
+                    ewait, Some(&mut event), Some(&device_version))?;
+            },
+        }
+
+        Ok(event)
+    }
+
     /// Returns a command builder used to map data for reading or writing.
     ///
     /// Call `.enq()` to enqueue the command.
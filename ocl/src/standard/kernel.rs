@@ -9,14 +9,17 @@ use std::collections::{HashMap, BTreeMap};
 use std::marker::PhantomData;
 use std::cell::RefCell;
 use std::borrow::Borrow;
-use crate::core::ffi::c_void;
+use crate::core::ffi::{c_void, cl_kernel};
 use crate::core::{self, util, OclPrm, Kernel as KernelCore, CommandQueue as CommandQueueCore, Mem as MemCore,
     ArgVal, KernelInfo, KernelInfoResult, KernelArgInfo, KernelArgInfoResult,
-    KernelWorkGroupInfo, KernelWorkGroupInfoResult, AsMem, MemCmdAll, ClVersions};
+    KernelWorkGroupInfo, KernelWorkGroupInfoResult, KernelArgAddressQualifier, AsMem, MemCmdAll,
+    ClVersions, Status, ClNullEventPtr, ProfilingInfo, OpenclVersion, MemFlags};
 use crate::core::error::{ErrorKind as OclCoreErrorKind};
+#[cfg(feature = "opencl_version_2_1")]
+use crate::core::KernelSubGroupInfo;
 use crate::error::{Error as OclError, Result as OclResult, ErrorKind as OclErrorKind};
 use crate::standard::{SpatialDims, Program, Queue, WorkDims, Sampler, Device, ClNullEventPtrEnum,
-    ClWaitListPtrEnum, Buffer, Image};
+    ClWaitListPtrEnum, Buffer, Image, Event, EventList};
 pub use self::arg_type::{BaseType, Cardinality, ArgType};
 
 
@@ -47,9 +50,143 @@ pub enum KernelError {
         (required: {}, specified: {}). Use named arguments with 'None' or zero values to \
         declare arguments you plan to assign a value to at a later time.", required, specified)]
     BuilderWrongArgCount { required: u32, specified: u32 },
+    #[fail(display = "Invalid {} work size: {:?}. Work size dimensions may not be zero.",
+        _0, _1)]
+    ZeroWorkSize(&'static str, SpatialDims),
+    #[fail(display = "No kernel named '{}' exists within the program. Available kernels: {:?}.",
+        name, available)]
+    BuilderInvalidKernelName { name: String, available: Vec<String> },
+    #[fail(display = "Kernel function name mismatch (expected: '{}', actual: '{}').",
+        expected, actual)]
+    NameMismatch { expected: String, actual: String },
+    #[fail(display = "Kernel argument info is not available. The program must be built with \
+        the '-cl-kernel-arg-info' compiler option.")]
+    ArgInfoUnavailable,
+    #[fail(display = "The local work size ({:?}) does not evenly divide the global work size \
+        ({:?}) in dimension {}. Devices below OpenCL 2.0 require uniform work-groups.",
+        lws, gws, dim)]
+    NonUniformWorkGroup { dim: usize, gws: SpatialDims, lws: SpatialDims },
+    #[fail(display = "Invalid batch size: {} elements ({} bytes). Batches must be non-empty and \
+        span a multiple of the device's base address alignment ({} bytes).", batch, batch_bytes,
+        align_bytes)]
+    BatchSize { batch: usize, batch_bytes: usize, align_bytes: usize },
+    #[fail(display = "Invalid work dimensions: {} (global work size: {:?}, local work size: \
+        {:?}). The dimension count must be 1, 2, or 3 and match the length of each work size.",
+        dims, gws, lws)]
+    WorkDims { dims: u32, gws: Vec<usize>, lws: Option<Vec<usize>> },
+    #[fail(display = "Unable to set one or more named scalar arguments: {:?}.", errors)]
+    NamedScalars { errors: Vec<String> },
 }
 
 
+/// Returns an error if any specified dimension of `dims` is zero.
+fn check_work_size(kind: &'static str, dims: SpatialDims) -> OclResult<SpatialDims> {
+    match dims.validate() {
+        Ok(()) => Ok(dims),
+        Err(_) => Err(KernelError::ZeroWorkSize(kind, dims).into()),
+    }
+}
+
+
+/// Converts work sizes given as slices into `SpatialDims`, verifying that
+/// `dims` is 1, 2, or 3 and that each slice has `dims` elements.
+fn work_dims_from_slices(dims: u32, gws: &[usize], lws: Option<&[usize]>)
+        -> Result<(SpatialDims, SpatialDims), KernelError> {
+    let to_spatial = |sizes: &[usize]| match *sizes {
+        [d0] => Some(SpatialDims::One(d0)),
+        [d0, d1] => Some(SpatialDims::Two(d0, d1)),
+        [d0, d1, d2] => Some(SpatialDims::Three(d0, d1, d2)),
+        _ => None,
+    };
+
+    let err = || KernelError::WorkDims { dims, gws: gws.to_vec(), lws: lws.map(|l| l.to_vec()) };
+
+    if gws.len() != dims as usize || lws.is_some_and(|l| l.len() != dims as usize) {
+        return Err(err());
+    }
+    let gws = to_spatial(gws).ok_or_else(err)?;
+    let lws = match lws {
+        Some(lws) => to_spatial(lws).ok_or_else(err)?,
+        None => SpatialDims::Unspecified,
+    };
+    Ok((gws, lws))
+}
+
+
+/// Returns the index of the first dimension in which `lws` does not evenly
+/// divide `gws`, if any.
+fn non_uniform_dim(gws: [usize; 3], lws: [usize; 3]) -> Option<usize> {
+    (0..3).find(|&dim| lws[dim] != 0 && !gws[dim].is_multiple_of(lws[dim]))
+}
+
+
+/// A heuristic used by `Kernel::auto_lws_with` to choose a local work size.
+///
+/// Every strategy only chooses sizes which evenly divide the global work
+/// size and which respect the maximum work-group size of the kernel and the
+/// maximum work-item sizes of the device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LwsStrategy {
+    /// Makes each dimension, in order, as large as possible.
+    Max,
+    /// Makes the first dimension the largest possible multiple of the
+    /// preferred work-group size multiple of the kernel
+    /// (`CL_KERNEL_PREFERRED_WORK_GROUP_SIZE_MULTIPLE`), falling back to
+    /// `Max` if there is none.
+    Occupancy,
+    /// Balances the size of each dimension (e.g. `16x16` rather than
+    /// `256x1`). Equivalent to `Max` for one-dimensional work sizes.
+    Square,
+}
+
+
+/// Returns the largest divisor of `len` which is no greater than `limit` and
+/// which is a multiple of `multiple`.
+fn largest_divisor(len: usize, limit: usize, multiple: usize) -> Option<usize> {
+    (1..=limit.min(len)).rev().find(|&d| len.is_multiple_of(d) && d.is_multiple_of(multiple))
+}
+
+/// Returns the number of work-groups, per dimension, launched by a dispatch
+/// with the global work size `gws` and local work size `lws`.
+fn work_group_count(gws: SpatialDims, lws: SpatialDims) -> Option<(usize, usize, usize)> {
+    match (gws.to_lens(), lws.to_lens()) {
+        (Ok(g), Ok(l)) if !l.contains(&0) => Some((util::padded_len(g[0], l[0]) / l[0],
+            util::padded_len(g[1], l[1]) / l[1], util::padded_len(g[2], l[2]) / l[2])),
+        _ => None,
+    }
+}
+
+/// Chooses a local work size for a global work size of `gws` (`dim_count`
+/// dimensions) according to `strategy`.
+fn choose_lws(gws: [usize; 3], dim_count: usize, max_wg_size: usize, max_wi_sizes: &[usize],
+        preferred_multiple: usize, strategy: LwsStrategy) -> [usize; 3] {
+    let mut lws = [1; 3];
+    let mut remaining = max_wg_size.max(1);
+
+    // The balanced per-dimension size for `LwsStrategy::Square`:
+    let mut square_side = 1;
+    while (square_side + 1usize).pow(dim_count as u32) <= remaining {
+        square_side += 1;
+    }
+
+    for dim in 0..dim_count {
+        let mut limit = remaining.min(max_wi_sizes.get(dim).cloned().unwrap_or(1));
+        if strategy == LwsStrategy::Square { limit = limit.min(square_side); }
+
+        let preferred = match strategy {
+            LwsStrategy::Occupancy if dim == 0 && preferred_multiple > 1 => {
+                largest_divisor(gws[dim], limit, preferred_multiple)
+            },
+            _ => None,
+        };
+
+        lws[dim] = preferred.or_else(|| largest_divisor(gws[dim], limit, 1)).unwrap_or(1);
+        remaining /= lws[dim];
+    }
+
+    lws
+}
+
 /// A kernel command builder used to enqueue a kernel with a mix of default
 /// and optionally specified arguments.
 #[must_use = "commands do nothing unless enqueued"]
@@ -61,6 +198,11 @@ pub struct KernelCmd<'k> {
     lws: SpatialDims,
     wait_events: Option<ClWaitListPtrEnum<'k>>,
     new_event: Option<ClNullEventPtrEnum<'k>>,
+    timing: Option<&'k RefCell<KernelTiming>>,
+    pending_waits: &'k RefCell<EventList>,
+    last_event: Option<&'k RefCell<Event>>,
+    arg_cache: Option<&'k RefCell<ArgCache>>,
+    transient_args: &'k RefCell<Vec<(u32, MemCore)>>,
 }
 
 /// A kernel enqueue command.
@@ -188,12 +330,17 @@ impl<'k> KernelCmd<'k> {
 
     /// Enqueues this kernel command.
     ///
+    /// Returns an error if the global or local work size contains a zero
+    /// dimension (see `SpatialDims::validate`) or, on devices below OpenCL
+    /// 2.0, if the local work size does not evenly divide the global work
+    /// size (`KernelError::NonUniformWorkGroup`).
+    ///
     /// # Safety
     ///
     /// All kernel code must be considered untrusted. Therefore the act of
     /// calling this function contains implied unsafety even though the API
     /// itself is safe.
-    pub unsafe fn enq(self) -> OclResult<()> {
+    pub unsafe fn enq(mut self) -> OclResult<()> {
         let queue = match self.queue {
             Some(q) => q,
             None => return Err(KernelError::CmdNoQueue.into()),
@@ -205,14 +352,197 @@ impl<'k> KernelCmd<'k> {
             Some(gws) => gws,
             None => return Err(KernelError::CmdNoGws.into()),
         };
+        check_work_size("global", self.gws)?;
+        check_work_size("local", self.lws)?;
+
+        // Non-uniform work-groups are only allowed on OpenCL 2.0+ devices:
+        if let Some(lws) = self.lws.to_work_size() {
+            if let Some(dim) = non_uniform_dim(gws, lws) {
+                let version = queue.device_versions()?[0];
+                if version < OpenclVersion::new(2, 0) {
+                    return Err(KernelError::NonUniformWorkGroup { dim, gws: self.gws,
+                        lws: self.lws }.into());
+                }
+            }
+        }
+
+        // Events recorded by `Kernel::set_arg_after` are waited on in
+        // addition to any passed to `::ewait`:
+        let pending_waits = self.pending_waits;
+        let combined_waits = if pending_waits.borrow().is_empty() {
+            None
+        } else {
+            let mut combined = self.wait_events.take().map(EventList::from)
+                .unwrap_or_else(EventList::new);
+            for event in pending_waits.borrow().iter() {
+                combined.push(event.clone());
+            }
+            Some(combined)
+        };
+        let wait_events = match combined_waits {
+            Some(ref combined) => Some(ClWaitListPtrEnum::from(combined)),
+            None => self.wait_events,
+        };
+
+        if self.timing.is_none() && self.last_event.is_none() {
+            core::enqueue_kernel(queue, self.kernel, dim_count,
+                self.gwo.to_work_offset(), &gws, self.lws.to_work_size(),
+                wait_events, self.new_event)?;
+            pending_waits.borrow_mut().clear();
+            return release_transient_args(self.kernel, self.transient_args, self.arg_cache);
+        }
+
+        // Timing and completion tracking require an event regardless of
+        // whether or not the caller asked for one:
+        let mut event = Event::empty();
+        core::enqueue_kernel(queue, self.kernel, dim_count, self.gwo.to_work_offset(),
+            &gws, self.lws.to_work_size(), wait_events, Some(&mut event))?;
+        pending_waits.borrow_mut().clear();
+
+        if let Some(mut enew) = self.new_event {
+            enew.clone_from(&event);
+        }
+
+        if let Some(last_event) = self.last_event {
+            *last_event.borrow_mut() = event.clone();
+        }
+
+        // Record the event before releasing transient arguments so that a
+        // failed release does not lose track of the enqueued command:
+        let timing_res = match self.timing {
+            Some(timing) => timing.borrow_mut().push(event),
+            None => Ok(()),
+        };
+        let release_res = release_transient_args(self.kernel, self.transient_args,
+            self.arg_cache);
 
-        core::enqueue_kernel(queue, &self.kernel, dim_count, self.gwo.to_work_offset(),
-            &gws, self.lws.to_work_size(), self.wait_events, self.new_event)
-            .map_err(OclError::from)
+        timing_res.and(release_res)
     }
 }
 
 
+/// Aggregate device execution times for the enqueues of a `Kernel`.
+///
+/// Returned by `Kernel::timing_stats`. All times are in nanoseconds, as
+/// measured between the `Start` and `End` profiling counters of each
+/// command.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct KernelTimingStats {
+    /// The number of enqueues measured.
+    pub count: u64,
+    /// The sum of all measured execution times.
+    pub total_ns: u64,
+    /// The shortest measured execution time (zero if `count` is zero).
+    pub min_ns: u64,
+    /// The longest measured execution time.
+    pub max_ns: u64,
+}
+
+impl KernelTimingStats {
+    /// Returns the mean execution time or `None` if nothing has been
+    /// measured.
+    pub fn mean_ns(&self) -> Option<u64> {
+        self.total_ns.checked_div(self.count)
+    }
+
+    /// Adds a single execution time.
+    fn record(&mut self, duration_ns: u64) {
+        self.min_ns = if self.count == 0 { duration_ns } else { self.min_ns.min(duration_ns) };
+        self.max_ns = self.max_ns.max(duration_ns);
+        self.total_ns += duration_ns;
+        self.count += 1;
+    }
+}
+
+
+/// Timing state for a kernel with timing enabled.
+#[derive(Debug, Default)]
+struct KernelTiming {
+    pending: Vec<Event>,
+    stats: KernelTimingStats,
+}
+
+impl KernelTiming {
+    /// Adds a newly enqueued command's event, first folding in any leading
+    /// events which have already completed.
+    fn push(&mut self, event: Event) -> OclResult<()> {
+        self.collect(false)?;
+        self.pending.push(event);
+        Ok(())
+    }
+
+    /// Folds the execution time of pending events into the stats, stopping
+    /// at the first incomplete event unless `wait` is set in which case all
+    /// are waited on.
+    fn collect(&mut self, wait: bool) -> OclResult<()> {
+        let mut done = 0;
+        for event in self.pending.iter() {
+            if wait {
+                event.wait_for()?;
+            } else if !event.is_complete()? {
+                break;
+            }
+            let start = event.profiling_info(ProfilingInfo::Start)?.time()?;
+            let end = event.profiling_info(ProfilingInfo::End)?.time()?;
+            self.stats.record(end.saturating_sub(start));
+            done += 1;
+        }
+        self.pending.drain(..done);
+        Ok(())
+    }
+}
+
+
+/// The values most recently bound to the arguments of a kernel with argument
+/// caching enabled.
+#[derive(Debug, Default)]
+struct ArgCache {
+    // The size and (unless null) value bytes bound to each argument index:
+    bound: HashMap<u32, (usize, Option<Vec<u8>>)>,
+    redundant: u64,
+}
+
+impl ArgCache {
+    /// Records `arg_val` as bound to `arg_idx`. Returns `false` if it is
+    /// identical to the value already bound there.
+    fn update(&mut self, arg_idx: u32, arg_val: &ArgVal) -> bool {
+        let (size, value) = arg_val.as_raw();
+        let bytes = if value.is_null() {
+            None
+        } else {
+            Some(unsafe { std::slice::from_raw_parts(value as *const u8, size) }.to_vec())
+        };
+        let entry = (size, bytes);
+
+        if self.bound.get(&arg_idx) == Some(&entry) {
+            self.redundant += 1;
+            false
+        } else {
+            self.bound.insert(arg_idx, entry);
+            true
+        }
+    }
+}
+
+
+/// Releases the buffers created by `Kernel::set_arg_slice` once the kernel
+/// has been enqueued, resetting each of their arguments to null.
+///
+/// The enqueued command itself retains the buffers until it completes. All
+/// buffers are released even if resetting an argument fails, in which case
+/// the first error is returned.
+fn release_transient_args(kernel: &KernelCore, transient_args: &RefCell<Vec<(u32, MemCore)>>,
+        arg_cache: Option<&RefCell<ArgCache>>) -> OclResult<()> {
+    let mut res = Ok(());
+    for (arg_idx, _mem) in transient_args.borrow_mut().drain(..) {
+        if let Some(cache) = arg_cache { cache.borrow_mut().bound.remove(&arg_idx); }
+        let arg_res = core::set_kernel_arg(kernel, arg_idx, ArgVal::mem_null());
+        if res.is_ok() { res = arg_res.map_err(OclError::from); }
+    }
+    res
+}
+
+
 /// Converts an argument index specifier to `u32`.
 #[derive(Clone, Debug)]
 pub enum ArgIdxSpecifier {
@@ -261,6 +591,12 @@ impl From<String> for ArgIdxSpecifier {
     }
 }
 
+impl<'a> From<&'a String> for ArgIdxSpecifier {
+    fn from(s: &'a String) -> ArgIdxSpecifier {
+        ArgIdxSpecifier::Str(s.clone().into())
+    }
+}
+
 
 /// Contains owned or shared argument values.
 #[derive(Debug, Clone)]
@@ -439,6 +775,52 @@ impl<'b, T> From<T> for ArgValConverter<'b, T> where T: OclPrm {
 
 
 
+/// A tuple of kernel arguments which can be set in a single call with
+/// `Kernel::set_args`.
+///
+/// Implemented for tuples of up to twelve elements, each of which may be
+/// anything accepted by `Kernel::set_arg` (a `Buffer` or `Image` reference,
+/// scalar, or vector). `T` is the tuple of the underlying element types of
+/// each argument and is always inferred.
+pub trait KernelArgs<'a, T> {
+    /// Sets each element of this tuple as the argument of `kernel` at the
+    /// index corresponding to its position within the tuple.
+    fn set_on(self, kernel: &Kernel) -> OclResult<()>;
+}
+
+macro_rules! impl_kernel_args {
+    ($( ($($idx:tt: $av:ident, $t:ident),+) )+) => { $(
+        impl<'a, $($av, $t),+> KernelArgs<'a, ($($t,)+)> for ($($av,)+)
+                where $($t: OclPrm, $av: Into<ArgValConverter<'a, $t>>),+ {
+            fn set_on(self, kernel: &Kernel) -> OclResult<()> {
+                $( kernel.set_arg::<$t, u32, _>($idx, self.$idx)?; )+
+                Ok(())
+            }
+        }
+    )+ }
+}
+
+impl_kernel_args! {
+    (0: A0, T0)
+    (0: A0, T0, 1: A1, T1)
+    (0: A0, T0, 1: A1, T1, 2: A2, T2)
+    (0: A0, T0, 1: A1, T1, 2: A2, T2, 3: A3, T3)
+    (0: A0, T0, 1: A1, T1, 2: A2, T2, 3: A3, T3, 4: A4, T4)
+    (0: A0, T0, 1: A1, T1, 2: A2, T2, 3: A3, T3, 4: A4, T4, 5: A5, T5)
+    (0: A0, T0, 1: A1, T1, 2: A2, T2, 3: A3, T3, 4: A4, T4, 5: A5, T5, 6: A6, T6)
+    (0: A0, T0, 1: A1, T1, 2: A2, T2, 3: A3, T3, 4: A4, T4, 5: A5, T5, 6: A6, T6,
+        7: A7, T7)
+    (0: A0, T0, 1: A1, T1, 2: A2, T2, 3: A3, T3, 4: A4, T4, 5: A5, T5, 6: A6, T6,
+        7: A7, T7, 8: A8, T8)
+    (0: A0, T0, 1: A1, T1, 2: A2, T2, 3: A3, T3, 4: A4, T4, 5: A5, T5, 6: A6, T6,
+        7: A7, T7, 8: A8, T8, 9: A9, T9)
+    (0: A0, T0, 1: A1, T1, 2: A2, T2, 3: A3, T3, 4: A4, T4, 5: A5, T5, 6: A6, T6,
+        7: A7, T7, 8: A8, T8, 9: A9, T9, 10: A10, T10)
+    (0: A0, T0, 1: A1, T1, 2: A2, T2, 3: A3, T3, 4: A4, T4, 5: A5, T5, 6: A6, T6,
+        7: A7, T7, 8: A8, T8, 9: A9, T9, 10: A10, T10, 11: A11, T11)
+}
+
+
 /// A map of argument names -> indexes.
 #[derive(Clone, Debug)]
 struct NamedArgs(Option<HashMap<Cow<'static, str>, u32>>);
@@ -500,7 +882,7 @@ impl MemArgs {
 /// `Kernel` includes features that a raw OpenCL kernel does not, including:
 ///
 /// 1. Type-checked arguments (not just size-checked)
-/// 2. Named arguments (with a `&'static str` name)
+/// 2. Named arguments (with a `&'static str` or `String` name)
 /// 3. Prevention of a potential (difficult to debug) segfault if a buffer or
 ///    image used by a kernel is dropped prematurely.
 /// 4. Stored defaults for the:
@@ -516,6 +898,16 @@ impl MemArgs {
 /// argument and enqueue a kernel at the same time. Use the `KernelBuilder` to
 /// create multiple identical kernels (`KernelBuilder` is clonable and
 /// re-usable).
+///
+/// ### Thread Safety
+///
+/// `clSetKernelArg` mutates the state of the underlying kernel object and is
+/// not safe to call concurrently (with itself or with an enqueue) on the
+/// same kernel. For this reason `Kernel` is `Send` but **not** `Sync`: a
+/// `&Kernel` cannot be shared between threads. To use a kernel from several
+/// threads either build one `Kernel` per thread (recommended, building from
+/// an existing program is cheap) or wrap it in a `Mutex` and hold the lock
+/// while setting arguments **and** enqueuing.
 #[derive(Debug)]
 pub struct Kernel {
     obj_core: KernelCore,
@@ -526,6 +918,11 @@ pub struct Kernel {
     gws: SpatialDims,
     lws: SpatialDims,
     arg_types: Option<Vec<ArgType>>,
+    timing: Option<RefCell<KernelTiming>>,
+    arg_cache: Option<RefCell<ArgCache>>,
+    pending_waits: RefCell<EventList>,
+    last_event: Option<RefCell<Event>>,
+    transient_args: RefCell<Vec<(u32, MemCore)>>,
 }
 
 impl Kernel {
@@ -561,10 +958,36 @@ impl Kernel {
     }
 
     /// Returns the argument index of a named argument if it exists.
-    pub fn named_arg_idx(&self, name: &'static str) -> Option<u32> {
+    pub fn named_arg_idx(&self, name: &str) -> Option<u32> {
         self.named_args.resolve_idx(name).ok()
     }
 
+    /// Clears all argument names so that arguments can be re-bound from
+    /// scratch (e.g. when the argument layout changes between phases of an
+    /// application).
+    ///
+    /// Only host-side bookkeeping is reset. The underlying kernel object and
+    /// the argument values already set on it are unaffected. Buffers and
+    /// images previously set as arguments continue to be kept alive until
+    /// their argument index is set to something else or the kernel is
+    /// dropped.
+    ///
+    /// Use `::name_arg` to assign new names.
+    pub fn reset_args(&mut self) {
+        self.named_args = NamedArgs(None);
+    }
+
+    /// Assigns a name to the argument at index `idx`, replacing any existing
+    /// assignment for that name.
+    pub fn name_arg<S>(&mut self, name: S, idx: u32) -> OclResult<()>
+            where S: Into<Cow<'static, str>> {
+        if idx >= self.num_args()? {
+            return Err(KernelError::ArgIdxOor(self.name()?, idx).into());
+        }
+        self.named_args.insert(name.into(), idx);
+        Ok(())
+    }
+
     /// Sets an argument by index without checks of any kind.
     ///
     /// Setting buffer or image (`cl_mem`) arguments this way may cause
@@ -574,13 +997,32 @@ impl Kernel {
     /// This also bypasses the check to determine if the type of the value you
     /// pass here matches the type defined in your kernel.
     pub unsafe fn set_arg_unchecked(&self, arg_idx: u32, arg_val: ArgVal) -> OclResult<()> {
-        core::set_kernel_arg(&self.obj_core, arg_idx, arg_val).map_err(OclError::from)
+        self.set_kernel_arg(arg_idx, arg_val)
     }
 
     /// Sets an argument by index.
     fn _set_arg<T: OclPrm>(&self, arg_idx: u32, arg_val: ArgVal) -> OclResult<()> {
         self.verify_arg_type::<T>(arg_idx)?;
-        core::set_kernel_arg(&self.obj_core, arg_idx, arg_val).map_err(OclError::from)
+        self.set_kernel_arg(arg_idx, arg_val)
+    }
+
+    /// Calls `clSetKernelArg` unless argument caching is enabled and
+    /// `arg_val` is identical to the value already bound to `arg_idx`.
+    fn set_kernel_arg(&self, arg_idx: u32, arg_val: ArgVal) -> OclResult<()> {
+        if let Some(ref cache) = self.arg_cache {
+            if !cache.borrow_mut().update(arg_idx, &arg_val) { return Ok(()); }
+        }
+
+        core::set_kernel_arg(&self.obj_core, arg_idx, arg_val).map_err(|err| {
+            // The bound value is unknown after a failure:
+            if let Some(ref cache) = self.arg_cache { cache.borrow_mut().bound.remove(&arg_idx); }
+            OclError::from(err)
+        })?;
+
+        // A buffer created by `::set_arg_slice` is no longer needed once its
+        // argument has been replaced:
+        self.transient_args.borrow_mut().retain(|&(idx, _)| idx != arg_idx);
+        Ok(())
     }
 
     /// Sets a `Buffer`, `Image`, scalar, or vector argument by index or by
@@ -624,9 +1066,152 @@ impl Kernel {
         self._set_arg::<T>(arg_idx, val)
     }
 
+    /// Sets every argument of this kernel, in order starting from index `0`,
+    /// from the elements of a tuple.
+    ///
+    /// Each element may be anything accepted by `::set_arg`. Because
+    /// arguments are always assigned from index `0`, calling this repeatedly
+    /// (e.g. once per frame) with different values simply rebinds them.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// kern.set_args((&input_buffer, &output_buffer, width, height))?;
+    /// ```
+    pub fn set_args<'a, T, A>(&self, args: A) -> OclResult<()> where A: KernelArgs<'a, T> {
+        args.set_on(self)
+    }
+
+    /// Sets the size of a `__local` argument, by index or by name, to
+    /// `length` elements of type `T`.
+    ///
+    /// The size may be changed between enqueues without rebuilding the
+    /// kernel (e.g. when tuning the local work size at runtime).
+    pub fn set_arg_local<T, Ai>(&self, idx: Ai, length: usize) -> OclResult<()>
+            where T: OclPrm, Ai: Into<ArgIdxSpecifier> {
+        let arg_idx = idx.into().to_idx(&self.named_args)?;
+        self.mem_args.remove(&arg_idx);
+        self._set_arg::<T>(arg_idx, ArgVal::local::<T>(&length))
+    }
+
+    /// Sets a buffer argument, by index or by name, to a copy of `data`
+    /// without requiring a `Buffer` to be created and kept alive.
+    ///
+    /// A short-lived, read-only buffer is created in the kernel's context
+    /// and initialized from `data`. It is owned by the kernel until the next
+    /// successful enqueue, after which it is released and the argument is
+    /// reset to null. The argument must therefore be set again before each
+    /// enqueue. Setting the argument to anything else before enqueuing
+    /// releases the buffer immediately.
+    ///
+    /// Intended for small, frequently changing, uniform data (e.g. a
+    /// transform matrix). Each call allocates a new device buffer.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// kern.set_arg_slice("transform", &matrix[..])?;
+    /// unsafe { kern.enq()?; }
+    /// ```
+    pub fn set_arg_slice<T, Ai>(&self, idx: Ai, data: &[T]) -> OclResult<()>
+            where T: OclPrm, Ai: Into<ArgIdxSpecifier> {
+        let arg_idx = idx.into().to_idx(&self.named_args)?;
+        self.verify_arg_type::<T>(arg_idx)?;
+
+        let context = match self.info(KernelInfo::Context)? {
+            KernelInfoResult::Context(context) => context,
+            _ => unreachable!(),
+        };
+        let mem = unsafe {
+            core::create_buffer(&context, MemFlags::new().read_only().copy_host_ptr(),
+                data.len(), Some(data))?
+        };
+
+        self.mem_args.remove(&arg_idx);
+        self.set_kernel_arg(arg_idx, ArgVal::mem(&mem))?;
+        self.transient_args.borrow_mut().push((arg_idx, mem));
+        Ok(())
+    }
+
+    /// Sets each named scalar argument in `values` to its value, converted
+    /// to the type declared in the kernel source.
+    ///
+    /// Useful when argument values come from runtime configuration (e.g. a
+    /// parameter file). Integer arguments only accept whole numbers within
+    /// the range of their type.
+    ///
+    /// Every entry is attempted. If any name is unknown, is not a scalar
+    /// numeric argument, or has a value which does not fit, a single
+    /// `KernelError::NamedScalars` listing all of them is returned. Requires
+    /// argument type information (see `KernelError::ArgInfoUnavailable`).
+    pub fn set_named_scalars(&self, values: &HashMap<String, f64>) -> OclResult<()> {
+        let arg_types = match self.arg_types {
+            Some(ref arg_types) => arg_types,
+            None => return Err(KernelError::ArgInfoUnavailable.into()),
+        };
+
+        let mut names: Vec<&String> = values.keys().collect();
+        names.sort();
+
+        let mut errors = Vec::new();
+        for name in names {
+            let arg_idx = match self.named_args.resolve_idx(name) {
+                Ok(idx) => idx,
+                Err(_) => {
+                    errors.push(format!("'{}': no argument with this name exists", name));
+                    continue;
+                },
+            };
+            if let Err(err) = self.set_scalar_from_f64(arg_idx, &arg_types[arg_idx as usize],
+                    values[name]) {
+                errors.push(format!("'{}': {}", name, err));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(KernelError::NamedScalars { errors }.into())
+        }
+    }
+
+    /// Sets the scalar argument at `arg_idx`, of type `arg_type`, to `value`
+    /// converted to that type.
+    fn set_scalar_from_f64(&self, arg_idx: u32, arg_type: &ArgType, value: f64)
+            -> Result<(), String> {
+        if arg_type.is_ptr() || arg_type.cardinality() != Cardinality::One {
+            return Err(format!("the argument ({:?}) is not a scalar", arg_type));
+        }
+
+        macro_rules! set_int {
+            ($ty:ty) => {{
+                if value.fract() != 0.0 || value < <$ty>::MIN as f64 ||
+                        value > <$ty>::MAX as f64 {
+                    return Err(format!("{} is not a valid '{}'", value, stringify!($ty)));
+                }
+                let val = value as $ty;
+                self.set_kernel_arg(arg_idx, ArgVal::scalar(&val))
+            }};
+        }
+
+        self.mem_args.remove(&arg_idx);
+        let result = match arg_type.base_type() {
+            BaseType::Char => set_int!(i8),
+            BaseType::Uchar => set_int!(u8),
+            BaseType::Short => set_int!(i16),
+            BaseType::Ushort => set_int!(u16),
+            BaseType::Int => set_int!(i32),
+            BaseType::Uint => set_int!(u32),
+            BaseType::Long => set_int!(i64),
+            BaseType::Ulong => set_int!(u64),
+            BaseType::Float => self.set_kernel_arg(arg_idx, ArgVal::scalar(&(value as f32))),
+            BaseType::Double => self.set_kernel_arg(arg_idx, ArgVal::scalar(&value)),
+            _ => return Err(format!("the argument ({:?}) is not numeric", arg_type)),
+        };
+        result.map_err(|err| err.to_string())
+    }
+
     /// Modifies the kernel argument named: `name`.
     #[deprecated(since = "0.18.0", note = "Use `::set_arg` instead.")]
-    pub fn set_arg_buf_named<'a, T, M>(&'a self, name: &'static str,
+    pub fn set_arg_buf_named<'a, T, M>(&'a self, name: &str,
             buffer_opt: Option<M>) -> OclResult<()>
             where T: OclPrm, M: AsMem<T> + MemCmdAll {
         let arg_idx = self.named_args.resolve_idx(name)?;
@@ -644,7 +1229,7 @@ impl Kernel {
 
     /// Modifies the kernel argument named: `name`.
     #[deprecated(since = "0.18.0", note = "Use `::set_arg` instead.")]
-    pub fn set_arg_img_named<'a, T, M>(&'a self, name: &'static str,
+    pub fn set_arg_img_named<'a, T, M>(&'a self, name: &str,
             image_opt: Option<M>) -> OclResult<()>
             where T: OclPrm, M: AsMem<T> + MemCmdAll {
         let arg_idx = self.named_args.resolve_idx(name)?;
@@ -662,7 +1247,7 @@ impl Kernel {
 
     /// Sets the value of a named sampler argument.
     #[deprecated(since = "0.18.0", note = "Use `::set_arg_sampler_named` instead.")]
-    pub fn set_arg_smp_named<'a>(&'a self, name: &'static str,
+    pub fn set_arg_smp_named<'a>(&'a self, name: &str,
             sampler_opt: Option<&Sampler>) -> OclResult<()> {
         let arg_idx = self.named_args.resolve_idx(name)?;
         match sampler_opt {
@@ -673,7 +1258,7 @@ impl Kernel {
 
     /// Modifies the kernel argument named: `name`.
     #[deprecated(since = "0.18.0", note = "Use `::set_arg` instead.")]
-    pub fn set_arg_scl_named<'a, T, B>(&'a self, name: &'static str, scalar: B)
+    pub fn set_arg_scl_named<'a, T, B>(&'a self, name: &str, scalar: B)
             -> OclResult<()>
             where T: OclPrm, B: Borrow<T> {
         let arg_idx = self.named_args.resolve_idx(name)?;
@@ -682,13 +1267,22 @@ impl Kernel {
 
     /// Modifies the kernel argument named: `name`.
     #[deprecated(since = "0.18.0", note = "Use `::set_arg` instead.")]
-    pub fn set_arg_vec_named<'a, T, B>(&'a self, name: &'static str, vector: B)
+    pub fn set_arg_vec_named<'a, T, B>(&'a self, name: &str, vector: B)
             -> OclResult<()>
             where T: OclPrm, B: Borrow<T> {
         let arg_idx = self.named_args.resolve_idx(name)?;
         self._set_arg::<T>(arg_idx, ArgVal::vector(vector.borrow()))
     }
 
+    /// Sets a sampler argument by index or by name.
+    ///
+    /// Use this for kernels which read images using an explicitly passed
+    /// `sampler_t` (e.g. `read_imagef(img, sampler, coord)`).
+    pub fn set_arg_sampler<Ai>(&self, idx: Ai, sampler: &Sampler) -> OclResult<()>
+            where Ai: Into<ArgIdxSpecifier> {
+        self.set_arg_sampler_named(idx, Some(sampler))
+    }
+
     /// Sets the value of a named sampler argument.
     pub fn set_arg_sampler_named<'a, Ai>(&'a self, idx: Ai,
             sampler_opt: Option<&Sampler>) -> OclResult<()>
@@ -701,6 +1295,54 @@ impl Kernel {
         }
     }
 
+    /// Sets a device queue (`queue_t`) argument by index or by name.
+    ///
+    /// `queue` must be an on-device queue (see `Queue::new_on_device`).
+    pub fn set_arg_queue<Ai>(&self, idx: Ai, queue: &Queue) -> OclResult<()>
+            where Ai: Into<ArgIdxSpecifier> {
+        let arg_idx = idx.into().to_idx(&self.named_args)?;
+        self._set_arg::<u64>(arg_idx, ArgVal::queue(queue.as_core()))
+    }
+
+    /// Sets a buffer argument by index or by name and records `producer`,
+    /// the event of the command which writes to `buffer`, as a dependency of
+    /// the next enqueue of this kernel.
+    ///
+    /// Recorded events accumulate across calls and are waited on (along with
+    /// any passed to `KernelCmd::ewait`) by the next successful enqueue, after
+    /// which they are cleared. This automates dependency tracking for simple
+    /// linear pipelines, particularly on out-of-order queues:
+    ///
+    /// ```rust,ignore
+    /// let mut produced = Event::empty();
+    /// kernel_a.cmd().enew(&mut produced).enq()?;
+    /// kernel_b.set_arg_after("input", &buffer, &produced)?;
+    /// // Waits for `kernel_a` to complete:
+    /// kernel_b.enq()?;
+    /// ```
+    pub fn set_arg_after<T, Ai>(&self, idx: Ai, buffer: &Buffer<T>, producer: &Event)
+            -> OclResult<()>
+            where T: OclPrm, Ai: Into<ArgIdxSpecifier> {
+        self.set_arg(idx, buffer)?;
+        self.pending_waits.borrow_mut().push(producer.clone());
+        Ok(())
+    }
+
+    /// Declares the SVM pointers which this kernel may access indirectly,
+    /// that is, other than through its arguments (for example by following
+    /// pointers stored within an SVM allocation).
+    ///
+    /// The implementation may not make memory referenced only this way
+    /// available to the kernel unless it is declared. Each call replaces the
+    /// previously declared set of pointers (`CL_KERNEL_EXEC_INFO_SVM_PTRS`).
+    ///
+    /// Returns an error if any device associated with the kernel's program
+    /// does not support OpenCL 2.0.
+    #[cfg(feature = "opencl_version_2_0")]
+    pub fn set_exec_info_svm_ptrs(&self, ptrs: &[*const c_void]) -> OclResult<()> {
+        core::set_kernel_exec_info_svm_ptrs(&self.obj_core, ptrs, None).map_err(OclError::from)
+    }
+
     /// Returns a command builder which is used to chain parameters of an
     /// 'enqueue' command together.
     pub fn cmd(&self) -> KernelCmd {
@@ -711,7 +1353,12 @@ impl Kernel {
             gws: self.gws,
             lws: self.lws,
             wait_events: None,
-            new_event: None
+            new_event: None,
+            timing: self.timing.as_ref(),
+            pending_waits: &self.pending_waits,
+            last_event: self.last_event.as_ref(),
+            arg_cache: self.arg_cache.as_ref(),
+            transient_args: &self.transient_args,
         }
     }
 
@@ -729,6 +1376,210 @@ impl Kernel {
         self.cmd().enq()
     }
 
+    /// Enqueues this kernel on the default queue using the shape of `buffer`
+    /// (see `Buffer::with_shape`) as the global work size.
+    ///
+    /// A buffer with a two-dimensional shape results in a two-dimensional
+    /// work size, etc. Buffers without shape metadata result in a
+    /// one-dimensional work size equal to their length.
+    ///
+    /// Shorthand for `.cmd().global_work_size(buffer.work_dims()).enq()`
+    ///
+    /// # Safety
+    ///
+    /// All kernel code must be considered untrusted. Therefore the act of
+    /// calling this function contains implied unsafety even though the API
+    /// itself is safe.
+    pub unsafe fn enq_over<T: OclPrm>(&self, buffer: &Buffer<T>) -> OclResult<()> {
+        self.cmd().global_work_size(buffer.work_dims()).enq()
+    }
+
+    /// Enqueues this kernel on the default queue once for each consecutive
+    /// `batch` elements of `input` and returns the events of the enqueued
+    /// commands.
+    ///
+    /// For each batch a sub-buffer covering it is set as the argument at
+    /// `arg_idx` and the kernel is enqueued with a one-dimensional global
+    /// work size equal to the length of the batch (the last batch may be
+    /// shorter). Other arguments are left as they are. After returning, the
+    /// argument at `arg_idx` remains set to the sub-buffer of the last
+    /// batch.
+    ///
+    /// Because each batch starts at a sub-buffer origin, `batch` elements
+    /// must span a multiple of the base address alignment of the device
+    /// (see `Device::mem_base_addr_align`), otherwise a
+    /// `KernelError::BatchSize` error is returned.
+    ///
+    /// # Safety
+    ///
+    /// All kernel code must be considered untrusted. Therefore the act of
+    /// calling this function contains implied unsafety even though the API
+    /// itself is safe.
+    pub unsafe fn enqueue_batched<T, Ai>(&self, arg_idx: Ai, input: &Buffer<T>, batch: usize)
+            -> OclResult<EventList>
+            where T: OclPrm, Ai: Into<ArgIdxSpecifier> {
+        let queue = self.queue.as_ref().ok_or(KernelError::CmdNoQueue)?;
+        let align_bytes = queue.device().mem_base_addr_align()? as usize / 8;
+        let batch_bytes = batch * std::mem::size_of::<T>();
+        if batch == 0 || !batch_bytes.is_multiple_of(align_bytes) {
+            return Err(KernelError::BatchSize { batch, batch_bytes, align_bytes }.into());
+        }
+
+        let arg_idx = arg_idx.into();
+        let flags = input.flags()? & (MemFlags::READ_WRITE | MemFlags::WRITE_ONLY |
+            MemFlags::READ_ONLY);
+        let mut events = EventList::new();
+
+        for offset in (0..input.len()).step_by(batch) {
+            let len = batch.min(input.len() - offset);
+            let sub_buffer = input.create_sub_buffer(Some(flags), offset, len)?;
+            self.set_arg(arg_idx.clone(), &sub_buffer)?;
+            self.cmd().global_work_size(len).enew(&mut events).enq()?;
+        }
+
+        Ok(events)
+    }
+
+    /// Enqueues this kernel on the default queue repeatedly, reading the
+    /// first element of `flag` after each enqueue and passing it, along with
+    /// the zero-based iteration index, to `predicate`. Stops once `predicate`
+    /// returns `false` or after `max_iters` iterations and returns the number
+    /// of times the kernel was enqueued.
+    ///
+    /// This is intended for iterative algorithms where the kernel writes a
+    /// convergence flag (or residual) to `flag` each pass. Only a single
+    /// element is read back per iteration. The read is enqueued on the same
+    /// (default) queue as the kernel, ordering it after the kernel without
+    /// blocking on the kernel itself, and blocks until the value is
+    /// available since it decides whether another pass is enqueued.
+    ///
+    /// # Safety
+    ///
+    /// All kernel code must be considered untrusted. Therefore the act of
+    /// calling this function contains implied unsafety even though the API
+    /// itself is safe.
+    pub unsafe fn enqueue_until<T, F>(&self, flag: &Buffer<T>, max_iters: usize,
+            mut predicate: F) -> OclResult<usize>
+            where T: OclPrm, F: FnMut(usize, T) -> bool {
+        let queue = self.queue.as_ref().ok_or(KernelError::CmdNoQueue)?;
+        let mut value = [T::default()];
+
+        for iter in 0..max_iters {
+            self.cmd().queue(queue).enq()?;
+            flag.read(&mut value[..]).queue(queue).len(1).enq()?;
+
+            if !predicate(iter, value[0]) {
+                return Ok(iter + 1);
+            }
+        }
+
+        Ok(max_iters)
+    }
+
+    /// Enqueues this kernel on the default queue with a `dims`-dimensional
+    /// global work size of `gws` (and local work size of `lws`, if given),
+    /// regardless of the dimensionality of the default work sizes.
+    ///
+    /// The default global work offset is not used. Returns
+    /// `KernelError::WorkDims` unless `dims` is 1, 2, or 3 and both `gws` and
+    /// `lws` contain exactly `dims` sizes.
+    ///
+    /// # Safety
+    ///
+    /// All kernel code must be considered untrusted. Therefore the act of
+    /// calling this function contains implied unsafety even though the API
+    /// itself is safe.
+    pub unsafe fn enqueue_dims(&self, dims: u32, gws: &[usize], lws: Option<&[usize]>)
+            -> OclResult<()> {
+        let (gws, lws) = work_dims_from_slices(dims, gws, lws)?;
+        self.cmd()
+            .global_work_offset(SpatialDims::Unspecified)
+            .global_work_size(gws)
+            .local_work_size(lws)
+            .enq()
+    }
+
+    /// Enqueues this kernel on the default queue with a one-dimensional
+    /// global work size of `total`, treating the data as flat.
+    ///
+    /// Useful for launching a kernel built with multi-dimensional default
+    /// work sizes over all of its elements at once. The default global work
+    /// offset and local work size are not used (the implementation chooses
+    /// the local work size).
+    ///
+    /// # Safety
+    ///
+    /// All kernel code must be considered untrusted. Therefore the act of
+    /// calling this function contains implied unsafety even though the API
+    /// itself is safe.
+    pub unsafe fn enqueue_flat(&self, total: usize) -> OclResult<()> {
+        self.enqueue_dims(1, &[total], None)
+    }
+
+    /// Enqueues this kernel on the default queue, waits for the queue to
+    /// finish, then reads the entire contents of `output` into a new `Vec`.
+    ///
+    /// This is a convenience for experiments and examples. It performs a
+    /// **full synchronization** (`clFinish`) of the default queue followed by
+    /// a blocking read and should not be used where commands are meant to be
+    /// pipelined. Use `::enq` (or `::cmd`) and `Buffer::read` directly there.
+    ///
+    /// # Safety
+    ///
+    /// All kernel code must be considered untrusted. Therefore the act of
+    /// calling this function contains implied unsafety even though the API
+    /// itself is safe.
+    pub unsafe fn run_and_read<T: OclPrm>(&self, output: &Buffer<T>) -> OclResult<Vec<T>> {
+        let queue = self.queue.as_ref().ok_or(KernelError::CmdNoQueue)?;
+        self.cmd().queue(queue).enq()?;
+        queue.finish()?;
+
+        let mut vec = vec![T::default(); output.len()];
+        output.read(&mut vec).queue(queue).enq()?;
+        Ok(vec)
+    }
+
+    /// Forces the driver to finish compiling this kernel by enqueuing a
+    /// single work-item dispatch on the default queue then waiting for the
+    /// queue to finish.
+    ///
+    /// Some drivers defer compilation until a kernel is first enqueued,
+    /// making that enqueue much slower than subsequent ones. Calling this
+    /// during startup moves that one-time cost out of latency sensitive code.
+    ///
+    /// The dispatch has the same dimensionality as the default global work
+    /// size (one dimension if unspecified) but contains only the work-item
+    /// at the origin, no global work offset, and an implementation-chosen
+    /// local work size. The dispatch waits on any events recorded by
+    /// `::set_arg_after`, which remain recorded for the next enqueue. Timing
+    /// and completion tracking are unaffected.
+    ///
+    /// # Safety
+    ///
+    /// The kernel is actually executed for that work-item, using whichever
+    /// arguments are currently set, so every argument must be set (an error
+    /// is returned otherwise) and any side effects of running the first
+    /// work-item (e.g. writes to a buffer) must be acceptable. Kernels
+    /// requiring a specific work-group size (`reqd_work_group_size`) will
+    /// return an error. All kernel code must also be considered untrusted.
+    pub unsafe fn prewarm(&self) -> OclResult<()> {
+        let queue = self.queue.as_ref().ok_or(KernelError::CmdNoQueue)?;
+        let dim_count = std::cmp::max(self.gws.dim_count(), 1);
+
+        // Wait on (but do not clear) events recorded by `::set_arg_after` so
+        // that the next regular enqueue still waits on them:
+        let pending_waits = self.pending_waits.borrow();
+        let wait_events = if pending_waits.is_empty() {
+            None
+        } else {
+            Some(ClWaitListPtrEnum::from(&*pending_waits))
+        };
+
+        core::enqueue_kernel(queue, &self.obj_core, dim_count, None, &[1, 1, 1], None,
+            wait_events, None::<ClNullEventPtrEnum>)?;
+        queue.finish()
+    }
+
     /// Changes the default queue.
     ///
     /// Returns a ref for chaining i.e.:
@@ -791,6 +1642,98 @@ impl Kernel {
         self.queue.as_ref()
     }
 
+    /// Enables the accumulation of device execution times for every
+    /// subsequent enqueue of this kernel.
+    ///
+    /// Each enqueue then creates an event (cloned into any destination
+    /// passed to `KernelCmd::enew`) whose `Start` and `End` profiling
+    /// counters are folded into the stats returned by `::timing_stats` once
+    /// the command completes. Calling this again has no effect.
+    ///
+    /// Every queue this kernel is enqueued on must have been created with
+    /// `CommandQueueProperties::PROFILING_ENABLE`, otherwise enqueuing or
+    /// reading the stats will return an error.
+    pub fn enable_timing(&mut self) -> &mut Kernel {
+        if self.timing.is_none() {
+            self.timing = Some(RefCell::new(KernelTiming::default()));
+        }
+        self
+    }
+
+    /// Enables argument caching.
+    ///
+    /// Each subsequent call to one of the `set_arg*` methods then skips the
+    /// call to `clSetKernelArg` when the size and value passed are identical
+    /// to those already bound to that argument (e.g. the same buffer being
+    /// set again every iteration of a loop). Calling this again has no
+    /// effect.
+    ///
+    /// Arguments set through the underlying `core::Kernel` directly are not
+    /// tracked and must not be mixed with caching.
+    pub fn enable_arg_caching(&mut self) -> &mut Kernel {
+        if self.arg_cache.is_none() {
+            self.arg_cache = Some(RefCell::new(ArgCache::default()));
+        }
+        self
+    }
+
+    /// Enables completion tracking.
+    ///
+    /// Each subsequent enqueue of this kernel then creates an event (cloned
+    /// into any destination passed to `KernelCmd::enew`) which is retained
+    /// until the next enqueue, allowing `::is_idle` to report whether the
+    /// most recent dispatch has completed. Calling this again has no effect.
+    pub fn enable_completion_tracking(&mut self) -> &mut Kernel {
+        if self.last_event.is_none() {
+            self.last_event = Some(RefCell::new(Event::empty()));
+        }
+        self
+    }
+
+    /// Returns `true` if the most recent enqueue of this kernel has completed
+    /// or if it has not been enqueued since `::enable_completion_tracking`
+    /// was called.
+    ///
+    /// Does not block. Returns an error if completion tracking is not
+    /// enabled.
+    pub fn is_idle(&self) -> OclResult<bool> {
+        match self.last_event {
+            Some(ref last_event) => {
+                let last_event = last_event.borrow();
+                if last_event.is_empty() {
+                    Ok(true)
+                } else {
+                    last_event.is_complete().map_err(OclError::from)
+                }
+            },
+            None => Err("Kernel::is_idle: Completion tracking is not enabled. Call \
+                'Kernel::enable_completion_tracking' first.".into()),
+        }
+    }
+
+    /// Returns the number of `set_arg*` calls skipped since
+    /// `::enable_arg_caching` was called because the value was already
+    /// bound, or `None` if caching is not enabled.
+    pub fn redundant_arg_binds(&self) -> Option<u64> {
+        self.arg_cache.as_ref().map(|cache| cache.borrow().redundant)
+    }
+
+    /// Returns the execution times accumulated since `::enable_timing` was
+    /// called or `None` if timing is not enabled.
+    ///
+    /// Blocks until all previously enqueued commands of this kernel have
+    /// completed.
+    pub fn timing_stats(&self) -> OclResult<Option<KernelTimingStats>> {
+        match self.timing {
+            Some(ref timing) => {
+                let mut timing = timing.borrow_mut();
+                timing.collect(true)?;
+                Ok(Some(timing.stats))
+            },
+            None => Ok(None),
+        }
+    }
+
     /// Returns the default global work offset.
     pub fn default_global_work_offset(&self) -> SpatialDims {
         self.gwo
@@ -806,6 +1749,67 @@ impl Kernel {
         self.lws
     }
 
+    /// Returns the number of work-groups, per dimension (x, y, z), which
+    /// enqueuing this kernel with its default global and local work sizes
+    /// will launch.
+    ///
+    /// Unused dimensions have a count of `1`. A global work size which is
+    /// not a multiple of the local work size is rounded up. Returns `None` if
+    /// either default work size is unspecified.
+    pub fn work_group_count(&self) -> Option<(usize, usize, usize)> {
+        work_group_count(self.gws, self.lws)
+    }
+
+    /// Returns a local work size suitable for enqueuing this kernel on
+    /// `device` using its default global work size, chosen using
+    /// `LwsStrategy::Max`.
+    ///
+    /// See `::auto_lws_with`.
+    pub fn auto_lws(&self, device: Device) -> OclResult<SpatialDims> {
+        self.auto_lws_with(device, LwsStrategy::Max)
+    }
+
+    /// Returns a local work size suitable for enqueuing this kernel on
+    /// `device` using its default global work size, chosen according to
+    /// `strategy`.
+    ///
+    /// The result has the same number of dimensions as the default global
+    /// work size, evenly divides it, and respects both the maximum
+    /// work-group size of this kernel on `device`
+    /// (`CL_KERNEL_WORK_GROUP_SIZE`) and the maximum work-item sizes of
+    /// `device`. Pass it to `::set_default_local_work_size` or
+    /// `KernelCmd::local_work_size` to use it.
+    ///
+    /// Returns `KernelError::CmdNoGws` if no default global work size has
+    /// been set.
+    pub fn auto_lws_with(&self, device: Device, strategy: LwsStrategy) -> OclResult<SpatialDims> {
+        let gws = match self.gws.to_lens() {
+            Ok(lens) => lens,
+            Err(_) => return Err(KernelError::CmdNoGws.into()),
+        };
+
+        let max_wg_size = match self.wg_info(device, KernelWorkGroupInfo::WorkGroupSize)? {
+            KernelWorkGroupInfoResult::WorkGroupSize(s) => s,
+            _ => unreachable!(),
+        };
+        let preferred_multiple = match self.wg_info(device,
+                KernelWorkGroupInfo::PreferredWorkGroupSizeMultiple)? {
+            KernelWorkGroupInfoResult::PreferredWorkGroupSizeMultiple(m) => m,
+            _ => unreachable!(),
+        };
+        let max_wi_sizes = device.max_work_item_sizes()?;
+
+        let dim_count = self.gws.dim_count() as usize;
+        let lws = choose_lws(gws, dim_count, max_wg_size, &max_wi_sizes, preferred_multiple,
+            strategy);
+
+        Ok(match dim_count {
+            1 => SpatialDims::One(lws[0]),
+            2 => SpatialDims::Two(lws[0], lws[1]),
+            _ => SpatialDims::Three(lws[0], lws[1], lws[2]),
+        })
+    }
+
     /// Returns a reference to the core pointer wrapper, usable by functions in
     /// the `core` module.
     #[inline]
@@ -813,6 +1817,79 @@ impl Kernel {
         self
     }
 
+    /// Releases this kernel immediately, returning any error reported by
+    /// `clReleaseKernel` instead of panicking as dropping it would.
+    ///
+    /// Because this consumes the kernel, it can not be enqueued or have its
+    /// arguments set afterwards (this is enforced at compile time rather
+    /// than by checking a 'released' state). Buffers and images held as
+    /// arguments are dropped along with it. If other references to the
+    /// underlying kernel object exist (e.g. clones of `::as_core`) its
+    /// reference count is decremented but it is not destroyed until they
+    /// are dropped.
+    pub fn release(self) -> OclResult<()> {
+        let obj_core = self.obj_core;
+        let result = unsafe { core::release_kernel(&obj_core) };
+        std::mem::forget(obj_core);
+        result.map_err(OclError::from)
+    }
+
+    /// Returns a `Kernel` wrapping a raw `cl_kernel` created elsewhere.
+    ///
+    /// The returned kernel has no default queue or work sizes, no named
+    /// arguments, and does not check argument types.
+    ///
+    /// Every `Kernel` owns exactly one reference to the underlying kernel
+    /// object and releases it when dropped.
+    ///
+    /// ## Ownership
+    ///
+    /// * `retain: true`: The reference count is incremented before wrapping.
+    ///   The caller keeps its own reference and remains responsible for
+    ///   releasing it.
+    /// * `retain: false`: The caller's reference is transferred to the
+    ///   returned `Kernel`. The caller must **not** release `ptr` afterwards.
+    ///
+    /// ## Safety
+    ///
+    /// `ptr` must be a valid `cl_kernel`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `ptr` is null.
+    pub unsafe fn from_raw(ptr: cl_kernel, retain: bool) -> Kernel {
+        let obj_core = if retain {
+            KernelCore::from_raw_copied_ptr(ptr)
+        } else {
+            KernelCore::from_raw_create_ptr(ptr)
+        };
+
+        Kernel {
+            obj_core,
+            named_args: NamedArgs(None),
+            mem_args: MemArgs(Some(RefCell::new(BTreeMap::new()))),
+            queue: None,
+            gwo: SpatialDims::Unspecified,
+            gws: SpatialDims::Unspecified,
+            lws: SpatialDims::Unspecified,
+            arg_types: None,
+            timing: None,
+            arg_cache: None,
+            pending_waits: RefCell::new(EventList::new()),
+            last_event: None,
+            transient_args: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Returns the raw `cl_kernel` pointer.
+    ///
+    /// The pointer is owned by this `Kernel` and is only valid for as long as
+    /// it exists. Do not release it.
+    #[inline]
+    pub fn as_ptr(&self) -> cl_kernel {
+        self.obj_core.as_ptr()
+    }
+
     /// Returns information about this kernel.
     pub fn info(&self, info_kind: KernelInfo) -> OclResult<KernelInfoResult> {
         core::get_kernel_info(&self.obj_core, info_kind).map_err(OclError::from)
@@ -824,6 +1901,79 @@ impl Kernel {
         core::get_kernel_work_group_info(&self.obj_core, device, info_kind).map_err(OclError::from)
     }
 
+    /// Returns the amount of local memory, in bytes, used by this kernel on
+    /// `device` (`CL_KERNEL_LOCAL_MEM_SIZE`).
+    ///
+    /// This includes both statically allocated `__local` variables and any
+    /// local memory arguments which have been set.
+    pub fn local_mem_size(&self, device: Device) -> OclResult<usize> {
+        match self.wg_info(device, KernelWorkGroupInfo::LocalMemSize)? {
+            KernelWorkGroupInfoResult::LocalMemSize(s) => Ok(s as usize),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the minimum amount of private memory, in bytes, used by each
+    /// work-item of this kernel on `device` (`CL_KERNEL_PRIVATE_MEM_SIZE`).
+    ///
+    /// Together with `::local_mem_size` this indicates how many work-groups
+    /// can be resident on a compute unit at once.
+    pub fn private_mem_size(&self, device: Device) -> OclResult<usize> {
+        match self.wg_info(device, KernelWorkGroupInfo::PrivateMemSize)? {
+            KernelWorkGroupInfoResult::PrivateMemSize(s) => Ok(s as usize),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns subgroup information for this kernel when enqueued on
+    /// `device` with the local work size `local_work_size`.
+    ///
+    /// Returns an error if `device` does not support the `cl_khr_subgroups`
+    /// extension or `local_work_size` is unspecified.
+    #[cfg(feature = "opencl_version_2_1")]
+    fn sub_group_info(&self, device: Device, local_work_size: &SpatialDims,
+            info_kind: KernelSubGroupInfo) -> OclResult<usize> {
+        if !device.has_extension("cl_khr_subgroups")? {
+            return Err(format!("Kernel::sub_group_info: The device ('{}') does not support \
+                subgroups (the 'cl_khr_subgroups' extension).",
+                device.name().unwrap_or_else(|_| "<unknown>".into())).into());
+        }
+        let lens = local_work_size.to_lens().map_err(|_| OclError::from(
+            "Kernel::sub_group_info: The local work size must be specified."))?;
+        let dim_count = local_work_size.dim_count() as usize;
+        core::get_kernel_sub_group_info(&self.obj_core, device.as_core(), info_kind,
+            &lens[..dim_count], None).map_err(OclError::from)
+    }
+
+    /// Returns the maximum subgroup size of this kernel when enqueued on
+    /// `device` with the local work size `local_work_size`
+    /// (`CL_KERNEL_MAX_SUB_GROUP_SIZE_FOR_NDRANGE`).
+    ///
+    /// Returns an error if `device` does not support subgroups
+    /// (`cl_khr_subgroups`).
+    ///
+    /// [Version Controlled: OpenCL 2.1+]
+    #[cfg(feature = "opencl_version_2_1")]
+    pub fn max_sub_group_size_for_ndrange(&self, device: Device, local_work_size: &SpatialDims)
+            -> OclResult<usize> {
+        self.sub_group_info(device, local_work_size, KernelSubGroupInfo::MaxSubGroupSizeForNdrange)
+    }
+
+    /// Returns the number of subgroups in each work-group of this kernel when
+    /// enqueued on `device` with the local work size `local_work_size`
+    /// (`CL_KERNEL_SUB_GROUP_COUNT_FOR_NDRANGE`).
+    ///
+    /// Use this to size per-subgroup buffers for subgroup reductions.
+    /// Returns an error if `device` does not support subgroups
+    /// (`cl_khr_subgroups`).
+    ///
+    /// [Version Controlled: OpenCL 2.1+]
+    #[cfg(feature = "opencl_version_2_1")]
+    pub fn sub_group_count_for_ndrange(&self, device: Device, local_work_size: &SpatialDims)
+            -> OclResult<usize> {
+        self.sub_group_info(device, local_work_size, KernelSubGroupInfo::SubGroupCountForNdrange)
+    }
+
     /// Returns argument information for this kernel.
     pub fn arg_info(&self, arg_idx: u32, info_kind: KernelArgInfo)
             -> OclResult<KernelArgInfoResult> {
@@ -836,6 +1986,48 @@ impl Kernel {
             .map(|r| r.into()).map_err(OclError::from)
     }
 
+    /// Returns an error if the function name of this kernel (as reported by
+    /// `CL_KERNEL_FUNCTION_NAME`) is not `name`.
+    pub fn verify_name(&self, name: &str) -> OclResult<()> {
+        let actual = self.name()?;
+        if actual == name {
+            Ok(())
+        } else {
+            Err(KernelError::NameMismatch { expected: name.to_owned(), actual }.into())
+        }
+    }
+
+    /// Returns a description of each argument of this kernel, in order,
+    /// obtained using `clGetKernelArgInfo`.
+    ///
+    /// Useful for verifying that host-side bindings match the kernel
+    /// signature, e.g. by printing each descriptor.
+    ///
+    /// Returns `KernelError::ArgInfoUnavailable` if the program was not
+    /// built with the `-cl-kernel-arg-info` compiler option (argument info
+    /// is only otherwise guaranteed for programs built from source on some
+    /// platforms). Requires OpenCL 1.2 or later.
+    pub fn describe_args(&self) -> OclResult<Vec<ArgDescriptor>> {
+        let map_unavailable = |err: OclError| {
+            if err.api_status() == Some(Status::CL_KERNEL_ARG_INFO_NOT_AVAILABLE) {
+                KernelError::ArgInfoUnavailable.into()
+            } else {
+                err
+            }
+        };
+
+        (0..self.num_args()?).map(|index| {
+            let name = arg_name(&self.obj_core, index).map_err(map_unavailable)?;
+            let type_name = arg_type_name(&self.obj_core, index).map_err(map_unavailable)?;
+            let address_qualifier = match self.arg_info(index, KernelArgInfo::AddressQualifier)
+                    .map_err(map_unavailable)? {
+                KernelArgInfoResult::AddressQualifier(aq) => aq,
+                _ => unreachable!(),
+            };
+            Ok(ArgDescriptor { index, name, type_name, address_qualifier })
+        }).collect()
+    }
+
     /// Returns the number of arguments this kernel has.
     pub fn num_args(&self) -> OclResult<u32> {
         match core::get_kernel_info(&self.obj_core, KernelInfo::NumArgs) {
@@ -967,6 +2159,7 @@ pub struct KernelBuilder<'b> {
     gws: SpatialDims,
     lws: SpatialDims,
     disable_arg_check: bool,
+    allow_unset_args: bool,
 }
 
 impl<'b> KernelBuilder<'b> {
@@ -983,6 +2176,7 @@ impl<'b> KernelBuilder<'b> {
             gws: SpatialDims::Unspecified,
             lws: SpatialDims::Unspecified,
             disable_arg_check: false,
+            allow_unset_args: false,
         }
     }
 
@@ -1068,6 +2262,40 @@ impl<'b> KernelBuilder<'b> {
         self
     }
 
+    /// Sets the default global work offset from a fallible computation,
+    /// returning the error, if any.
+    ///
+    /// Allows building with `?`. For example:
+    /// `kb.try_global_work_offset(offset())?.try_arg(buffer())?.build()?`.
+    pub fn try_global_work_offset<'s, D, E>(&'s mut self, gwo: Result<D, E>)
+            -> OclResult<&'s mut KernelBuilder<'b>>
+            where D: Into<SpatialDims>, E: Into<OclError> {
+        self.gwo = gwo.map_err(Into::into)?.into();
+        Ok(self)
+    }
+
+    /// Sets the default global work size from a fallible computation,
+    /// returning the error, if any.
+    ///
+    /// Also returns an error if any specified dimension is zero.
+    pub fn try_global_work_size<'s, D, E>(&'s mut self, gws: Result<D, E>)
+            -> OclResult<&'s mut KernelBuilder<'b>>
+            where D: Into<SpatialDims>, E: Into<OclError> {
+        self.gws = check_work_size("global", gws.map_err(Into::into)?.into())?;
+        Ok(self)
+    }
+
+    /// Sets the default local work size from a fallible computation,
+    /// returning the error, if any.
+    ///
+    /// Also returns an error if any specified dimension is zero.
+    pub fn try_local_work_size<'s, D, E>(&'s mut self, lws: Result<D, E>)
+            -> OclResult<&'s mut KernelBuilder<'b>>
+            where D: Into<SpatialDims>, E: Into<OclError> {
+        self.lws = check_work_size("local", lws.map_err(Into::into)?.into())?;
+        Ok(self)
+    }
+
     /// Adds a new argument to the kernel and returns the index.
     fn new_arg(&mut self, arg_val: ArgValKeeper<'b>, type_id: Option<TypeId>, mem: Option<MemCore>) -> u32 {
         let arg_idx = self.args.len() as u32;
@@ -1090,6 +2318,9 @@ impl<'b> KernelBuilder<'b> {
     fn new_arg_buf<T, M>(&mut self, buffer_opt: Option<&'b M>) -> u32
             where T: OclPrm, M: 'b + AsMem<T> + MemCmdAll {
         match buffer_opt {
+            // The argument value points to the `cl_mem` within the buffer
+            // itself (not a local) and is copied by `clSetKernelArg`, so the
+            // same buffer may be bound to any number of argument slots:
             Some(buffer) => {
                 self.new_arg(ArgValKeeper::Shared(ArgVal::mem(buffer.as_mem())),
                     Some(TypeId::of::<T>()),
@@ -1169,6 +2400,27 @@ impl<'b> KernelBuilder<'b> {
         self
     }
 
+    /// Adds a new argument, produced by a fallible computation, to the
+    /// kernel, returning the error, if any.
+    ///
+    /// See `::arg`.
+    pub fn try_arg<'s, T, A, E>(&'s mut self, arg: Result<A, E>)
+            -> OclResult<&'s mut KernelBuilder<'b>>
+            where T: OclPrm, A: Into<ArgValConverter<'b, T>>, E: Into<OclError> {
+        Ok(self.arg(arg.map_err(Into::into)?))
+    }
+
+    /// Adds a new named argument, produced by a fallible computation, to the
+    /// kernel, returning the error, if any.
+    ///
+    /// See `::arg_named`.
+    pub fn try_arg_named<'s, T, S, A, E>(&'s mut self, name: S, arg: Result<A, E>)
+            -> OclResult<&'s mut KernelBuilder<'b>>
+            where S: Into<Cow<'static, str>>, T: OclPrm, A: Into<ArgValConverter<'b, T>>,
+                E: Into<OclError> {
+        Ok(self.arg_named(name, arg.map_err(Into::into)?))
+    }
+
     /// Adds a new argument to the kernel specifying the buffer object represented
     /// by 'buffer'.
     ///
@@ -1242,6 +2494,17 @@ impl<'b> KernelBuilder<'b> {
         self
     }
 
+    /// Adds a new argument to the kernel specifying the device queue
+    /// (`queue_t`) represented by 'queue'. Argument is added to the bottom of
+    /// the argument order.
+    ///
+    /// `queue` must be an on-device queue (see `Queue::new_on_device`).
+    pub fn arg_queue<'s>(&'s mut self, queue: &'b Queue) -> &'s mut KernelBuilder<'b> {
+        // Type is ignored:
+        self.new_arg(ArgValKeeper::Shared(ArgVal::queue(queue.as_core())), None, None);
+        self
+    }
+
     /// Adds a new argument specifying the allocation of a local variable of size
     /// `length * sizeof(T)` bytes (builder_style).
     ///
@@ -1249,6 +2512,10 @@ impl<'b> KernelBuilder<'b> {
     ///
     /// Local variables are used to share data between work items in the same
     /// workgroup.
+    ///
+    /// Kernels with several `__local` arguments simply call this once for
+    /// each, in argument order. Each may have a different type and length.
+    /// Use `Kernel::set_arg_local` to change a size after building.
     pub fn arg_local<'s, T>(&'s mut self, length: usize) -> &'s mut KernelBuilder<'b>
             where T: OclPrm {
         self.new_arg_loc::<T>(length);
@@ -1340,8 +2607,8 @@ impl<'b> KernelBuilder<'b> {
     ///
     /// Named arguments can be easily modified later using `::set_arg_scl_named()`.
     #[deprecated(since = "0.18.0", note = "Use ::arg_named instead.")]
-    pub fn arg_scl_named<'s, T>(&'s mut self, name: &'static str, scalar: T) -> &'s mut KernelBuilder<'b>
-            where T: OclPrm {
+    pub fn arg_scl_named<'s, T, S>(&'s mut self, name: S, scalar: T) -> &'s mut KernelBuilder<'b>
+            where S: Into<Cow<'static, str>>, T: OclPrm {
         let arg_idx = self.new_arg_scl(scalar);
         self.named_args.insert(name.into(), arg_idx);
         self
@@ -1355,8 +2622,8 @@ impl<'b> KernelBuilder<'b> {
     ///
     /// Named arguments can be easily modified later using `::set_arg_vec_named()`.
     #[deprecated(since = "0.18.0", note = "Use ::arg_named instead.")]
-    pub fn arg_vec_named<'s, T>(&'s mut self, name: &'static str, vector: T) -> &'s mut KernelBuilder<'b>
-            where T: OclPrm {
+    pub fn arg_vec_named<'s, T, S>(&'s mut self, name: S, vector: T) -> &'s mut KernelBuilder<'b>
+            where S: Into<Cow<'static, str>>, T: OclPrm {
         let arg_idx = self.new_arg_vec(vector);
         self.named_args.insert(name.into(), arg_idx);
         self
@@ -1368,7 +2635,9 @@ impl<'b> KernelBuilder<'b> {
     /// The argument is added to the bottom of the argument order.
     ///
     /// Named arguments can be easily modified later using `::set_arg_smp_named()`.
-    pub fn arg_sampler_named<'s>(&'s mut self, name: &'static str, sampler_opt: Option<&'b Sampler>) -> &'s mut KernelBuilder<'b> {
+    pub fn arg_sampler_named<'s, S>(&'s mut self, name: S, sampler_opt: Option<&'b Sampler>)
+            -> &'s mut KernelBuilder<'b>
+            where S: Into<Cow<'static, str>> {
         let arg_idx = self.new_arg_smp(sampler_opt);
         self.named_args.insert(name.into(), arg_idx);
         self
@@ -1402,12 +2671,40 @@ impl<'b> KernelBuilder<'b> {
         self
     }
 
+    /// Allows the kernel to be built without declaring any arguments.
+    ///
+    /// Normally, every argument must be declared (using `::arg`,
+    /// `::arg_named`, etc.) before building. If no arguments have been
+    /// declared and this has been called, the argument count check is
+    /// skipped and all arguments must instead be set by index, using
+    /// `Kernel::set_arg`, before the kernel is enqueued. Argument types are
+    /// still checked when set.
+    pub fn allow_unset_args<'s>(&'s mut self) -> &'s mut KernelBuilder<'b> {
+        self.allow_unset_args = true;
+        self
+    }
+
     /// Builds and returns a new `Kernel`
+    ///
+    /// If no default queue has been specified with `::queue`, the scoped
+    /// default queue set by `ocl::with_default` (if any) is used.
     pub fn build(&self) -> OclResult<Kernel> {
         let program = self.program.ok_or(KernelError::BuilderNoProgram)?;
         let name = self.name.as_ref().ok_or(KernelError::BuilderNoKernelName)?;
 
-        let obj_core = core::create_kernel(program, name)?;
+        let obj_core = match core::create_kernel(program, name) {
+            Ok(k) => k,
+            Err(err) => {
+                // Provide a list of valid names if the name was not found:
+                if err.api_status() == Some(Status::CL_INVALID_KERNEL_NAME) {
+                    if let Ok(available) = program.kernel_names() {
+                        return Err(KernelError::BuilderInvalidKernelName {
+                            name: name.clone(), available }.into());
+                    }
+                }
+                return Err(err.into());
+            },
+        };
 
         let num_args = match core::get_kernel_info(&obj_core, KernelInfo::NumArgs) {
             Ok(KernelInfoResult::NumArgs(num)) => num,
@@ -1415,7 +2712,9 @@ impl<'b> KernelBuilder<'b> {
             _=> unreachable!(),
         };
 
-        if self.args.len() as u32 != num_args {
+        let args_unset = self.allow_unset_args && self.args.is_empty();
+
+        if !args_unset && self.args.len() as u32 != num_args {
             return Err(KernelError::BuilderWrongArgCount {
                 required: num_args,
                 specified: self.args.len() as u32,
@@ -1478,11 +2777,16 @@ impl<'b> KernelBuilder<'b> {
             obj_core,
             named_args: self.named_args.clone(),
             mem_args: self.mem_args.clone(),
-            queue: self.queue.clone(),
+            queue: self.queue.clone().or_else(Queue::scoped_default),
             gwo: self.gwo,
             gws: self.gws,
             lws: self.lws,
             arg_types,
+            timing: None,
+            arg_cache: None,
+            pending_waits: RefCell::new(EventList::new()),
+            last_event: None,
+            transient_args: RefCell::new(Vec::new()),
         })
     }
 }
@@ -1519,6 +2823,126 @@ pub fn arg_name(core: &KernelCore, arg_idx: u32) -> OclResult<String> {
 }
 
 
+/// A description of a kernel argument, as declared in kernel source.
+///
+/// Returned by `Kernel::describe_args`. Displays as it would be declared
+/// (e.g. `[0] __global float* src`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArgDescriptor {
+    /// The index of the argument.
+    pub index: u32,
+    /// The name of the argument.
+    pub name: String,
+    /// The type name of the argument (e.g. `float*` or `uint`).
+    pub type_name: String,
+    /// The address space qualifier of the argument.
+    pub address_qualifier: KernelArgAddressQualifier,
+}
+
+impl std::fmt::Display for ArgDescriptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let qualifier = match self.address_qualifier {
+            KernelArgAddressQualifier::Global => "__global ",
+            KernelArgAddressQualifier::Local => "__local ",
+            KernelArgAddressQualifier::Constant => "__constant ",
+            KernelArgAddressQualifier::Private => "",
+        };
+        write!(f, "[{}] {}{} {}", self.index, qualifier, self.type_name, self.name)
+    }
+}
+
+
+/// Declares a struct describing the argument layout of a kernel.
+///
+/// Each field becomes a named kernel argument, in declaration order. Because
+/// the fields are typed, passing a value of the wrong type (e.g. a
+/// `Buffer<i32>` where an `&Buffer<f32>` was declared) is a compile error
+/// rather than an error returned by `KernelBuilder::build` or
+/// `Kernel::set_arg`.
+///
+/// The generated struct has two methods:
+///
+/// * `declare(self, &mut KernelBuilder)`: declares every field on a kernel
+///   builder using `::arg_named` (the field name is used as the argument
+///   name).
+/// * `set(self, &Kernel)`: sets every field on an already built kernel using
+///   `::set_arg`.
+///
+/// Note that this only checks the Rust side of the layout. The device side
+/// (the kernel function signature) is still checked at runtime, as usual.
+///
+/// ### Example
+///
+/// ```rust,ignore
+/// kernel_args! {
+///     pub struct AddArgs<'a> {
+///         buffer: &'a Buffer<f32>,
+///         addend: f32,
+///     }
+/// }
+///
+/// let mut builder = pro_que.kernel_builder("add");
+/// AddArgs { buffer: &buffer, addend: 10.0 }.declare(&mut builder);
+/// let kernel = builder.build()?;
+///
+/// // Later:
+/// AddArgs { buffer: &other_buffer, addend: 5.0 }.set(&kernel)?;
+/// ```
+#[macro_export]
+macro_rules! kernel_args {
+    ($(#[$attr:meta])* $vis:vis struct $name:ident<$lt:lifetime> {
+        $($field:ident: $ty:ty),* $(,)*
+    }) => {
+        $(#[$attr])*
+        $vis struct $name<$lt> {
+            $(pub $field: $ty),*
+        }
+
+        impl<$lt> $name<$lt> {
+            /// Declares each field, in order, as a named argument.
+            #[allow(dead_code)]
+            pub fn declare<'s>(self, builder: &'s mut $crate::builders::KernelBuilder<$lt>)
+                    -> &'s mut $crate::builders::KernelBuilder<$lt> {
+                $( builder.arg_named(stringify!($field), self.$field); )*
+                builder
+            }
+
+            /// Sets each field, by name, on a built kernel.
+            #[allow(dead_code)]
+            pub fn set(self, kernel: &$crate::Kernel) -> $crate::Result<()> {
+                $( kernel.set_arg(stringify!($field), self.$field)?; )*
+                Ok(())
+            }
+        }
+    };
+    ($(#[$attr:meta])* $vis:vis struct $name:ident {
+        $($field:ident: $ty:ty),* $(,)*
+    }) => {
+        $(#[$attr])*
+        $vis struct $name {
+            $(pub $field: $ty),*
+        }
+
+        impl $name {
+            /// Declares each field, in order, as a named argument.
+            #[allow(dead_code)]
+            pub fn declare<'s, 'b>(self, builder: &'s mut $crate::builders::KernelBuilder<'b>)
+                    -> &'s mut $crate::builders::KernelBuilder<'b> {
+                $( builder.arg_named(stringify!($field), self.$field); )*
+                builder
+            }
+
+            /// Sets each field, by name, on a built kernel.
+            #[allow(dead_code)]
+            pub fn set(self, kernel: &$crate::Kernel) -> $crate::Result<()> {
+                $( kernel.set_arg(stringify!($field), self.$field)?; )*
+                Ok(())
+            }
+        }
+    };
+}
+
+
 pub mod arg_type {
     #![allow(unused_imports)]
     use std::any::{Any, TypeId};
@@ -1742,6 +3166,17 @@ pub mod arg_type {
             self.matches(TypeId::of::<T>())
         }
 
+        /// Returns the base (element) type.
+        pub fn base_type(&self) -> BaseType {
+            self.base_type
+        }
+
+        /// Returns the number of components (e.g. `Cardinality::Four` for
+        /// `float4`).
+        pub fn cardinality(&self) -> Cardinality {
+            self.cardinality
+        }
+
         #[allow(dead_code)]
         pub fn is_ptr(&self) -> bool {
             self.is_ptr
@@ -1790,4 +3225,122 @@ mod tests {
         unsafe { kernel_diff.enq()?; }
         Ok(())
     }
+
+    #[test]
+    fn builder_try_methods() {
+        use crate::builders::KernelBuilder;
+        use crate::Error as OclError;
+
+        let mut kb = KernelBuilder::new();
+        assert!(kb.try_global_work_size(Ok::<_, OclError>([16, 16])).is_ok());
+        assert!(kb.try_global_work_size(Ok::<_, OclError>([16, 0])).is_err());
+        assert!(kb.try_local_work_size(Err::<[usize; 2], _>("no local size")).is_err());
+        assert!(kb.try_global_work_offset(Ok::<_, OclError>([0, 0])).is_ok());
+        assert!(kb.try_arg(Ok::<_, OclError>(1.0f32)).is_ok());
+        assert!(kb.try_arg_named("a", Err::<f32, _>("no value")).is_err());
+    }
+
+    #[test]
+    fn choose_lws_strategies() {
+        use super::{choose_lws, LwsStrategy};
+
+        let max_wi = [1024, 1024, 64];
+
+        assert_eq!(choose_lws([4096, 1, 1], 1, 256, &max_wi, 32, LwsStrategy::Max), [256, 1, 1]);
+        assert_eq!(choose_lws([1000, 1, 1], 1, 256, &max_wi, 32, LwsStrategy::Max), [250, 1, 1]);
+        assert_eq!(choose_lws([1000, 1, 1], 1, 256, &max_wi, 8, LwsStrategy::Occupancy),
+            [200, 1, 1]);
+        // No multiple of 32 divides 1000:
+        assert_eq!(choose_lws([1000, 1, 1], 1, 256, &max_wi, 32, LwsStrategy::Occupancy),
+            [250, 1, 1]);
+        assert_eq!(choose_lws([512, 512, 1], 2, 256, &max_wi, 32, LwsStrategy::Max), [256, 1, 1]);
+        assert_eq!(choose_lws([512, 512, 1], 2, 256, &max_wi, 32, LwsStrategy::Square),
+            [16, 16, 1]);
+        assert_eq!(choose_lws([64, 64, 64], 3, 512, &max_wi, 32, LwsStrategy::Square), [8, 8, 8]);
+        // Respects the maximum work-item size of each dimension:
+        assert_eq!(choose_lws([128, 128, 128], 3, 1024, &[4, 4, 4], 1, LwsStrategy::Max),
+            [4, 4, 4]);
+    }
+
+    #[test]
+    fn work_group_count() {
+        use super::work_group_count;
+        use crate::SpatialDims;
+
+        assert_eq!(work_group_count(SpatialDims::Two(1024, 512), SpatialDims::Two(16, 16)),
+            Some((64, 32, 1)));
+        assert_eq!(work_group_count(SpatialDims::One(1000), SpatialDims::One(64)),
+            Some((16, 1, 1)));
+        assert_eq!(work_group_count(SpatialDims::One(1000), SpatialDims::Unspecified), None);
+        assert_eq!(work_group_count(SpatialDims::Unspecified, SpatialDims::One(64)), None);
+    }
+
+    #[test]
+    fn arg_descriptor_display() {
+        use super::ArgDescriptor;
+        use crate::enums::KernelArgAddressQualifier;
+
+        let desc = |index, name: &str, type_name: &str, address_qualifier| ArgDescriptor {
+            index, name: name.to_owned(), type_name: type_name.to_owned(), address_qualifier };
+
+        assert_eq!(desc(0, "src", "float*", KernelArgAddressQualifier::Global).to_string(),
+            "[0] __global float* src");
+        assert_eq!(desc(2, "scratch", "float*", KernelArgAddressQualifier::Local).to_string(),
+            "[2] __local float* scratch");
+        assert_eq!(desc(3, "len", "uint", KernelArgAddressQualifier::Private).to_string(),
+            "[3] uint len");
+    }
+
+    #[test]
+    fn timing_stats_record() {
+        use super::KernelTimingStats;
+
+        let mut stats = KernelTimingStats::default();
+        assert_eq!(stats.mean_ns(), None);
+
+        for &d in [300u64, 100, 200].iter() { stats.record(d); }
+        assert_eq!(stats, KernelTimingStats { count: 3, total_ns: 600, min_ns: 100, max_ns: 300 });
+        assert_eq!(stats.mean_ns(), Some(200));
+    }
+
+    #[test]
+    fn arg_cache_update() {
+        use crate::core::ArgVal;
+        use super::ArgCache;
+
+        let mut cache = ArgCache::default();
+        assert!(cache.update(0, &ArgVal::scalar(&5u32)));
+        assert!(!cache.update(0, &ArgVal::scalar(&5u32)));
+        assert!(cache.update(0, &ArgVal::scalar(&6u32)));
+        assert!(cache.update(1, &ArgVal::scalar(&6u32)));
+        assert!(cache.update(1, &ArgVal::local::<f32>(&64)));
+        assert!(!cache.update(1, &ArgVal::local::<f32>(&64)));
+        assert!(cache.update(1, &ArgVal::local::<f32>(&128)));
+        assert_eq!(cache.redundant, 2);
+    }
+
+    #[test]
+    fn work_dims_from_slices() {
+        use super::work_dims_from_slices;
+        use crate::SpatialDims;
+
+        assert_eq!(work_dims_from_slices(1, &[64], None).unwrap(),
+            (SpatialDims::One(64), SpatialDims::Unspecified));
+        assert_eq!(work_dims_from_slices(3, &[8, 4, 2], Some(&[2, 2, 2])).unwrap(),
+            (SpatialDims::Three(8, 4, 2), SpatialDims::Three(2, 2, 2)));
+        assert!(work_dims_from_slices(0, &[], None).is_err());
+        assert!(work_dims_from_slices(4, &[1, 1, 1, 1], None).is_err());
+        assert!(work_dims_from_slices(2, &[8], None).is_err());
+        assert!(work_dims_from_slices(2, &[8, 8], Some(&[4])).is_err());
+    }
+
+    #[test]
+    fn non_uniform_dim() {
+        use super::non_uniform_dim;
+
+        assert_eq!(non_uniform_dim([1024, 512, 1], [16, 16, 1]), None);
+        assert_eq!(non_uniform_dim([1000, 1, 1], [64, 1, 1]), Some(0));
+        assert_eq!(non_uniform_dim([64, 30, 4], [8, 8, 4]), Some(1));
+        assert_eq!(non_uniform_dim([64, 64, 6], [8, 8, 4]), Some(2));
+    }
 }
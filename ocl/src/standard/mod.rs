@@ -16,19 +16,26 @@ mod event;
 mod spatial_dims;
 
 pub use self::platform::{PlatformError, Extensions, Platform};
-pub use self::device::{DeviceError, Device, DeviceSpecifier};
+pub use self::device::{DeviceError, Device, DeviceSpecifier, DeviceDescription, Partition,
+    SubDevice};
 pub use self::context::{Context, ContextBuilder};
-pub use self::program::{Program, ProgramBuilder, BuildOpt};
-pub use self::queue::Queue;
-pub use self::kernel::{KernelError, KernelCmd, Kernel, KernelBuilder};
+pub use self::program::{Program, ProgramBuilder, BuildOpt, Diagnostic, DiagnosticSeverity,
+    parse_build_log};
+#[cfg(feature = "async")]
+pub use self::program::FutureBuild;
+pub use self::queue::{Queue, with_default};
+pub use self::kernel::{KernelError, KernelCmd, Kernel, KernelBuilder, KernelArgs, ArgDescriptor,
+    LwsStrategy, KernelTimingStats};
 pub use self::buffer::{BufferCmdKind, BufferCmdDataShape, BufferCmd, Buffer, QueCtx,
-    BufferBuilder, BufferReadCmd, BufferWriteCmd, BufferMapCmd, BufferCmdError, WriteSrc};
+    BufferBuilder, BufferReadCmd, BufferWriteCmd, BufferMapCmd, BufferCmdError, BufferError,
+    WriteSrc, WeakBuffer};
 pub use self::image::{ImageCmdKind, ImageCmd, Image, ImageBuilder};
 pub use self::sampler::Sampler;
 pub use self::pro_que::{ProQue, ProQueBuilder};
-pub use self::event::{Event, EventArray, EventList, IntoMarker, RawEventArray, IntoRawEventArray};
+pub use self::event::{Event, EventArray, EventList, IntoMarker, RawEventArray, IntoRawEventArray,
+    ProfilingSummary};
 pub use self::spatial_dims::SpatialDims;
-#[cfg(not(feature = "async_block"))]
+#[cfg(all(feature = "async", not(feature = "async_block")))]
 pub use self::cb::{_unpark_task, box_raw_void};
 pub use self::traits::{MemLen, WorkDims};
 pub use self::types::{ClNullEventPtrEnum, ClWaitListPtrEnum};
@@ -62,7 +69,7 @@ impl<'a, T> HostSlice<'a, T> where T: 'a {
 //================================ FUNCTIONS ==================================
 //=============================================================================
 
-#[cfg(not(feature = "async_block"))]
+#[cfg(all(feature = "async", not(feature = "async_block")))]
 mod cb {
     use crate::core::ffi::c_void;
     use num_traits::FromPrimitive;
@@ -8,8 +8,10 @@ use std;
 use std::ops::{Deref, DerefMut};
 use std::str::SplitWhitespace;
 use crate::ffi::cl_platform_id;
-use crate::core::{self, PlatformId as PlatformIdCore, PlatformInfo, PlatformInfoResult, ClPlatformIdPtr};
+use crate::core::{self, PlatformId as PlatformIdCore, PlatformInfo, PlatformInfoResult, ClPlatformIdPtr,
+    DeviceType, Status, OpenclVersion};
 use crate::error::{Error as OclError, Result as OclResult};
+use crate::standard::Device;
 
 
 #[derive(Debug, Fail)]
@@ -105,18 +107,22 @@ impl Platform {
             .map(|r| r.into()).map_err(OclError::from)
     }
 
-    /// Returns the platform driver version as a string.
+    /// Returns the OpenCL version supported by the platform.
     ///
-    /// Returns the OpenCL version supported by the implementation. This
-    /// version string has the following format:
+    /// The version is parsed from the `CL_PLATFORM_VERSION` string which has
+    /// the following format:
     ///
     /// * OpenCL<space><major_version.minor_version><space><platform-specific
     ///   information>
     ///
-    /// * The major_version.minor_version value returned will be '1.2'.
-    ///
-    /// * TODO: Convert this to new version system returning an `OpenclVersion`.
-    pub fn version(&self) -> OclResult<String> {
+    /// Use `::version_string` to obtain the unparsed string.
+    pub fn version(&self) -> OclResult<OpenclVersion> {
+        self.0.version().map_err(OclError::from)
+    }
+
+    /// Returns the unparsed platform version string (including any
+    /// platform-specific information).
+    pub fn version_string(&self) -> OclResult<String> {
         core::get_platform_info(&self.0, PlatformInfo::Version)
             .map(|r| r.into()).map_err(OclError::from)
     }
@@ -142,6 +148,32 @@ impl Platform {
         extensions.map(|e| Extensions { inner: e.into() }).map_err(OclError::from)
     }
 
+    /// Returns a list of the devices on this platform matching the
+    /// `device_types` bitfield (e.g. `DeviceType::GPU | DeviceType::CPU`).
+    ///
+    /// Returns an empty list (rather than an error) if no devices match. All
+    /// other OpenCL errors are returned as such.
+    pub fn devices(&self, device_types: DeviceType) -> OclResult<Vec<Device>> {
+        match core::get_device_ids(self.0, Some(device_types), None) {
+            Ok(ids) => Ok(Device::list_from_core(ids)),
+            Err(err) => {
+                if err.api_status() == Some(Status::CL_DEVICE_NOT_FOUND) {
+                    Ok(Vec::new())
+                } else {
+                    Err(err.into())
+                }
+            },
+        }
+    }
+
+    /// Returns a list of the GPU devices on this platform or an empty list
+    /// if there are none.
+    ///
+    /// Equivalent to `::devices(DeviceType::GPU)`.
+    pub fn gpu_devices(&self) -> OclResult<Vec<Device>> {
+        self.devices(DeviceType::GPU)
+    }
+
     /// Returns a reference to the underlying `PlatformIdCore`.
     pub fn as_core(&self) -> &PlatformIdCore {
         &self.0
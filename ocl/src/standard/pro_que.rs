@@ -69,15 +69,29 @@ impl ProQue {
         }
     }
 
-    // /// Creates a kernel with pre-assigned dimensions.
-    // pub fn create_kernel(&self, name: &str) -> OclResult<Kernel> {
-    //     let kernel = Kernel::new(name.to_string(), &self.program)?
-    //         .queue(self.queue.clone());
-    //     match self.dims {
-    //         Some(d) => Ok(kernel.gws(d)),
-    //         None => Ok(kernel),
-    //     }
-    // }
+    /// Creates a kernel with the default queue and the global work size,
+    /// `gws`, pre-configured.
+    ///
+    /// Arguments do not need to be declared beforehand and must instead be
+    /// set by index using `Kernel::set_arg` before enqueuing. Use
+    /// `::kernel_builder` to declare arguments (or named arguments) up
+    /// front.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// let kernel = pro_que.create_kernel("add", buffer.len())?;
+    /// kernel.set_arg(0, &buffer)?;
+    /// kernel.set_arg(1, &10.0f32)?;
+    /// unsafe { kernel.enq()?; }
+    /// ```
+    pub fn create_kernel<S, D>(&self, name: S, gws: D) -> OclResult<Kernel>
+            where S: Into<String>, D: Into<SpatialDims> {
+        let mut kb = self.kernel_builder(name);
+        kb.global_work_size(gws);
+        kb.allow_unset_args();
+        kb.build()
+    }
 
     /// Returns a new `KernelBuilder` with the name, program, default queue,
     /// and global work size pre-configured.
@@ -8,13 +8,18 @@ use std::path::PathBuf;
 use std::collections::HashSet;
 use std::convert::Into;
 
-
+#[cfg(feature = "async")]
+use futures::{Future, Poll, Async};
 use crate::core::{self, Result as OclCoreResult, Program as ProgramCore, Context as ContextCore,
-    ProgramInfo, ProgramInfoResult, ProgramBuildInfo, ProgramBuildInfoResult};
+    ProgramInfo, ProgramInfoResult, ProgramBuildInfo, ProgramBuildInfoResult, ProgramBuildStatus};
+#[cfg(feature = "async")]
+use crate::core::{ProgramBuildError, Error as OclCoreError};
 #[cfg(feature = "opencl_version_2_1")]
 use core::ClVersions;
 use crate::error::{Result as OclResult, Error as OclError};
 use crate::standard::{Context, Device, DeviceSpecifier};
+#[cfg(feature = "async")]
+use crate::standard::Event;
 
 
 /// A program from which kernels can be created from.
@@ -83,6 +88,21 @@ impl Program {
         core::get_program_info(&self.0, info_kind)
     }
 
+    /// Returns the names of all kernels in this program
+    /// (`CL_PROGRAM_KERNEL_NAMES`).
+    ///
+    /// Useful to validate a kernel name before creating a kernel.
+    pub fn kernel_names(&self) -> OclResult<Vec<String>> {
+        match self.info(ProgramInfo::KernelNames)? {
+            ProgramInfoResult::KernelNames(names) => Ok(names.split(';')
+                .map(|n| n.trim())
+                .filter(|n| !n.is_empty())
+                .map(|n| n.to_owned())
+                .collect()),
+            _ => unreachable!(),
+        }
+    }
+
     /// Returns info about this program's build.
     ///
     /// * TODO: Check that device is valid.
@@ -91,6 +111,29 @@ impl Program {
         core::get_program_build_info(&self.0, &device, info_kind)
     }
 
+    /// Returns the build status and build log for each device associated with
+    /// this program, as `(device, status, log)`.
+    ///
+    /// A program built for several devices may have built successfully for
+    /// some and failed for others. When a build fails, the same information
+    /// is available from the returned error (see `Error::build_statuses`).
+    pub fn build_statuses(&self) -> OclResult<Vec<(Device, ProgramBuildStatus, String)>> {
+        let devices = self.0.devices()?;
+        Ok(core::get_program_build_statuses(&self.0, &devices)?.into_iter()
+            .map(|(device, status, log)| (Device::from(device), status, log))
+            .collect())
+    }
+
+    /// Returns the diagnostics contained in the build log for `device`.
+    ///
+    /// See [`parse_build_log`](fn.parse_build_log.html).
+    pub fn build_diagnostics(&self, device: Device) -> OclResult<Vec<Diagnostic>> {
+        match self.build_info(device, ProgramBuildInfo::BuildLog)? {
+            ProgramBuildInfoResult::BuildLog(log) => Ok(parse_build_log(&log)),
+            _ => unreachable!(),
+        }
+    }
+
     fn fmt_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.debug_struct("Program")
             .field("ReferenceCount", &self.info(ProgramInfo::ReferenceCount))
@@ -133,6 +176,127 @@ impl DerefMut for Program {
 }
 
 
+/// The severity of a compiler diagnostic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+    Note,
+    /// The build log format was not recognized.
+    Unknown,
+}
+
+
+/// A single compiler diagnostic parsed from a program build log.
+///
+/// `line` and `column` are one-based, as reported by the compiler, and refer
+/// to the combined program source. Either may be unavailable depending on
+/// the vendor log format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: DiagnosticSeverity,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    pub message: String,
+}
+
+
+/// Severity markers, checked in order at each position in a log line.
+static SEVERITY_TAGS: &[(&str, DiagnosticSeverity)] = &[
+    ("fatal error: ", DiagnosticSeverity::Error),
+    ("catastrophic error: ", DiagnosticSeverity::Error),
+    ("error: ", DiagnosticSeverity::Error),
+    ("warning: ", DiagnosticSeverity::Warning),
+    ("note: ", DiagnosticSeverity::Note),
+];
+
+
+/// Parses the location prefix of a diagnostic line, returning the line and
+/// column (if any).
+///
+/// Recognizes `file:line:col`, `file:line`, `file(line)`, `file(line,col)`
+/// and `"file", line N`.
+fn parse_location(loc: &str) -> Option<(Option<u32>, Option<u32>)> {
+    let loc = loc.trim_end();
+
+    if let Some(pos) = loc.rfind(", line ") {
+        return loc[pos + 7..].trim().parse().ok().map(|l| (Some(l), None));
+    }
+
+    if loc.ends_with(')') {
+        let pos = loc.rfind('(')?;
+        let mut nums = loc[pos + 1..loc.len() - 1].split(',').map(|n| n.trim().parse());
+        return match (nums.next(), nums.next()) {
+            (Some(Ok(l)), None) => Some((Some(l), None)),
+            (Some(Ok(l)), Some(Ok(c))) => Some((Some(l), Some(c))),
+            _ => None,
+        };
+    }
+
+    let mut parts = loc.rsplitn(3, ':');
+    let last = parts.next()?.trim().parse().ok()?;
+    match parts.next().map(|p| p.trim().parse()) {
+        Some(Ok(line)) if parts.next().is_some() => Some((Some(line), Some(last))),
+        _ => Some((Some(last), None)),
+    }
+}
+
+
+/// Parses a single build log line.
+fn parse_line(line: &str) -> Option<Diagnostic> {
+    let line = line.trim();
+
+    // Find the left-most severity marker which either starts the line or
+    // follows a location prefix (`...: error: ...`).
+    let (pos, tag, severity) = SEVERITY_TAGS.iter()
+        .filter_map(|&(tag, sev)| {
+            line.match_indices(tag)
+                .find(|&(i, _)| i == 0 || line[..i].ends_with(": ") || line[..i].ends_with(' '))
+                .map(|(i, _)| (i, tag, sev))
+        })
+        .min_by_key(|&(i, _, _)| i)?;
+
+    let message = line[pos + tag.len()..].trim().to_owned();
+
+    let (line_num, column) = if pos == 0 {
+        (None, None)
+    } else {
+        let loc = line[..pos].trim_end();
+        if !loc.ends_with(':') { return None; }
+        parse_location(&loc[..loc.len() - 1])?
+    };
+
+    Some(Diagnostic { severity, line: line_num, column, message })
+}
+
+
+/// Parses a program build log into a list of diagnostics.
+///
+/// Recognizes the clang style format (`<kernel>:12:5: error: ...`) used by
+/// most Intel, NVIDIA, AMD, and POCL compilers as well as the older AMD
+/// (`"/tmp/OCL.cl", line 12: error: ...`) and parenthesized
+/// (`<kernel>(12): error: ...`) formats. Lines which are not diagnostics
+/// (source excerpts, carets, summaries) are skipped.
+///
+/// If the log is non-empty but no diagnostics can be recognized, a single
+/// `DiagnosticSeverity::Unknown` diagnostic containing the entire log is
+/// returned.
+pub fn parse_build_log(log: &str) -> Vec<Diagnostic> {
+    let diags: Vec<_> = log.lines().filter_map(parse_line).collect();
+
+    if diags.is_empty() && !log.trim().is_empty() {
+        vec![Diagnostic {
+            severity: DiagnosticSeverity::Unknown,
+            line: None,
+            column: None,
+            message: log.trim().to_owned(),
+        }]
+    } else {
+        diags
+    }
+}
+
+
 /// A build option used by ProgramBuilder.
 ///
 /// Strings intended for use either by the compiler as a command line switch
@@ -460,6 +624,45 @@ impl<'b> ProgramBuilder<'b> {
         Ok(src_strings)
     }
 
+    /// Begins building a new program without blocking and returns a future
+    /// which resolves to the built program, along with its build log, once
+    /// compilation has completed.
+    ///
+    /// Useful for overlapping the compilation of many programs (e.g. kernel
+    /// variants built at startup). If the build fails, the future resolves
+    /// to an error containing the build log (see `Error::build_log`).
+    ///
+    /// Requires the `async` feature.
+    #[cfg(feature = "async")]
+    pub fn build_async(&self, context: &Context) -> OclResult<FutureBuild> {
+        let device_list = match self.device_spec {
+            Some(ref ds) => ds.to_device_list(context.platform()?)?,
+            None => context.devices(),
+        };
+
+        let program = match self.with {
+            CreateWith::Source(_) => {
+                core::create_program_with_source(context.as_core(), &self.get_src_strings()?)?
+            },
+            CreateWith::Binaries(bins) => {
+                core::create_program_with_binary(context.as_core(), &device_list[..], bins)?
+            },
+            #[cfg(feature = "opencl_version_2_1")]
+            CreateWith::Il(il) => {
+                let device_versions = context.device_versions()?;
+                core::create_program_with_il(context.as_core(), il, Some(&device_versions))?
+            },
+            #[cfg(not(feature = "opencl_version_2_1"))]
+            CreateWith::Il(_) => {
+                return Err("ocl::ProgramBuilder::build_async: Unreachable section (IL).".into());
+            },
+            CreateWith::None => return Err("Unable to build program: no source, binary, \
+                or IL has been specified".into()),
+        };
+
+        FutureBuild::new(program, device_list, &self.get_compiler_options()?, context)
+    }
+
     /// Returns a newly built Program.
     //
     // * TODO: If the context is associated with more than one device,
@@ -544,3 +747,116 @@ impl<'b> ProgramBuilder<'b> {
     }
 }
 
+
+/// A future which resolves to a built `Program` and its build log once an
+/// asynchronous build (`ProgramBuilder::build_async`) has completed.
+///
+/// The build log contains the (non-empty) logs of every device, separated by
+/// newlines.
+#[cfg(feature = "async")]
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub struct FutureBuild {
+    program: Option<ProgramCore>,
+    devices: Vec<Device>,
+    build_event: Event,
+}
+
+#[cfg(feature = "async")]
+impl FutureBuild {
+    /// Begins building `program` for `devices`.
+    fn new(program: ProgramCore, devices: Vec<Device>, cmplr_opts: &CString, context: &Context)
+            -> OclResult<FutureBuild> {
+        // Completed by the build callback, which takes ownership of the
+        // reference passed as user data:
+        let build_event = Event::user(context)?;
+        let user_data = build_event.clone().into_raw();
+
+        unsafe {
+            if let Err(err) = core::build_program_async(&program, Some(&devices[..]), cmplr_opts,
+                    core::_complete_build_user_event, user_data) {
+                // The callback will never be called if the build could not
+                // begin (indicated by an API status error):
+                if err.api_status().is_some() {
+                    let _ = Event::from_raw(user_data);
+                }
+                return Err(err.into());
+            }
+        }
+
+        Ok(FutureBuild {
+            program: Some(program),
+            devices,
+            build_event,
+        })
+    }
+}
+
+#[cfg(feature = "async")]
+impl Future for FutureBuild {
+    type Item = (Program, String);
+    type Error = OclError;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        if let Async::NotReady = self.build_event.poll()? {
+            return Ok(Async::NotReady);
+        }
+
+        let program = self.program.take().expect("FutureBuild::poll: Polled after completion.");
+        let device_logs = core::get_program_build_statuses(&program, &self.devices)?;
+        let mut build_log = String::new();
+
+        for (_, _, device_log) in device_logs.iter() {
+            if !device_log.trim().is_empty() {
+                if !build_log.is_empty() { build_log.push('\n'); }
+                build_log.push_str(device_log.trim_end());
+            }
+        }
+
+        if device_logs.iter().any(|&(_, status, _)| status != ProgramBuildStatus::Success) {
+            return Err(OclCoreError::from(ProgramBuildError::DeviceBuildLogs(build_log,
+                device_logs)).into());
+        }
+
+        Ok(Async::Ready((Program(program), build_log)))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_build_log, Diagnostic, DiagnosticSeverity};
+
+    #[test]
+    fn parse_build_log_formats() {
+        let log = "<kernel>:3:12: error: use of undeclared identifier 'x'\n\
+            \x20   buffer[i] = x;\n\
+            \x20               ^\n\
+            \"/tmp/OCL1.cl\", line 7: warning: variable \"y\" was declared but never referenced\n\
+            <kernel>(9): note: previous declaration\n\
+            error: unrecoverable errors\n\
+            1 error generated.";
+
+        let diags = parse_build_log(log);
+        assert_eq!(diags, vec![
+            Diagnostic { severity: DiagnosticSeverity::Error, line: Some(3), column: Some(12),
+                message: "use of undeclared identifier 'x'".to_owned() },
+            Diagnostic { severity: DiagnosticSeverity::Warning, line: Some(7), column: None,
+                message: "variable \"y\" was declared but never referenced".to_owned() },
+            Diagnostic { severity: DiagnosticSeverity::Note, line: Some(9), column: None,
+                message: "previous declaration".to_owned() },
+            Diagnostic { severity: DiagnosticSeverity::Error, line: None, column: None,
+                message: "unrecoverable errors".to_owned() },
+        ]);
+    }
+
+    #[test]
+    fn parse_build_log_unknown() {
+        assert!(parse_build_log("  \n").is_empty());
+
+        let diags = parse_build_log("Something odd happened.");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].severity, DiagnosticSeverity::Unknown);
+        assert_eq!(diags[0].message, "Something odd happened.");
+    }
+}
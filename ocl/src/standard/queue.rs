@@ -1,11 +1,14 @@
 //! An `OpenCL` command queue.
 
 use std;
+use std::cell::RefCell;
 use std::ops::{Deref, DerefMut};
+use crate::ffi::cl_command_queue;
 use crate::core::{self, Result as OclCoreResult, CommandQueue as CommandQueueCore, CommandQueueInfo,
-    CommandQueueInfoResult, OpenclVersion, CommandQueueProperties, ClWaitListPtr, ClContextPtr};
+    CommandQueueInfoResult, OpenclVersion, CommandQueueProperties, ClWaitListPtr, ClContextPtr,
+    AsMem, OclPrm, MemMigrationFlags};
 use crate::error::{Error as OclError, Result as OclResult};
-use crate::standard::{Context, Device, Event};
+use crate::standard::{Context, Device, Event, ClWaitListPtrEnum};
 
 /// A command queue which manages all actions taken on kernels, buffers, and
 /// images.
@@ -35,6 +38,46 @@ impl Queue {
         })
     }
 
+    /// Returns a new on-device queue (`CL_QUEUE_ON_DEVICE`) on `device`.
+    ///
+    /// On-device queues are used by kernels to enqueue child kernels
+    /// (`enqueue_kernel`) and are passed to a kernel as a `queue_t` argument
+    /// using `KernelBuilder::arg_queue` or `Kernel::set_arg_queue`. They
+    /// cannot be used to enqueue commands from the host.
+    ///
+    /// `properties` is combined with the `ON_DEVICE` and
+    /// `OUT_OF_ORDER_EXEC_MODE_ENABLE` flags (both required). If `queue_size`
+    /// (in bytes) is `None`, the device's preferred size is used.
+    ///
+    /// Returns an error if the device does not support OpenCL 2.0.
+    #[cfg(feature = "opencl_version_2_0")]
+    pub fn new_on_device(context: &Context, device: Device,
+            properties: Option<CommandQueueProperties>, queue_size: Option<u32>)
+            -> OclResult<Queue> {
+        let device_version = device.version()?;
+        let properties = properties.unwrap_or_default() | CommandQueueProperties::ON_DEVICE |
+            CommandQueueProperties::OUT_OF_ORDER_EXEC_MODE_ENABLE;
+
+        let obj_core = core::create_command_queue_with_properties(context, &device,
+            Some(properties), queue_size, Some(&device_version))?;
+
+        Ok(Queue {
+            obj_core,
+            device_version,
+        })
+    }
+
+    /// Makes this on-device queue (see `::new_on_device`) the default
+    /// on-device queue of its device, used by kernels calling
+    /// `get_default_queue`.
+    ///
+    /// Returns an error if the device does not support OpenCL 2.1.
+    #[cfg(feature = "opencl_version_2_1")]
+    pub fn set_default_device_queue(&self) -> OclResult<()> {
+        core::set_default_device_command_queue(&self.context(), &self.device(), &self.obj_core,
+            Some(&self.device_version)).map_err(OclError::from)
+    }
+
     /// Issues all previously queued OpenCL commands to the device.
     pub fn flush(&self) -> OclResult<()> {
         core::flush(&self.obj_core).map_err(OclError::from)
@@ -47,6 +90,43 @@ impl Queue {
 
     /// Enqueues a marker command which waits for either a list of events to
     /// complete, or all previously enqueued commands to complete.
+    ///
+    /// ### Markers and Barriers
+    ///
+    /// Both commands complete once the commands (or events) they wait on
+    /// have completed, so the returned event of either can be used as a
+    /// handle for "everything submitted so far". The difference is in how
+    /// they affect commands enqueued *after* them:
+    ///
+    /// * A marker does not affect subsequent commands. On an out-of-order
+    ///   queue they may run before the marker completes.
+    /// * A barrier (`::enqueue_barrier`) prevents every subsequent command
+    ///   from starting until it completes.
+    ///
+    /// On an in-order queue the two are equivalent. Neither blocks the host;
+    /// wait on the returned event to do so. See `::marker` for a shorthand
+    /// which waits on all previously enqueued commands.
+    ///
+    /// ### Partial Results
+    ///
+    /// A marker enqueued on a *second* queue (within the same context) can be
+    /// used to order a read of intermediate results with respect to other
+    /// commands without waiting for a long-running kernel on the first queue
+    /// to complete:
+    ///
+    /// ```rust,ignore
+    /// unsafe { kernel.cmd().queue(&compute_queue).enq()?; }
+    /// // ... later, while the kernel may still be running:
+    /// let marker = snapshot_queue.enqueue_marker(Some(&progress_events))?;
+    /// let _ = accum_buffer.read_into_wait(&mut snapshot, &[marker])?;
+    /// ```
+    ///
+    /// Note that OpenCL makes no guarantee about the visibility (or
+    /// consistency) of memory being written by a kernel which has not yet
+    /// completed. Whether a snapshot taken this way reflects any progress is
+    /// device and driver dependent.
+    ///
+    /// [Version Controlled: OpenCL 1.2+]
     pub fn enqueue_marker<Ewl>(&self, ewait: Option<Ewl>) -> OclResult<Event>
             where Ewl: ClWaitListPtr
     {
@@ -56,6 +136,120 @@ impl Queue {
             .map_err(OclError::from)
     }
 
+    /// Enqueues a copy of `len` elements from `src` to `dst` and returns the
+    /// event of the copy command.
+    ///
+    /// Either pointer may be a shared virtual memory (SVM) pointer or an
+    /// ordinary host pointer. The command does not block. Wait on the
+    /// returned event (or pass it to other commands) before using `dst`.
+    ///
+    /// Returns an error if the device does not support SVM.
+    ///
+    /// ## Safety
+    ///
+    /// Both regions must be valid for `len` elements, must not overlap, and
+    /// must remain valid until the command completes.
+    ///
+    /// [Version Controlled: OpenCL 2.0+]
+    #[cfg(feature = "opencl_version_2_0")]
+    pub unsafe fn svm_memcpy<T, Ewl>(&self, dst: *mut T, src: *const T, len: usize,
+            ewait: Option<Ewl>) -> OclResult<Event>
+            where T: OclPrm, Ewl: ClWaitListPtr
+    {
+        self.verify_svm("Queue::svm_memcpy")?;
+        let mut event = Event::empty();
+        core::enqueue_svm_memcpy(&self.obj_core, false, dst, src, len, ewait,
+            Some(&mut event), Some(&self.device_version))?;
+        Ok(event)
+    }
+
+    /// Enqueues a command filling `len` elements of the SVM region beginning
+    /// at `svm_ptr` with `pattern` and returns the event of the fill command.
+    ///
+    /// Returns an error if the device does not support SVM.
+    ///
+    /// ## Safety
+    ///
+    /// `svm_ptr` must point to an SVM allocation, aligned to the size of `T`,
+    /// which remains valid for `len` elements until the command completes.
+    ///
+    /// [Version Controlled: OpenCL 2.0+]
+    #[cfg(feature = "opencl_version_2_0")]
+    pub unsafe fn svm_memfill<T, Ewl>(&self, svm_ptr: *mut T, pattern: T, len: usize,
+            ewait: Option<Ewl>) -> OclResult<Event>
+            where T: OclPrm, Ewl: ClWaitListPtr
+    {
+        self.verify_svm("Queue::svm_memfill")?;
+        let mut event = Event::empty();
+        core::enqueue_svm_mem_fill(&self.obj_core, svm_ptr, pattern, len, ewait,
+            Some(&mut event), Some(&self.device_version))?;
+        Ok(event)
+    }
+
+    /// Returns an error, mentioning `caller`, if the device of this queue
+    /// does not support shared virtual memory.
+    #[cfg(feature = "opencl_version_2_0")]
+    fn verify_svm(&self, caller: &str) -> OclResult<()> {
+        let device = self.device();
+        if device.supports_svm()? {
+            Ok(())
+        } else {
+            Err(format!("{}: The device ('{}') does not support shared virtual memory.", caller,
+                device.name().unwrap_or_else(|_| "<unknown>".into())).into())
+        }
+    }
+
+    /// Enqueues a marker command which completes once all previously
+    /// enqueued commands have completed and returns its event.
+    ///
+    /// Subsequently enqueued commands are not held back (see the markers and
+    /// barriers section of `::enqueue_marker`).
+    ///
+    /// [Version Controlled: OpenCL 1.2+]
+    pub fn marker(&self) -> OclResult<Event> {
+        self.enqueue_marker(None::<&Event>)
+    }
+
+    /// Enqueues a barrier command which waits for either a list of events to
+    /// complete, or all previously enqueued commands to complete, before any
+    /// subsequently enqueued commands may begin.
+    ///
+    /// Unlike a marker, a barrier blocks execution of later commands, which
+    /// is useful for ordering commands on an out-of-order queue.
+    ///
+    /// [Version Controlled: OpenCL 1.2+]
+    pub fn enqueue_barrier<Ewl>(&self, ewait: Option<Ewl>) -> OclResult<Event>
+            where Ewl: ClWaitListPtr
+    {
+        let mut barrier_event = Event::empty();
+        core::enqueue_barrier_with_wait_list(&self.obj_core, ewait, Some(&mut barrier_event),
+                Some(&self.device_version)).map(|_| barrier_event)
+            .map_err(OclError::from)
+    }
+
+    /// Enqueues a command which migrates a list of memory objects (buffers or
+    /// images) to the device associated with this queue (or to the host if
+    /// `flags` contains `MIGRATE_MEM_OBJECT_HOST`) after every event in
+    /// `ewait` has completed.
+    ///
+    /// Migrating explicitly ahead of time avoids an implicit transfer when a
+    /// memory object is first used on another device in the same context.
+    /// The returned event can be used to overlap the migration with other
+    /// work.
+    ///
+    /// [Version Controlled: OpenCL 1.2+]
+    pub fn migrate<T, M>(&self, mems: &[&M], flags: MemMigrationFlags, ewait: &[Event])
+            -> OclResult<Event>
+            where T: OclPrm, M: AsMem<T>
+    {
+        let mems: Vec<_> = mems.iter().map(|m| m.as_mem().clone()).collect();
+        let ewait = if ewait.is_empty() { None } else { Some(ClWaitListPtrEnum::from(ewait)) };
+        let mut migrate_event = Event::empty();
+        core::enqueue_migrate_mem_objects(&self.obj_core, &mems, flags, ewait,
+                Some(&mut migrate_event), Some(&self.device_version)).map(|_| migrate_event)
+            .map_err(OclError::from)
+    }
+
     /// Returns a reference to the core pointer wrapper, usable by functions in
     /// the `core` module.
     #[inline]
@@ -63,6 +257,49 @@ impl Queue {
         &self.obj_core
     }
 
+    /// Returns a `Queue` wrapping a raw `cl_command_queue` created elsewhere.
+    ///
+    /// Every `Queue` (and every clone of one) owns exactly one reference to
+    /// the underlying command queue and releases it when dropped.
+    ///
+    /// ## Ownership
+    ///
+    /// * `retain: true`: The reference count is incremented before wrapping.
+    ///   The caller keeps its own reference and remains responsible for
+    ///   releasing it.
+    /// * `retain: false`: The caller's reference is transferred to the
+    ///   returned `Queue`. The caller must **not** release `ptr` afterwards.
+    ///
+    /// ## Safety
+    ///
+    /// `ptr` must be a valid `cl_command_queue`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `ptr` is null.
+    pub unsafe fn from_raw(ptr: cl_command_queue, retain: bool) -> OclResult<Queue> {
+        let obj_core = if retain {
+            CommandQueueCore::from_raw_copied_ptr(ptr)
+        } else {
+            CommandQueueCore::from_raw_create_ptr(ptr)
+        };
+        let device_version = Device::from(obj_core.device()?).version()?;
+
+        Ok(Queue {
+            obj_core,
+            device_version,
+        })
+    }
+
+    /// Returns the raw `cl_command_queue` pointer.
+    ///
+    /// The pointer is owned by this `Queue` and is only valid for as long as
+    /// it (or a clone) exists. Do not release it.
+    #[inline]
+    pub fn as_ptr(&self) -> cl_command_queue {
+        self.obj_core.as_ptr()
+    }
+
     /// Returns a copy of the Context associated with this queue.
     pub fn context(&self) -> Context {
         self.obj_core.context().map(Context::from).unwrap()
@@ -78,11 +315,30 @@ impl Queue {
         self.device_version
     }
 
+    /// Returns the default queue of the current thread if this is called
+    /// from within a closure passed to `ocl::with_default`.
+    pub fn scoped_default() -> Option<Queue> {
+        SCOPED_DEFAULT_QUEUE.with(|def| def.borrow().clone())
+    }
+
     /// Returns info about this queue.
     pub fn info(&self, info_kind: CommandQueueInfo) -> OclCoreResult<CommandQueueInfoResult> {
         core::get_command_queue_info(&self.obj_core, info_kind)
     }
 
+    /// Returns the properties this queue was created with.
+    ///
+    /// Useful for checking whether the queue was created with, for example,
+    /// `CommandQueueProperties::PROFILING_ENABLE` (required for
+    /// `Event::profiling_info`) or
+    /// `CommandQueueProperties::OUT_OF_ORDER_EXEC_MODE_ENABLE`.
+    pub fn properties(&self) -> OclResult<CommandQueueProperties> {
+        match self.info(CommandQueueInfo::Properties)? {
+            CommandQueueInfoResult::Properties(props) => Ok(props),
+            _ => unreachable!(),
+        }
+    }
+
     fn fmt_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.debug_struct("Queue")
             .field("Context", &self.info(CommandQueueInfo::Context))
@@ -131,3 +387,41 @@ unsafe impl<'a> ClContextPtr for &'a Queue {
             Unable to obtain a context pointer.")
     }
 }
+
+
+thread_local! {
+    static SCOPED_DEFAULT_QUEUE: RefCell<Option<Queue>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous scoped default queue when dropped (including
+/// during a panic).
+struct ScopedDefaultReset(Option<Queue>);
+
+impl Drop for ScopedDefaultReset {
+    fn drop(&mut self) {
+        let prev = self.0.take();
+        SCOPED_DEFAULT_QUEUE.with(|def| *def.borrow_mut() = prev);
+    }
+}
+
+/// Calls `f` with `queue` set as the default queue of the current thread,
+/// restoring the previous default (if any) afterwards.
+///
+/// Within `f`, builders which would otherwise require a queue or context
+/// fall back to the scoped default: `BufferBuilder::build` uses it when
+/// neither `::queue` nor `::context` has been called and
+/// `KernelBuilder::build` uses it as the default queue of the kernel when
+/// `::queue` has not been called. Calls may be nested.
+///
+/// ### Example
+/// ```rust,ignore
+/// ocl::with_default(&queue, || {
+///     let buffer = Buffer::<f32>::builder().len(1024).build()?;
+///     // ...
+/// })
+/// ```
+pub fn with_default<F, R>(queue: &Queue, f: F) -> R where F: FnOnce() -> R {
+    let prev = SCOPED_DEFAULT_QUEUE.with(|def| def.borrow_mut().replace(queue.clone()));
+    let _reset = ScopedDefaultReset(prev);
+    f()
+}
@@ -4,7 +4,7 @@ use std::fmt::Debug;
 use std::ops::Index;
 use num_traits::{Num, ToPrimitive};
 use crate::error::{Result as OclResult};
-use crate::standard::{MemLen, WorkDims};
+use crate::standard::{MemLen, WorkDims, Device};
 use crate::core::util;
 
 
@@ -58,7 +58,7 @@ impl SpatialDims {
             }
         } else if d1.is_some() {
             if d0.is_some() {
-                Ok(SpatialDims::Two(d1.unwrap(), d0.unwrap()))
+                Ok(SpatialDims::Two(d0.unwrap(), d1.unwrap()))
             } else {
                 Err(std_err_msg.into())
             }
@@ -69,6 +69,81 @@ impl SpatialDims {
         }
     }
 
+    /// Returns a new three dimensional `SpatialDims` with all dimensions
+    /// equal to `n` (`(n, n, n)`).
+    pub fn cube(n: usize) -> SpatialDims {
+        SpatialDims::Three(n, n, n)
+    }
+
+    /// Returns a new three dimensional `SpatialDims` from `[d0, d1, d2]`.
+    pub fn from_dims(dims: [usize; 3]) -> SpatialDims {
+        SpatialDims::Three(dims[0], dims[1], dims[2])
+    }
+
+    /// Returns a one dimensional global and local work size, as
+    /// `(gws, lws)`, for processing `n` elements in work groups of `local`
+    /// work-items.
+    ///
+    /// The global work size is `n` rounded up to the nearest multiple of
+    /// `local` so that every work group is full. Up to `local - 1` trailing
+    /// work-items therefore lie beyond the data and the kernel is expected
+    /// to bounds-check against `n`:
+    ///
+    /// ```c
+    /// if (get_global_id(0) >= n) return;
+    /// ```
+    ///
+    /// Returns an error if `n` or `local` is zero.
+    ///
+    pub fn for_elements(n: usize, local: usize) -> OclResult<(SpatialDims, SpatialDims)> {
+        if n == 0 || local == 0 {
+            return Err(format!("SpatialDims::for_elements: The element count ({}) and local \
+                size ({}) must both be non-zero.", n, local).into());
+        }
+        Ok((SpatialDims::One(util::padded_len(n, local)), SpatialDims::One(local)))
+    }
+
+    /// Returns the global work offset which continues where a previous
+    /// dispatch, of size `prev_gws` and offset `prev_gwo`, left off.
+    ///
+    /// The offset is advanced along the highest dimension of `prev_gws`
+    /// (`d0` for one, `d1` for two, and `d2` for three dimensions), the
+    /// remaining dimensions are kept as they were in `prev_gwo`. This allows
+    /// a large grid to be processed as a chain of partial grids (strips or
+    /// slabs) with each being enqueued using the `::global_work_offset`
+    /// builder method.
+    ///
+    /// An unspecified `prev_gwo` is treated as all zeros. Returns an error if
+    /// `prev_gws` is unspecified or if `prev_gwo` has more dimensions than
+    /// `prev_gws`.
+    ///
+    pub fn offset_after<Dgws, Dgwo>(prev_gws: Dgws, prev_gwo: Dgwo) -> OclResult<SpatialDims>
+            where Dgws: Into<SpatialDims>, Dgwo: Into<SpatialDims> {
+        let (gws, gwo) = (prev_gws.into(), prev_gwo.into());
+        let dim_count = gws.dim_count();
+
+        let lens = match gws.to_lens() {
+            Ok(lens) => lens,
+            Err(_) => return Err("SpatialDims::offset_after: The previous global work size \
+                must be specified.".into()),
+        };
+        if gwo.dim_count() > dim_count {
+            return Err(format!("SpatialDims::offset_after: The previous global work offset \
+                ({:?}) has more dimensions than the previous global work size ({:?}).",
+                gwo, gws).into());
+        }
+
+        let mut offset = gwo.to_offset().unwrap_or([0, 0, 0]);
+        let dim_idx = dim_count as usize - 1;
+        offset[dim_idx] += lens[dim_idx];
+
+        match dim_count {
+            1 => Ok(SpatialDims::One(offset[0])),
+            2 => Ok(SpatialDims::Two(offset[0], offset[1])),
+            _ => Ok(SpatialDims::Three(offset[0], offset[1], offset[2])),
+        }
+    }
+
     /// Returns the number of dimensions defined by this `SpatialDims`.
     pub fn dim_count(&self) -> u32 {
         match *self {
@@ -121,6 +196,72 @@ impl SpatialDims {
         Ok(util::padded_len(self.to_len(), incr))
     }
 
+    /// Returns an error if any specified dimension is zero.
+    ///
+    /// A zero dimension, when used as a work size, results in zero
+    /// work-items which is almost never intended and is rejected by OpenCL
+    /// with a less than helpful error. An `Unspecified` `SpatialDims` is
+    /// valid.
+    pub fn validate(&self) -> OclResult<()> {
+        let has_zero = match *self {
+            SpatialDims::Unspecified => false,
+            SpatialDims::One(d0) => d0 == 0,
+            SpatialDims::Two(d0, d1) => d0 == 0 || d1 == 0,
+            SpatialDims::Three(d0, d1, d2) => d0 == 0 || d1 == 0 || d2 == 0,
+        };
+
+        if has_zero {
+            Err(format!("Invalid dimensions: {:?}. Dimensions may not be zero.", self).into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns `dims` as a `SpatialDims` after verifying that it is usable as
+    /// a local work size on `device`.
+    ///
+    /// Each dimension must be non-zero and no larger than the corresponding
+    /// entry of `CL_DEVICE_MAX_WORK_ITEM_SIZES` and the product of all
+    /// dimensions (the work-group size) must not exceed
+    /// `CL_DEVICE_MAX_WORK_GROUP_SIZE`. This catches oversized work groups
+    /// where they are defined (useful when generating many candidate sizes,
+    /// e.g. while autotuning) rather than when the kernel is enqueued.
+    ///
+    /// Note that a kernel may have a lower limit than the device (see
+    /// `Kernel::wg_info`). `dims` must be specified.
+    pub fn checked<D: Into<SpatialDims>>(dims: D, device: &Device) -> OclResult<SpatialDims> {
+        let dims = dims.into();
+        dims.check_local_limits(&device.max_work_item_sizes()?, device.max_wg_size()?)?;
+        Ok(dims)
+    }
+
+    /// Returns an error if this `SpatialDims` is unspecified, contains a zero
+    /// dimension, or exceeds the specified work-item or work-group limits.
+    fn check_local_limits(&self, max_wi_sizes: &[usize], max_wg_size: usize) -> OclResult<()> {
+        let lens = match self.to_work_size() {
+            Some(lens) => lens,
+            None => return Err("SpatialDims::checked: The local work size must be \
+                specified.".into()),
+        };
+        self.validate()?;
+
+        for (dim_idx, (&len, &max)) in lens.iter().zip(max_wi_sizes.iter())
+                .take(self.dim_count() as usize).enumerate() {
+            if len > max {
+                return Err(format!("SpatialDims::checked: Dimension {} of the local work \
+                    size ({:?}) exceeds the maximum work-item size for that dimension ({}).",
+                    dim_idx, self, max).into());
+            }
+        }
+
+        if self.to_len() > max_wg_size {
+            return Err(format!("SpatialDims::checked: The local work size ({:?}) contains {} \
+                work-items which exceeds the maximum work-group size ({}).", self, self.to_len(),
+                max_wg_size).into());
+        }
+        Ok(())
+    }
+
     /// Returns `true` if this `SpatialDims` is an `Unspecified` variant.
     pub fn is_unspecified(&self) -> bool {
         if let SpatialDims::Unspecified = *self { true } else { false }
@@ -332,3 +473,86 @@ pub fn to_usize<T: Num + ToPrimitive + Debug + Copy>(val: T) -> usize {
         Dimensions must have positive values.", val))
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::SpatialDims;
+    use crate::standard::WorkDims;
+
+    #[test]
+    fn three_dims_round_trip() {
+        let dims = SpatialDims::from_dims([4, 5, 6]);
+        assert_eq!(dims, SpatialDims::from([4, 5, 6]));
+        assert_eq!(dims.dim_count(), 3);
+        assert_eq!(dims.to_len(), 4 * 5 * 6);
+        assert_eq!(dims.to_work_size(), Some([4, 5, 6]));
+        assert_eq!(dims.to_work_offset(), Some([4, 5, 6]));
+        assert_eq!((dims[0], dims[1], dims[2]), (4, 5, 6));
+
+        let cube = SpatialDims::cube(8);
+        assert_eq!(cube, SpatialDims::Three(8, 8, 8));
+        assert_eq!(cube.to_len(), 512);
+
+        // Lower dimensions are padded with ones (sizes) or zeros (offsets):
+        assert_eq!(SpatialDims::from((3, 2)).to_work_size(), Some([3, 2, 1]));
+        assert_eq!(SpatialDims::from((3, 2)).to_work_offset(), Some([3, 2, 0]));
+        assert_eq!(SpatialDims::Unspecified.to_work_offset(), None);
+
+        assert_eq!(SpatialDims::new(Some(3), Some(2), None).unwrap(), SpatialDims::Two(3, 2));
+        assert_eq!(SpatialDims::new(Some(3), Some(2), Some(1)).unwrap(),
+            SpatialDims::Three(3, 2, 1));
+        assert!(SpatialDims::new(None, Some(2), None).is_err());
+    }
+
+    #[test]
+    fn validate() {
+        assert!(SpatialDims::Unspecified.validate().is_ok());
+        assert!(SpatialDims::Three(4, 2, 1).validate().is_ok());
+        assert!(SpatialDims::One(0).validate().is_err());
+        assert!(SpatialDims::Two(8, 0).validate().is_err());
+        assert!(SpatialDims::Three(8, 8, 0).validate().is_err());
+    }
+
+    #[test]
+    fn for_elements() {
+        assert_eq!(SpatialDims::for_elements(1000, 256).unwrap(),
+            (SpatialDims::One(1024), SpatialDims::One(256)));
+        assert_eq!(SpatialDims::for_elements(1024, 256).unwrap(),
+            (SpatialDims::One(1024), SpatialDims::One(256)));
+        assert_eq!(SpatialDims::for_elements(1, 64).unwrap(),
+            (SpatialDims::One(64), SpatialDims::One(64)));
+        assert!(SpatialDims::for_elements(0, 64).is_err());
+        assert!(SpatialDims::for_elements(64, 0).is_err());
+    }
+
+    #[test]
+    fn offset_after() {
+        assert_eq!(SpatialDims::offset_after(256, SpatialDims::Unspecified).unwrap(),
+            SpatialDims::One(256));
+        assert_eq!(SpatialDims::offset_after(256, 512).unwrap(), SpatialDims::One(768));
+        assert_eq!(SpatialDims::offset_after((64, 16), (0, 32)).unwrap(),
+            SpatialDims::Two(0, 48));
+        assert_eq!(SpatialDims::offset_after((8, 8, 4), SpatialDims::Unspecified).unwrap(),
+            SpatialDims::Three(0, 0, 4));
+        assert!(SpatialDims::offset_after(SpatialDims::Unspecified, 0usize).is_err());
+        assert!(SpatialDims::offset_after(64, (0, 0)).is_err());
+    }
+
+    #[test]
+    fn check_local_limits() {
+        let limits = |dims: SpatialDims| dims.check_local_limits(&[1024, 1024, 64], 256);
+
+        assert!(limits(SpatialDims::One(256)).is_ok());
+        assert!(limits(SpatialDims::Two(16, 16)).is_ok());
+        assert!(limits(SpatialDims::Three(4, 4, 16)).is_ok());
+
+        // Work-group size:
+        assert!(limits(SpatialDims::One(512)).is_err());
+        assert!(limits(SpatialDims::Two(32, 16)).is_err());
+        // Work-item size:
+        assert!(limits(SpatialDims::Three(1, 1, 128)).is_err());
+        // Zero and unspecified:
+        assert!(limits(SpatialDims::Two(16, 0)).is_err());
+        assert!(limits(SpatialDims::Unspecified).is_err());
+    }
+}
@@ -556,4 +556,189 @@ pub fn rw_vec() {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[test]
+pub fn rw_vec_try_lock() {
+    let rw_vec: RwVec<i32> = RwVec::from(vec![0; 16]);
+
+    {
+        let mut guard = rw_vec.try_lock().expect("lock should be uncontended");
+        guard[0] = 5;
+        assert!(rw_vec.try_lock().is_none());
+        assert!(rw_vec.try_read().is_none());
+    }
+
+    let read_guard = rw_vec.try_read().expect("lock should be released");
+    assert_eq!(read_guard[0], 5);
+    assert!(rw_vec.try_read().is_some());
+    assert!(rw_vec.try_lock().is_none());
+    drop(read_guard);
+
+    assert!(rw_vec.try_lock().is_some());
+}
+
+#[test]
+fn rw_vec_into_data_future() {
+    // Sole owner: the `Vec` is moved out and an empty one is left behind.
+    let rw_vec = RwVec::from(vec![1i32; 8]);
+    let data_ptr = rw_vec.try_read().unwrap().as_ptr();
+    let data = rw_vec.read().into_data_future().wait().unwrap();
+    assert_eq!(data, vec![1; 8]);
+    assert_eq!(data.as_ptr(), data_ptr);
+
+    // Shared: the `Vec` is cloned and the lock is released afterwards.
+    let rw_vec = RwVec::from(vec![2i32; 8]);
+    let data = rw_vec.clone().write().into_data_future().wait().unwrap();
+    assert_eq!(data, vec![2; 8]);
+    assert_eq!(rw_vec.try_read().expect("lock should be released").len(), 8);
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn rw_vec_as_array2() {
+    use crate::{ReadGuard, WriteGuard};
+
+    let rw_vec = RwVec::from(vec![0i32; 6]);
+
+    let mut guard = rw_vec.clone().write().wait().unwrap();
+    WriteGuard::as_array2_mut(&mut guard, 2, 3).unwrap()[[1, 2]] = 5;
+    assert_eq!(guard[5], 5);
+    assert!(WriteGuard::as_array2(&guard, 4, 2).is_err());
+    WriteGuard::release(guard);
+
+    let guard = rw_vec.read().wait().unwrap();
+    assert_eq!(ReadGuard::as_array2(&guard, 3, 2).unwrap()[[2, 1]], 5);
+}
+
+#[test]
+fn guard_set_release_complete() {
+    use crate::WriteGuard;
+
+    let context = Context::builder().build().unwrap();
+    let rw_vec = RwVec::from(vec![0i32; 16]);
+
+    let mut future_guard = rw_vec.write();
+    let release_event = future_guard.create_release_event(&context).unwrap().clone();
+    let mut guard = future_guard.wait().unwrap();
+    guard[0] = 1;
+
+    assert!(!release_event.is_complete().unwrap());
+    WriteGuard::set_release_complete(&guard).unwrap();
+    assert!(release_event.is_complete().unwrap());
+
+    // Completing again (including upon drop) is a no-op:
+    WriteGuard::set_release_complete(&guard).unwrap();
+    drop(guard);
+}
+
+#[test]
+fn guard_sync_to_buffer() {
+    use crate::{ProQue, WriteGuard};
+
+    let pro_que = ProQue::builder()
+        .src("__kernel void add(__global int* buf) { buf[get_global_id(0)] += 1; }")
+        .dims(64)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<i32>().unwrap();
+    let kernel = pro_que.kernel_builder("add").arg(&buffer).build().unwrap();
+    let rw_vec = RwVec::from(vec![0i32; 64]);
+
+    let mut guard = rw_vec.clone().write().wait().unwrap();
+    for (i, val) in guard.iter_mut().enumerate() { *val = i as i32; }
+    WriteGuard::sync(&guard, &buffer).unwrap();
+    drop(guard);
+
+    unsafe { kernel.enq().unwrap(); }
+    let expected: Vec<i32> = (1..65).collect();
+    buffer.assert_eq(&expected);
+}
+
+#[test]
+fn user_event_gates_kernel() {
+    use crate::core::CommandExecutionStatus;
+    use crate::ProQue;
+
+    let pro_que = ProQue::builder()
+        .src("__kernel void add(__global int* buf) { buf[get_global_id(0)] += 1; }")
+        .dims(64)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<i32>().unwrap();
+    let kernel = pro_que.kernel_builder("add").arg(&buffer).build().unwrap();
+
+    let gate = Event::user(pro_que.context()).unwrap();
+    let mut kernel_event = Event::empty();
+    unsafe { kernel.cmd().ewait(&gate).enew(&mut kernel_event).enq().unwrap(); }
+    pro_que.queue().flush().unwrap();
+
+    assert!(!kernel_event.is_complete().unwrap());
+    gate.set_status(CommandExecutionStatus::Complete).unwrap();
+    assert!(gate.set_status(CommandExecutionStatus::Complete).is_err());
+
+    kernel_event.wait_for().unwrap();
+    let mut vec = vec![0i32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 1));
+}
+
+/// Ensures that a kernel waiting on the release event of a guard runs only
+/// once the host is done with the guarded data.
+#[test]
+fn guard_release_event_gates_kernel() {
+    use crate::ProQue;
+
+    let pro_que = ProQue::builder()
+        .src("__kernel void add(__global int* buf) { buf[get_global_id(0)] += 1; }")
+        .dims(64)
+        .build().unwrap();
+
+    let buffer = pro_que.buffer_builder::<i32>().fill_val(0).build().unwrap();
+    let kernel = pro_que.kernel_builder("add").arg(&buffer).build().unwrap();
+    let rw_vec = RwVec::from(vec![0i32; buffer.len()]);
+
+    let mut future_guard = rw_vec.write();
+    let release_event = future_guard.create_release_event(pro_que.context()).unwrap().clone();
+
+    let mut kernel_event = Event::empty();
+    unsafe { kernel.cmd().ewait(&release_event).enew(&mut kernel_event).enq().unwrap(); }
+    pro_que.queue().flush().unwrap();
+
+    let mut guard = future_guard.wait().unwrap();
+    assert!(!kernel_event.is_complete().unwrap());
+    for ele in guard.iter_mut() { *ele = 7; }
+    drop(guard);
+
+    kernel_event.wait_for().unwrap();
+    buffer.assert_eq(&vec![1i32; buffer.len()]);
+}
+
+#[test]
+fn buffer_map_async() {
+    use crate::ProQue;
+    use crate::flags::MapFlags;
+
+    let pro_que = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims(64)
+        .build().unwrap();
+
+    let buffer = pro_que.buffer_builder::<i32>()
+        .flags(MemFlags::new().read_write().alloc_host_ptr())
+        .fill_val(7)
+        .build().unwrap();
+
+    let (future_map, map_event) = unsafe {
+        buffer.map_async(MapFlags::new().read(), 16, 32).unwrap()
+    };
+    assert!(!map_event.is_empty());
+
+    let mut mem_map = future_map.wait().unwrap();
+    assert!(map_event.is_complete().unwrap());
+    assert_eq!(mem_map.len(), 32);
+    assert!(mem_map.iter().all(|&v| v == 7));
+    mem_map.unmap().enq().unwrap();
+
+    assert!(unsafe { buffer.map_async(MapFlags::new().read(), 48, 32) }.is_err());
+}
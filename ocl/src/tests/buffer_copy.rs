@@ -123,3 +123,60 @@ fn buffer_copy_standard() {
         }
     }
 }
+
+#[test]
+fn buffer_from_data() {
+    use crate::standard::{Buffer, ProQue};
+
+    let pro_que = ProQue::builder()
+        .src("__kernel void noop() {}")
+        .dims(4)
+        .build().unwrap();
+
+    let host_vec = vec![1i32, 2, 3, 4, 5, 6];
+
+    Buffer::from_data(pro_que.queue(), [1i32, 2, 3, 4]).unwrap().assert_eq(&[1, 2, 3, 4]);
+    Buffer::from_data(pro_que.queue(), host_vec.clone()).unwrap().assert_eq(&host_vec);
+    Buffer::from_data(pro_que.queue(), &host_vec[2..5]).unwrap().assert_eq(&[3, 4, 5]);
+    assert!(Buffer::<i32>::from_data(pro_que.context(), Vec::new()).is_err());
+}
+
+#[test]
+fn buffer_read_all() {
+    use crate::standard::{Buffer, ProQue};
+
+    let pro_que = ProQue::builder()
+        .src("__kernel void noop() {}")
+        .dims(4)
+        .build().unwrap();
+
+    let host_vec: Vec<i32> = (0..1000).collect();
+    let buffer = Buffer::from_data(pro_que.queue(), &host_vec[..]).unwrap();
+    assert_eq!(buffer.read_all().unwrap(), host_vec);
+}
+
+#[test]
+fn buffer_from_raw_len() {
+    use crate::standard::{Buffer, ProQue};
+
+    let pro_que = ProQue::builder()
+        .src("__kernel void noop() {}")
+        .dims(4)
+        .build().unwrap();
+
+    let host_vec: Vec<i32> = (0..64).collect();
+    let owner = Buffer::from_data(pro_que.queue(), &host_vec[..]).unwrap();
+
+    let adopted = unsafe {
+        Buffer::<i32>::from_raw(owner.as_ptr(), true, Some(pro_que.queue().clone()), Some(16))
+    }.unwrap();
+    assert_eq!(adopted.len(), 16);
+    assert_eq!(adopted.read_all().unwrap(), &host_vec[..16]);
+
+    assert!(unsafe {
+        Buffer::<i32>::from_raw(owner.as_ptr(), true, None, Some(65))
+    }.is_err());
+
+    drop(adopted);
+    assert_eq!(owner.read_all().unwrap(), host_vec);
+}
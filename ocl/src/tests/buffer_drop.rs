@@ -0,0 +1,66 @@
+//! Ensures that buffers release their memory objects when dropped.
+
+use crate::core::{MemInfo, MemInfoResult};
+use crate::standard::{ProQue, Buffer};
+
+const DATASET_SIZE: usize = 1 << 18;
+const ITERS: usize = 4096;
+
+fn ref_count<T: crate::OclPrm>(buffer: &Buffer<T>) -> u32 {
+    match buffer.mem_info(MemInfo::ReferenceCount).unwrap() {
+        MemInfoResult::ReferenceCount(c) => c,
+        _ => unreachable!(),
+    }
+}
+
+/// Allocates and drops many large buffers. Without the memory object being
+/// released on drop this would exhaust device memory (1GiB per 1024
+/// iterations).
+#[test]
+fn buffer_drop() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims(DATASET_SIZE)
+        .build().unwrap();
+
+    for _ in 0..ITERS {
+        let buffer = pro_que.create_buffer::<f32>().unwrap();
+        buffer.cmd().fill(1.0f32, None).enq().unwrap();
+    }
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    assert_eq!(ref_count(&buffer), 1);
+
+    {
+        let clones: Vec<_> = (0..8).map(|_| buffer.clone()).collect();
+        assert_eq!(ref_count(&buffer), 9);
+        drop(clones);
+    }
+
+    assert_eq!(ref_count(&buffer), 1);
+}
+
+/// Ensures that a `WeakBuffer` does not keep its memory object alive and can
+/// only be upgraded while a `Buffer` still refers to it.
+#[test]
+fn weak_buffer() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims(DATASET_SIZE)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    buffer.cmd().fill(5.0f32, None).enq().unwrap();
+    let weak = buffer.downgrade();
+    assert_eq!(ref_count(&buffer), 2);
+
+    let upgraded = weak.upgrade().unwrap();
+    assert_eq!(ref_count(&buffer), 3);
+    assert_eq!(upgraded.len(), buffer.len());
+    upgraded.assert_eq(&vec![5.0f32; DATASET_SIZE]);
+
+    drop(buffer);
+    assert!(weak.upgrade().is_some());
+    drop(upgraded);
+    assert!(weak.upgrade().is_none());
+}
@@ -87,3 +87,23 @@ fn fill_with_float4() {
         assert_eq!(ele, final_val);
     }
 }
+
+
+#[test]
+fn fill_assert_eq() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void noop() {}")
+        .dims(1024)
+        .build().unwrap();
+
+    let buffer = pro_que.buffer_builder::<f32>().fill_val(0.5).build().unwrap();
+    buffer.assert_eq(&vec![0.5f32; buffer.len()]);
+    buffer.assert_approx_eq(&vec![0.5001f32; buffer.len()], 0.001);
+
+    let result = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+        let mut expected = vec![0.5f32; buffer.len()];
+        expected[7] = 1.0;
+        buffer.assert_eq(&expected);
+    }));
+    assert!(result.is_err());
+}
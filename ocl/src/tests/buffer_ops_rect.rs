@@ -360,3 +360,81 @@ fn buffer_ops_rect() {
 
     println!("{} total test runs complete.\n", ttl_runs);
 }
+
+/// Ensures that `Buffer::copy_rect_to` copies a 2D tile into the correct
+/// position of a larger buffer and rejects out-of-bounds regions.
+#[test]
+fn buffer_copy_rect_to() {
+    const TILE: usize = 4;
+    const MOSAIC: usize = 8;
+    let row_pitch = |width: usize| width * mem::size_of::<f32>();
+
+    let proque = ProQue::builder()
+        .src("__kernel void noop() {}")
+        .dims(MOSAIC * MOSAIC)
+        .build().unwrap();
+
+    let tile = Buffer::<f32>::builder()
+        .queue(proque.queue().clone())
+        .len(TILE * TILE)
+        .fill_val(7.0f32)
+        .build().unwrap();
+    let mosaic = proque.buffer_builder::<f32>().fill_val(0.0f32).build().unwrap();
+
+    // Copy the tile into the bottom-right quadrant:
+    let event = tile.copy_rect_to(&mosaic, [0, 0, 0], [TILE, TILE, 0], [TILE, TILE, 1],
+        [row_pitch(TILE), 0, row_pitch(MOSAIC), 0], &[]).unwrap();
+    event.wait_for().unwrap();
+
+    let mut vec = vec![0.0f32; mosaic.len()];
+    mosaic.read(&mut vec).enq().unwrap();
+
+    for y in 0..MOSAIC {
+        for x in 0..MOSAIC {
+            let expected = if x >= TILE && y >= TILE { 7.0 } else { 0.0 };
+            assert_eq!(vec[y * MOSAIC + x], expected, "(x: {}, y: {})", x, y);
+        }
+    }
+
+    // One row past the end of the destination:
+    assert!(tile.copy_rect_to(&mosaic, [0, 0, 0], [TILE, TILE + 1, 0], [TILE, TILE, 1],
+        [row_pitch(TILE), 0, row_pitch(MOSAIC), 0], &[]).is_err());
+    // One column past the end of the source:
+    assert!(tile.copy_rect_to(&mosaic, [1, 0, 0], [0, 0, 0], [TILE, TILE, 1],
+        [row_pitch(TILE), 0, row_pitch(MOSAIC), 0], &[]).is_err());
+}
+
+#[test]
+fn buffer_rect_default_pitches() {
+    const W: usize = 8;
+    const H: usize = 4;
+
+    let proque = ProQue::builder()
+        .src("__kernel void noop() {}")
+        .dims(W * H)
+        .build().unwrap();
+
+    let buf = proque.buffer_builder::<f32>().fill_val(0.0f32).build().unwrap();
+    let src: Vec<f32> = (0..(W * H)).map(|i| i as f32).collect();
+
+    // `None` pitches lay out the region tightly packed on both sides:
+    buf.cmd().write(&src).rect([0, 0, 0], [0, 0, 0], [W, H, 1], None, None, None, None)
+        .enq().unwrap();
+    buf.assert_eq(&src);
+
+    // Read the right half back into a tightly packed half-width vec:
+    let mut half = vec![0.0f32; (W / 2) * H];
+    buf.cmd().read(&mut half).rect([W / 2, 0, 0], [0, 0, 0], [W / 2, H, 1],
+        W * mem::size_of::<f32>(), None, None, None).enq().unwrap();
+
+    for y in 0..H {
+        for x in 0..(W / 2) {
+            assert_eq!(half[y * (W / 2) + x], src[y * W + x + W / 2]);
+        }
+    }
+
+    // A row pitch smaller than one row of the region is rejected:
+    let mut vec = vec![0.0f32; W * H];
+    assert!(buf.cmd().read(&mut vec).rect([0, 0, 0], [0, 0, 0], [W, H, 1],
+        (W - 1) * mem::size_of::<f32>(), None, None, None).enq().is_err());
+}
@@ -0,0 +1,31 @@
+use futures::Future;
+use crate::{Context, Program};
+
+#[test]
+fn build_async() {
+    let context = Context::builder().build().unwrap();
+
+    let good_src = r#"
+        kernel void multiply(global float* buffer, float coeff) {
+            buffer[get_global_id(0)] *= coeff;
+        }
+    "#;
+
+    let bad_src = r#"
+        kernel void multiply(global float* buffer, float coeff) {
+            not_a_variable + im_with_not_a_variable;
+        }
+    "#;
+
+    // Begin both builds before waiting on either:
+    let good = Program::builder().src(good_src).build_async(&context).unwrap();
+    let bad = Program::builder().src(bad_src).build_async(&context);
+
+    let (program, _log) = good.wait().unwrap();
+    assert_eq!(program.kernel_names().unwrap(), vec!["multiply".to_owned()]);
+
+    // Depending on the platform, the failure is reported either immediately
+    // or once the build completes:
+    let err = bad.and_then(|bad| bad.wait()).unwrap_err();
+    assert!(!err.build_log().unwrap().is_empty());
+}
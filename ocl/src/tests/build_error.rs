@@ -1,4 +1,6 @@
 use super::super::ProQue;
+use crate::standard::{Platform, Device, Context, Program};
+use crate::core::ProgramBuildStatus;
 
 #[test]
 #[should_panic]
@@ -15,3 +17,34 @@ fn bad_kernel_variable_names() {
 
     let ocl_pq = ProQue::builder().src(kernel).build().unwrap();
 }
+
+#[test]
+fn build_statuses() {
+    let platform = Platform::default();
+    let devices = Device::list_all(platform).unwrap();
+    let context = Context::builder().platform(platform).devices(&devices[..]).build().unwrap();
+
+    let err = Program::builder()
+        .src("kernel void broken(global float* buffer) { buffer[0] = not_a_variable; }")
+        .devices(&devices[..])
+        .build(&context)
+        .unwrap_err();
+
+    // One entry per device, naming those which rejected the program:
+    let statuses = err.build_statuses().unwrap();
+    assert_eq!(statuses.len(), devices.len());
+    assert!(statuses.iter().all(|(device, _, _)| devices.contains(device)));
+    assert!(statuses.iter().any(|&(_, status, ref log)| {
+        status == ProgramBuildStatus::Error && !log.is_empty()
+    }));
+
+    let program = Program::builder()
+        .src("kernel void fine(global float* buffer) { buffer[0] = 1.0f; }")
+        .devices(&devices[..])
+        .build(&context)
+        .unwrap();
+
+    let statuses = program.build_statuses().unwrap();
+    assert_eq!(statuses.len(), devices.len());
+    assert!(statuses.iter().all(|&(_, status, _)| status == ProgramBuildStatus::Success));
+}
@@ -1,4 +1,4 @@
-use crate::standard::{ProQue, EventList};
+use crate::standard::{ProQue, EventList, Event, Context};
 
 #[test]
 fn clear_completed() {
@@ -33,3 +33,56 @@ fn clear_completed() {
     }
 }
 
+
+#[test]
+fn event_list_to_wait_list() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void noop(__global float* buffer) {}")
+        .dims(64)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let kernel = pro_que.kernel_builder("noop").arg(&buffer).build().unwrap();
+
+    let mut event = Event::empty();
+    unsafe { kernel.cmd().enew(&mut event).enq().unwrap(); }
+
+    let mut event_list = EventList::new();
+    event_list.push(event.clone());
+    event_list.push(Event::empty());
+    event_list.push(event.clone());
+
+    assert_eq!(event_list.to_wait_list(false).unwrap().len(), 1);
+    event_list.verify_context(pro_que.context()).unwrap();
+
+    let other_context = Context::builder().build().unwrap();
+    assert!(event_list.verify_context(&other_context).is_err());
+
+    pro_que.finish().unwrap();
+    assert!(event_list.to_wait_list(true).unwrap().is_empty());
+}
+
+
+#[test]
+fn event_reset_user() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void noop(__global float* buffer) {}")
+        .dims(64)
+        .build().unwrap();
+
+    let mut gate = Event::user(pro_que.context()).unwrap();
+    gate.set_complete().unwrap();
+    assert!(gate.is_complete().unwrap());
+
+    gate.reset_user().unwrap();
+    assert!(!gate.is_complete().unwrap());
+    gate.set_complete().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let kernel = pro_que.kernel_builder("noop").arg(&buffer).build().unwrap();
+
+    let mut event = Event::empty();
+    unsafe { kernel.cmd().enew(&mut event).enq().unwrap(); }
+    assert!(event.reset_user().is_err());
+    assert!(Event::empty().reset_user().is_err());
+}
@@ -1,7 +1,9 @@
-//! Test adding multiple context properties.
+//! Test adding multiple context properties and per-device queues.
 
 use crate::standard::{Platform, Device, Context};
-use crate::core::{ContextPropertyValue};
+use crate::core::{ContextPropertyValue, ContextProperties, CL_PRINTF_CALLBACK_ARM,
+    CL_PRINTF_BUFFERSIZE_ARM, PrintfCallbackFn};
+use crate::ffi::{c_char, c_void, size_t};
 
 // static SRC: &'static str = r#"
 //     __kernel void multiply(__global float* buffer, float coeff) {
@@ -69,4 +71,50 @@ fn test_context_props() {
         //     event.wait().unwrap();
         // }
     }
-}
\ No newline at end of file
+}
+
+
+extern "C" fn printf_sink(_buffer: *const c_char, _len: size_t, _complete: size_t,
+        _user_data: *mut c_void) {}
+
+#[test]
+fn printf_props_raw() {
+    let sink: PrintfCallbackFn = printf_sink;
+    let props = ContextProperties::new()
+        .printf_buffer_size_arm(1 << 20)
+        .printf_callback_arm(sink);
+
+    let raw = props.to_raw();
+    assert_eq!(raw.len(), 5);
+    let pairs: Vec<_> = raw[..4].chunks(2).map(|pair| (pair[0], pair[1])).collect();
+    assert!(pairs.contains(&(CL_PRINTF_BUFFERSIZE_ARM, 1 << 20)));
+    assert!(pairs.contains(&(CL_PRINTF_CALLBACK_ARM, sink as usize as isize)));
+
+    let props = unsafe { ContextProperties::from_raw(&raw).unwrap() };
+    assert_eq!(props.to_raw().len(), 5);
+}
+
+#[test]
+fn context_queue_for() {
+    let platform = Platform::default();
+    let devices = Device::list_all(platform).unwrap();
+    let context = Context::builder()
+        .platform(platform)
+        .devices(&devices[..])
+        .build().unwrap();
+
+    for &device in devices.iter() {
+        let queue = context.queue_for(device).unwrap();
+        assert_eq!(queue.device(), device);
+
+        // The same queue is returned by later calls and by clones:
+        assert_eq!(context.queue_for(device).unwrap().as_ptr(), queue.as_ptr());
+        assert_eq!(context.clone().queue_for(device).unwrap().as_ptr(), queue.as_ptr());
+    }
+
+    // A device from another context is rejected:
+    let other_context = Context::builder().platform(platform).devices(devices[0]).build().unwrap();
+    if devices.len() > 1 {
+        assert!(other_context.queue_for(devices[1]).is_err());
+    }
+}
@@ -0,0 +1,49 @@
+//! Tests partitioning a device into sub-devices with
+//! `Device::create_sub_devices`.
+
+use crate::core::{DeviceInfo, DeviceInfoResult};
+use crate::standard::{Platform, Device, Partition, Context, Queue};
+
+#[test]
+fn device_fission() {
+    let device = Device::first(Platform::default()).unwrap();
+
+    let max_sub_devices = match device.info(DeviceInfo::PartitionMaxSubDevices).unwrap() {
+        DeviceInfoResult::PartitionMaxSubDevices(max) => max,
+        _ => unreachable!(),
+    };
+    if max_sub_devices < 2 {
+        println!("Skipping 'tests::device_fission': Device partitioning is not supported.");
+        return;
+    }
+
+    assert!(device.create_sub_devices(Partition::ByCounts(vec![])).is_err());
+
+    let sub_devices = device.create_sub_devices(Partition::Equally(1)).unwrap();
+    assert!(sub_devices.len() >= 2);
+
+    for sub_device in sub_devices.iter() {
+        assert_eq!(sub_device.max_compute_units().unwrap(), 1);
+    }
+
+    // Sub-devices are usable wherever a `Device` is:
+    let context = Context::builder()
+        .platform(Platform::default())
+        .devices(*sub_devices[0])
+        .build().unwrap();
+    let queue = Queue::new(&context, sub_devices[0].device(), None).unwrap();
+
+    // Dropping the sub-devices releases them. The context and queue retain
+    // the sub-device they were created with:
+    drop(sub_devices);
+    assert_eq!(queue.device().max_compute_units().unwrap(), 1);
+    drop(queue);
+    drop(context);
+
+    // Sub-devices are released each time they are dropped, allowing a
+    // device to be partitioned repeatedly:
+    for _ in 0..8 {
+        let sub_devices = device.create_sub_devices(Partition::Equally(1)).unwrap();
+        assert!(sub_devices.len() >= 2);
+    }
+}
@@ -0,0 +1,55 @@
+//! Tests creating an on-device queue with `Queue::new_on_device` and passing
+//! it to a kernel which enqueues a child kernel.
+
+use crate::core::OpenclVersion;
+use crate::standard::{Platform, Device, Context, Queue, Program, Kernel, Buffer};
+
+static SRC: &'static str = r#"
+    __kernel void parent(queue_t dev_queue, __global int* buf) {
+        if (get_global_id(0) == 0) {
+            enqueue_kernel(dev_queue, CLK_ENQUEUE_FLAGS_WAIT_KERNEL,
+                ndrange_1D(get_global_size(0)),
+                ^{ buf[get_global_id(0)] += 1; });
+        }
+    }
+"#;
+
+#[test]
+fn device_queue() {
+    let device = Device::first(Platform::default()).unwrap();
+    let context = Context::builder().devices(device).build().unwrap();
+
+    if device.version().unwrap() < OpenclVersion::new(2, 0) {
+        // Older devices must be rejected with an error:
+        assert!(Queue::new_on_device(&context, device, None, None).is_err());
+        println!("Skipping 'tests::device_queue': OpenCL 2.0 is not supported.");
+        return;
+    }
+
+    let queue = Queue::new(&context, device, None).unwrap();
+    let dev_queue = Queue::new_on_device(&context, device, None, Some(16 * 1024)).unwrap();
+
+    let program = Program::builder()
+        .devices(device)
+        .src(SRC)
+        .cmplr_opt("-cl-std=CL2.0")
+        .build(&context).unwrap();
+
+    let buffer = Buffer::<i32>::builder()
+        .queue(queue.clone())
+        .len(64)
+        .fill_val(1)
+        .build().unwrap();
+
+    let kernel = Kernel::builder()
+        .program(&program)
+        .name("parent")
+        .queue(queue.clone())
+        .global_work_size(64)
+        .arg_queue(&dev_queue)
+        .arg(&buffer)
+        .build().unwrap();
+
+    unsafe { kernel.enq().unwrap(); }
+    buffer.assert_eq(&[2; 64][..]);
+}
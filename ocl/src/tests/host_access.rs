@@ -0,0 +1,40 @@
+//! Ensures that host access to buffers created with restricted host access
+//! flags is reported clearly.
+
+use crate::standard::{ProQue, Buffer, BufferCmdError};
+use crate::error::ErrorKind;
+
+#[test]
+fn device_private_buffer() {
+    let src = r#"
+        __kernel void copy(__global const float* src, __global float* dst) {
+            dst[get_global_id(0)] = src[get_global_id(0)];
+        }
+    "#;
+
+    let pro_que = ProQue::builder()
+        .src(src)
+        .dims(1024)
+        .build().unwrap();
+
+    let scratch = Buffer::<f32>::new_device_private(pro_que.queue(), 1024).unwrap();
+    let output = pro_que.buffer_builder::<f32>().fill_val(0.0f32).build().unwrap();
+
+    let mut vec = vec![0.0f32; scratch.len()];
+
+    match *scratch.read(&mut vec).enq().unwrap_err().kind() {
+        ErrorKind::BufferCmd(BufferCmdError::HostAccessDenied { op, .. }) => assert_eq!(op, "read"),
+        ref kind => panic!("Unexpected error: {:?}", kind),
+    }
+    assert!(scratch.write(&vec).enq().is_err());
+
+    // Device-side access is unaffected:
+    scratch.cmd().fill(3.0f32, None).enq().unwrap();
+    let kernel = pro_que.kernel_builder("copy")
+        .arg(&scratch)
+        .arg(&output)
+        .build().unwrap();
+    unsafe { kernel.enq().unwrap(); }
+
+    output.assert_eq(&vec![3.0f32; output.len()]);
+}
@@ -1,6 +1,6 @@
 //! Tests various image operations.
 //!
-//! * TODO: Test fill and copy to buffer.
+//! * TODO: Test copy to buffer.
 //!
 //! Runs both the core function and the 'standard' method call for each.
 
@@ -245,3 +245,61 @@ fn image_ops() {
 
     println!("{} total test runs complete.\n", ttl_runs);
 }
+
+#[test]
+fn image_fill() {
+    const W: usize = 16;
+    const H: usize = 8;
+
+    let proque = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims([W, H])
+        .build().unwrap();
+
+    let image = Image::<f32>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::Float)
+        .image_type(MemObjectType::Image2d)
+        .dims([W, H])
+        .flags(flags::MEM_READ_WRITE | flags::MEM_COPY_HOST_PTR)
+        .copy_host_slice(&vec![0.0f32; W * H * 4])
+        .queue(proque.queue().clone())
+        .build().unwrap();
+
+    // Regions extending past the image are rejected:
+    assert!(image.fill([1.0; 4], [12, 0, 0], [8, 1, 1], &[]).is_err());
+    assert!(image.fill([1.0; 4], [0, 0, 0], [0, 1, 1], &[]).is_err());
+
+    let color = [0.25, 0.5, 0.75, 1.0];
+    let fill_event = image.fill(color, [2, 3, 0], [4, 2, 1], &[]).unwrap();
+
+    let mut vec = vec![0.0f32; W * H * 4];
+    image.read(&mut vec).ewait(&fill_event).enq().unwrap();
+
+    for y in 0..H {
+        for x in 0..W {
+            let idx = (y * W + x) * 4;
+            let filled = (2..6).contains(&x) && (3..5).contains(&y);
+            let expected = if filled { color } else { [0.0; 4] };
+            assert_eq!(&vec[idx..idx + 4], &expected[..], "pixel ({}, {})", x, y);
+        }
+    }
+    // Unnormalized integer images are filled with integer colors:
+    let int_image = Image::<u32>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnsignedInt32)
+        .image_type(MemObjectType::Image2d)
+        .dims([W, H])
+        .flags(flags::MEM_READ_WRITE | flags::MEM_COPY_HOST_PTR)
+        .copy_host_slice(&vec![0u32; W * H * 4])
+        .queue(proque.queue().clone())
+        .build().unwrap();
+
+    let fill_event = int_image.fill([1.0, 2.0, 3.0, 4.0], [0, 0, 0], [W, H, 1], &[]).unwrap();
+    let mut int_vec = vec![0u32; W * H * 4];
+    int_image.read(&mut int_vec).ewait(&fill_event).enq().unwrap();
+    assert!(int_vec.chunks(4).all(|px| px == [1, 2, 3, 4]));
+
+    assert!(image.fill(color, [usize::MAX, 0, 0], [1, 1, 1], &[]).is_err());
+}
+
@@ -1,6 +1,7 @@
 use std::thread;
 use std::time::Duration;
-use crate::standard::{ProQue, Kernel, Buffer};
+use crate::standard::{ProQue, Kernel, Buffer, Event};
+use crate::kernel_args;
 
 static SRC_0: &'static str = r#"
     __kernel void add(__global float* buffer, float addend) {
@@ -51,6 +52,117 @@ fn kernel_arg_ptr_out_of_scope() {
 }
 
 
+/// Bind the same buffer to two argument slots (as both the input and the
+/// in-place output) using both the builder and `Kernel::set_arg`. The
+/// `cl_mem` value must be copied by `clSetKernelArg` for each slot.
+#[test]
+fn kernel_arg_same_buffer_twice() {
+    let src = r#"
+        __kernel void add_in_place(__global const float* src, __global float* dst,
+                float addend) {
+            uint idx = get_global_id(0);
+            dst[idx] = src[idx] + addend;
+        }
+    "#;
+
+    let pro_que = ProQue::builder()
+        .src(src)
+        .dims([1024])
+        .build().unwrap();
+
+    let buffer = pro_que.buffer_builder::<f32>().fill_val(1.0).build().unwrap();
+
+    let kernel = pro_que.kernel_builder("add_in_place")
+        .arg(&buffer)
+        .arg(&buffer)
+        .arg(10.0f32)
+        .build().unwrap();
+
+    unsafe { kernel.enq().unwrap(); }
+
+    let mut vec = vec![0.0f32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 11.0));
+
+    // Re-bind the same buffer to both slots of the built kernel:
+    kernel.set_arg(0, &buffer).unwrap();
+    kernel.set_arg(1, &buffer).unwrap();
+    unsafe { kernel.enq().unwrap(); }
+
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 21.0));
+}
+
+
+/// Ensure that `Kernel::enq_over` derives a work size matching the shape of
+/// a buffer.
+#[test]
+fn kernel_enq_over_shape() {
+    let src = r#"
+        __kernel void mark(__global uint* buffer) {
+            uint idx = get_global_id(1) * get_global_size(0) + get_global_id(0);
+            buffer[idx] = get_work_dim();
+        }
+    "#;
+
+    let pro_que = ProQue::builder()
+        .src(src)
+        .dims([64, 32])
+        .build().unwrap();
+
+    assert!(pro_que.create_buffer::<u32>().unwrap().with_shape([64, 64]).is_err());
+
+    let buffer = pro_que.create_buffer::<u32>().unwrap().with_shape([64, 32]).unwrap();
+    assert_eq!(buffer.work_dims().to_lens().unwrap(), [64, 32, 1]);
+
+    let kernel = pro_que.kernel_builder("mark")
+        .global_work_size(1)
+        .arg(&buffer)
+        .build().unwrap();
+
+    unsafe { kernel.enq_over(&buffer).unwrap(); }
+
+    let mut vec = vec![0u32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 2));
+}
+
+/// Ensure that arguments can be set from a tuple and rebound repeatedly.
+#[test]
+fn kernel_set_args_tuple() {
+    let src = r#"
+        __kernel void scale(__global const float* src, __global float* dst,
+                float factor, uint len) {
+            uint idx = get_global_id(0);
+            if (idx < len) { dst[idx] = src[idx] * factor; }
+        }
+    "#;
+
+    let pro_que = ProQue::builder()
+        .src(src)
+        .dims([1024])
+        .build().unwrap();
+
+    let input = pro_que.buffer_builder::<f32>().fill_val(2.0).build().unwrap();
+    let output = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.kernel_builder("scale")
+        .arg(None::<&Buffer<f32>>)
+        .arg(None::<&Buffer<f32>>)
+        .arg(0.0f32)
+        .arg(0u32)
+        .build().unwrap();
+
+    let mut vec = vec![0.0f32; output.len()];
+
+    for &factor in &[3.0f32, 5.0] {
+        kernel.set_args((&input, &output, factor, input.len() as u32)).unwrap();
+        unsafe { kernel.enq().unwrap(); }
+        output.read(&mut vec).enq().unwrap();
+        assert!(vec.iter().all(|&v| v == 2.0 * factor));
+    }
+}
+
 /// Ensure that owned buffer/image kernel arguments work and that they do not
 /// unnecessarily restrict the lifetime of `KernelBuilder`.
 #[test]
@@ -123,6 +235,53 @@ fn kernel_arg_named_none() -> crate::Result<()> {
     Ok(())
 }
 
+/// Ensure that argument names only known at runtime can be used.
+#[test]
+fn kernel_arg_dynamic_names() {
+    let pro_que = ProQue::builder()
+        .src(SRC_0)
+        .dims(1024)
+        .build().unwrap();
+
+    // Names read from e.g. a config file:
+    let names: Vec<String> = "buffer addend".split(' ').map(String::from).collect();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let kernel = pro_que.kernel_builder("add")
+        .arg_named(names[0].clone(), None::<&Buffer<f32>>)
+        .arg_named(names[1].clone(), 0.0f32)
+        .build().unwrap();
+
+    assert_eq!(kernel.named_arg_idx(&names[1]), Some(1));
+    kernel.set_arg(&names[0], &buffer).unwrap();
+    kernel.set_arg(names[1].clone(), 5.0f32).unwrap();
+
+    unsafe { kernel.enq().unwrap(); }
+    buffer.assert_eq(&vec![5.0f32; 1024]);
+}
+
+/// Ensure that a kernel can be enqueued over consecutive batches of a buffer.
+#[test]
+fn kernel_enqueue_batched() {
+    let pro_que = ProQue::builder()
+        .src(SRC_0)
+        .dims(1000)
+        .build().unwrap();
+
+    let buffer = pro_que.buffer_builder::<f32>().fill_val(1.0f32).build().unwrap();
+    let kernel = pro_que.kernel_builder("add")
+        .arg(None::<&Buffer<f32>>)
+        .arg(2.0f32)
+        .build().unwrap();
+
+    let events = unsafe { kernel.enqueue_batched(0, &buffer, 256).unwrap() };
+    assert_eq!(events.len(), 4);
+    events.wait_for().unwrap();
+    buffer.assert_eq(&vec![3.0f32; 1000]);
+
+    assert!(unsafe { kernel.enqueue_batched(0, &buffer, 0) }.is_err());
+}
+
 /// Ensure that incorrectly sized floats cause an error (instead of silently
 /// passing gibberish).
 #[test]
@@ -143,4 +302,450 @@ fn kernel_arg_float_size() {
             None => panic!("{}", err),
         }
     };
-}
\ No newline at end of file
+}
+
+kernel_args! {
+    struct AddArgs<'a> {
+        buf: &'a Buffer<f32>,
+        addend: f32,
+    }
+}
+
+/// Ensure that arguments declared with `kernel_args!` can be used both when
+/// building and when re-setting arguments on a kernel.
+#[test]
+fn kernel_arg_macro() -> crate::Result<()> {
+    let ds_len = 1024;
+    let pro_que = ProQue::builder()
+        .src(SRC_0)
+        .dims(ds_len)
+        .build()?;
+
+    let buffer = pro_que.create_buffer::<f32>()?;
+
+    let mut kb = pro_que.kernel_builder("add");
+    AddArgs { buf: &buffer, addend: 10.0 }.declare(&mut kb);
+    let kernel = kb.build()?;
+    unsafe { kernel.enq()?; }
+
+    AddArgs { buf: &buffer, addend: 5.0 }.set(&kernel)?;
+    unsafe { kernel.enq()?; }
+
+    let mut output_vec = vec![0.; ds_len];
+    buffer.read(&mut output_vec).enq()?;
+    assert!(output_vec.iter().all(|&e| e == 15.));
+    Ok(())
+}
+
+
+/// Ensure that the size of a `__local` argument can be changed between
+/// enqueues without rebuilding the kernel.
+#[test]
+fn kernel_arg_local_resize() -> crate::Result<()> {
+    let src = r#"
+        __kernel void group_sum(__global float* out, __local float* scratch) {
+            size_t lid = get_local_id(0);
+            scratch[lid] = 1.0f;
+            barrier(CLK_LOCAL_MEM_FENCE);
+            if (lid == 0) {
+                float sum = 0.0f;
+                for (size_t i = 0; i < get_local_size(0); i++) {
+                    sum += scratch[i];
+                }
+                out[get_group_id(0)] = sum;
+            }
+        }
+    "#;
+
+    let ds_len = 256;
+    let pro_que = ProQue::builder()
+        .src(src)
+        .dims(ds_len)
+        .build()?;
+
+    let out = pro_que.create_buffer::<f32>()?;
+
+    let kernel = pro_que.kernel_builder("group_sum")
+        .arg(&out)
+        .arg_local::<f32>(1)
+        .build()?;
+
+    for &lws in &[16usize, 32, 64] {
+        kernel.set_arg_local::<f32, _>(1, lws)?;
+        unsafe { kernel.cmd().local_work_size(lws).enq()?; }
+
+        let mut sums = vec![0.0f32; ds_len / lws];
+        out.read(&mut sums).enq()?;
+        assert!(sums.iter().all(|&s| s == lws as f32));
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "opencl_version_2_0")]
+#[test]
+fn kernel_exec_info_svm_ptrs() {
+    use crate::core::OpenclVersion;
+
+    let pro_que = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims(1)
+        .build().unwrap();
+    let kernel = pro_que.kernel_builder("nop").build().unwrap();
+
+    if pro_que.device().version().unwrap() < OpenclVersion::new(2, 0) {
+        assert!(kernel.set_exec_info_svm_ptrs(&[]).is_err());
+    } else {
+        kernel.set_exec_info_svm_ptrs(&[]).unwrap();
+    }
+}
+
+#[test]
+fn kernel_enqueue_until() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void step(__global int* counter) { counter[0] += 1; }")
+        .dims(1)
+        .build().unwrap();
+
+    let counter = pro_que.buffer_builder::<i32>().len(4).fill_val(0).build().unwrap();
+    let kernel = pro_que.kernel_builder("step").arg(&counter).build().unwrap();
+
+    let iters = unsafe { kernel.enqueue_until(&counter, 100, |_, count| count < 10).unwrap() };
+    assert_eq!(iters, 10);
+    counter.assert_eq(&[10, 0, 0, 0]);
+
+    let iters = unsafe { kernel.enqueue_until(&counter, 5, |_, _| true).unwrap() };
+    assert_eq!(iters, 5);
+    counter.assert_eq(&[15, 0, 0, 0]);
+}
+
+#[test]
+fn kernel_run_and_read() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void square(__global int* out) {
+            int i = get_global_id(0);
+            out[i] = i * i;
+        }")
+        .dims(64)
+        .build().unwrap();
+
+    let out = pro_que.create_buffer::<i32>().unwrap();
+    let kernel = pro_que.kernel_builder("square").arg(&out).build().unwrap();
+
+    let vec = unsafe { kernel.run_and_read(&out).unwrap() };
+    assert_eq!(vec, (0..64).map(|i| i * i).collect::<Vec<i32>>());
+}
+
+#[test]
+fn kernel_set_arg_after() {
+    let src = r#"
+        __kernel void produce(__global int* out) {
+            out[get_global_id(0)] = get_global_id(0);
+        }
+
+        __kernel void consume(__global const int* in, __global int* out) {
+            out[get_global_id(0)] = in[get_global_id(0)] * 2;
+        }
+    "#;
+
+    let pro_que = ProQue::builder().src(src).dims(256).build().unwrap();
+    let produced = pro_que.create_buffer::<i32>().unwrap();
+    let consumed = pro_que.create_buffer::<i32>().unwrap();
+
+    let producer = pro_que.kernel_builder("produce").arg(&produced).build().unwrap();
+    let consumer = pro_que.kernel_builder("consume")
+        .arg(None::<&Buffer<i32>>)
+        .arg(&consumed)
+        .build().unwrap();
+
+    let mut event = Event::empty();
+    unsafe { producer.cmd().enew(&mut event).enq().unwrap(); }
+    consumer.set_arg_after(0, &produced, &event).unwrap();
+    unsafe { consumer.enq().unwrap(); }
+
+    let expected: Vec<i32> = (0..256).map(|i| i * 2).collect();
+    assert_eq!(consumed.read_all().unwrap(), expected);
+}
+
+#[test]
+fn kernel_mem_sizes() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void scratch(__global float* buffer, __local float* tmp) {
+            tmp[get_local_id(0)] = buffer[get_global_id(0)];
+            barrier(CLK_LOCAL_MEM_FENCE);
+            buffer[get_global_id(0)] = tmp[0];
+        }")
+        .dims(64)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let kernel = pro_que.kernel_builder("scratch")
+        .arg(&buffer)
+        .arg_local::<f32>(64)
+        .build().unwrap();
+
+    let device = pro_que.device();
+    assert!(kernel.local_mem_size(device).unwrap() >= 64 * std::mem::size_of::<f32>());
+    kernel.private_mem_size(device).unwrap();
+}
+
+#[test]
+fn kernel_enqueue_flat() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void mark(__global int* out) {
+            out[get_global_id(0)] = get_work_dim();
+        }")
+        .dims([4, 4, 4])
+        .build().unwrap();
+
+    let out = pro_que.create_buffer::<i32>().unwrap();
+    let kernel = pro_que.kernel_builder("mark").arg(&out).build().unwrap();
+
+    unsafe { kernel.enqueue_flat(64).unwrap(); }
+    assert_eq!(out.read_all().unwrap(), vec![1; 64]);
+
+    assert!(unsafe { kernel.enqueue_dims(2, &[64], None) }.is_err());
+    assert!(unsafe { kernel.enqueue_dims(4, &[4, 4, 2, 2], None) }.is_err());
+}
+
+#[test]
+fn kernel_is_idle() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void add(__global int* buffer) { buffer[get_global_id(0)] += 1; }")
+        .dims(64)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<i32>().unwrap();
+    let mut kernel = pro_que.kernel_builder("add").arg(&buffer).build().unwrap();
+    assert!(kernel.is_idle().is_err());
+
+    kernel.enable_completion_tracking();
+    assert!(kernel.is_idle().unwrap());
+
+    let gate = Event::user(pro_que.context()).unwrap();
+    unsafe { kernel.cmd().ewait(&gate).enq().unwrap(); }
+    assert!(!kernel.is_idle().unwrap());
+
+    gate.set_complete().unwrap();
+    pro_que.finish().unwrap();
+    assert!(kernel.is_idle().unwrap());
+}
+
+#[cfg(feature = "opencl_version_2_1")]
+#[test]
+fn kernel_sub_group_info() {
+    use crate::standard::SpatialDims;
+
+    let pro_que = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims(64)
+        .build().unwrap();
+    let kernel = pro_que.kernel_builder("nop").build().unwrap();
+    let device = pro_que.device();
+    let lws = SpatialDims::One(16);
+
+    if device.has_extension("cl_khr_subgroups").unwrap() {
+        assert!(kernel.max_sub_group_size_for_ndrange(device, &lws).unwrap() > 0);
+        assert!(kernel.sub_group_count_for_ndrange(device, &lws).unwrap() > 0);
+    } else {
+        assert!(kernel.max_sub_group_size_for_ndrange(device, &lws).is_err());
+    }
+    assert!(kernel.sub_group_count_for_ndrange(device, &SpatialDims::Unspecified).is_err());
+}
+
+#[test]
+fn kernel_release() {
+    use crate::core::{self, KernelInfo};
+
+    let pro_que = ProQue::builder()
+        .src(SRC_0)
+        .dims(64)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let kernel = pro_que.kernel_builder("add")
+        .arg(&buffer)
+        .arg(1.0f32)
+        .build().unwrap();
+
+    unsafe { kernel.enq().unwrap(); }
+    let kernel_core = kernel.as_core().clone();
+    kernel.release().unwrap();
+
+    // The cloned core reference keeps the kernel object alive:
+    core::get_kernel_info(&kernel_core, KernelInfo::FunctionName).unwrap();
+    pro_que.finish().unwrap();
+}
+
+#[test]
+fn kernel_multiple_local_args() {
+    const LWS: usize = 16;
+    const GWS: usize = LWS * 4;
+
+    let pro_que = ProQue::builder()
+        .src("__kernel void tiles(__global const float* input, __local float* tile_a,
+                __local int* tile_b, __global float* output)
+        {
+            uint lid = get_local_id(0);
+            uint lws = get_local_size(0);
+            tile_a[lid] = input[get_global_id(0)];
+            tile_b[lid] = lid;
+            tile_b[lid + lws] = lid * 2;
+            barrier(CLK_LOCAL_MEM_FENCE);
+            output[get_global_id(0)] = tile_a[lws - 1 - lid] + (float)tile_b[lid + lws];
+        }")
+        .dims(GWS)
+        .build().unwrap();
+
+    let input = pro_que.buffer_builder::<f32>()
+        .copy_host_slice(&(0..GWS).map(|i| i as f32).collect::<Vec<_>>())
+        .build().unwrap();
+    let output = pro_que.create_buffer::<f32>().unwrap();
+
+    // Two local buffers of different sizes bound to consecutive indices:
+    let kernel = pro_que.kernel_builder("tiles")
+        .arg(&input)
+        .arg_local::<f32>(LWS)
+        .arg_local::<i32>(LWS * 2)
+        .arg(&output)
+        .local_work_size(LWS)
+        .build().unwrap();
+
+    let local_bytes = LWS * std::mem::size_of::<f32>() + LWS * 2 * std::mem::size_of::<i32>();
+    assert!(kernel.local_mem_size(pro_que.device()).unwrap() >= local_bytes);
+
+    unsafe { kernel.enq().unwrap(); }
+
+    let mut vec = vec![0.0f32; GWS];
+    output.read(&mut vec).enq().unwrap();
+    for (i, &val) in vec.iter().enumerate() {
+        let (group, lid) = (i / LWS, i % LWS);
+        let expected = (group * LWS + (LWS - 1 - lid)) as f32 + (lid * 2) as f32;
+        assert_eq!(val, expected);
+    }
+
+    // Resizing one local argument leaves the other untouched:
+    kernel.set_arg_local::<i32, _>(2, LWS * 3).unwrap();
+    assert!(kernel.local_mem_size(pro_que.device()).unwrap() >= local_bytes + LWS * 4);
+}
+
+#[test]
+fn kernel_set_named_scalars() {
+    use std::collections::HashMap;
+
+    let pro_que = ProQue::builder()
+        .src("__kernel void params(__global float* out, float scale, int count, uchar flag) {
+            out[0] = scale * (float)count + (float)flag;
+        }")
+        .dims(1)
+        .build().unwrap();
+
+    let out = pro_que.create_buffer::<f32>().unwrap();
+    let kernel = pro_que.kernel_builder("params")
+        .arg_named("out", &out)
+        .arg_named("scale", 0.0f32)
+        .arg_named("count", 0i32)
+        .arg_named("flag", 0u8)
+        .build().unwrap();
+
+    let mut values = HashMap::new();
+    values.insert("scale".to_string(), 0.5);
+    values.insert("count".to_string(), 6.0);
+    values.insert("flag".to_string(), 2.0);
+    kernel.set_named_scalars(&values).unwrap();
+
+    unsafe { kernel.enq().unwrap(); }
+    let mut vec = vec![0.0f32; 1];
+    out.read(&mut vec).enq().unwrap();
+    assert_eq!(vec[0], 5.0);
+
+    // Every problem is reported together:
+    let mut values = HashMap::new();
+    values.insert("bogus".to_string(), 1.0);
+    values.insert("count".to_string(), 1.5);
+    values.insert("flag".to_string(), 300.0);
+    values.insert("out".to_string(), 1.0);
+    values.insert("scale".to_string(), 2.0);
+    let err = kernel.set_named_scalars(&values).unwrap_err().to_string();
+    for name in &["bogus", "count", "flag", "out"] {
+        assert!(err.contains(&format!("'{}'", name)), "{}", err);
+    }
+    assert!(!err.contains("'scale'"));
+}
+
+#[test]
+fn kernel_set_arg_slice() {
+    const WORK_SIZE: usize = 16;
+
+    let pro_que = ProQue::builder()
+        .src("__kernel void transform(__global const float* matrix, __global float* out) {
+            uint i = get_global_id(0);
+            out[i] = matrix[i % 4] * (float)i;
+        }")
+        .dims(WORK_SIZE)
+        .build().unwrap();
+
+    let output = pro_que.create_buffer::<f32>().unwrap();
+    let kernel = pro_que.kernel_builder("transform")
+        .arg_named("matrix", None::<&Buffer<f32>>)
+        .arg(&output)
+        .build().unwrap();
+
+    let mut vec = vec![0.0f32; WORK_SIZE];
+
+    // A new slice is bound before each enqueue:
+    for frame in 0..3 {
+        let matrix = [frame as f32, 1.0, 2.0, 3.0];
+        kernel.set_arg_slice("matrix", &matrix[..]).unwrap();
+        unsafe { kernel.enq().unwrap(); }
+
+        output.read(&mut vec).enq().unwrap();
+        for (i, &val) in vec.iter().enumerate() {
+            assert_eq!(val, matrix[i % 4] * i as f32);
+        }
+    }
+
+    // Replacing a slice argument before enqueuing releases it:
+    kernel.set_arg_slice("matrix", &[9.0f32; 4][..]).unwrap();
+    let matrix = pro_que.buffer_builder::<f32>().len(4).copy_host_slice(&[1.0f32; 4]).build().unwrap();
+    kernel.set_arg("matrix", &matrix).unwrap();
+    unsafe { kernel.enq().unwrap(); }
+
+    output.read(&mut vec).enq().unwrap();
+    for (i, &val) in vec.iter().enumerate() {
+        assert_eq!(val, i as f32);
+    }
+
+    // Type-checked like any other argument:
+    assert!(kernel.set_arg_slice("matrix", &[0i32; 4][..]).is_err());
+}
+
+#[test]
+fn kernel_prewarm() {
+    const WORK_SIZE: usize = 64;
+
+    let pro_que = ProQue::builder()
+        .src("__kernel void mark(__global int* out) {
+            out[get_global_id(0) + get_global_id(1) * get_global_size(0)] += 1;
+        }")
+        .dims((WORK_SIZE / 8, 8))
+        .build().unwrap();
+
+    let output = pro_que.create_buffer::<i32>().unwrap();
+    let kernel = pro_que.kernel_builder("mark")
+        .arg(&output)
+        .build().unwrap();
+
+    // Only the first work-item runs:
+    unsafe { kernel.prewarm().unwrap(); }
+    let mut vec = vec![0i32; WORK_SIZE];
+    output.read(&mut vec).enq().unwrap();
+    assert_eq!(vec[0], 1);
+    assert!(vec[1..].iter().all(|&val| val == 0));
+
+    // Arguments must be set:
+    let unset = pro_que.kernel_builder("mark")
+        .allow_unset_args()
+        .build().unwrap();
+    assert!(unsafe { unset.prewarm() }.is_err());
+}
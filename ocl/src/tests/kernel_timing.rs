@@ -0,0 +1,75 @@
+//! Tests the kernel execution time accumulator enabled by
+//! `Kernel::enable_timing` and `EventList::profiling_summary`.
+
+use crate::standard::{ProQue, Event, EventList};
+use crate::flags::CommandQueueProperties;
+
+#[test]
+fn kernel_timing_stats() {
+    let src = r#"
+        __kernel void add(__global float* buffer, float addend) {
+            buffer[get_global_id(0)] += addend;
+        }
+    "#;
+
+    let pro_que = ProQue::builder()
+        .src(src)
+        .dims(1024)
+        .queue_properties(CommandQueueProperties::new().profiling())
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let mut kernel = pro_que.kernel_builder("add")
+        .arg(&buffer)
+        .arg(1.0f32)
+        .build().unwrap();
+
+    assert_eq!(kernel.timing_stats().unwrap(), None);
+    kernel.enable_timing();
+
+    // Events requested by the caller are still created:
+    let mut event = Event::empty();
+    unsafe { kernel.cmd().enew(&mut event).enq().unwrap(); }
+    assert!(event.is_valid());
+
+    for _ in 0..9 {
+        unsafe { kernel.enq().unwrap(); }
+    }
+
+    let stats = kernel.timing_stats().unwrap().unwrap();
+    assert_eq!(stats.count, 10);
+    assert!(stats.min_ns <= stats.max_ns);
+    assert!(stats.total_ns >= stats.max_ns);
+    buffer.assert_eq(&vec![10.0f32; 1024]);
+}
+
+
+#[test]
+fn event_list_profiling_summary() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void add(__global float* buffer) { buffer[get_global_id(0)] += 1.0f; }")
+        .dims(1024)
+        .queue_properties(CommandQueueProperties::new().profiling())
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let kernel = pro_que.kernel_builder("add").arg(&buffer).build().unwrap();
+
+    assert_eq!(EventList::new().profiling_summary().unwrap().durations_ns.len(), 0);
+
+    let mut events = EventList::new();
+    for _ in 0..4 {
+        unsafe { kernel.cmd().enew(&mut events).enq().unwrap(); }
+    }
+
+    let summary = events.profiling_summary().unwrap();
+    assert_eq!(summary.durations_ns.len(), 4);
+    assert_eq!(summary.total_ns, summary.durations_ns.iter().sum::<u64>());
+    assert!(summary.end_ns >= summary.start_ns);
+
+    // An incomplete user event must be rejected rather than waited on:
+    let user_event = Event::user(pro_que.context()).unwrap();
+    events.push(user_event.clone());
+    assert!(events.profiling_summary().is_err());
+    user_event.set_complete().unwrap();
+}
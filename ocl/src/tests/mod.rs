@@ -10,7 +10,10 @@
 extern crate rand;
 
 pub mod build_error;
+#[cfg(feature = "async")]
+pub mod build_async;
 pub mod buffer_copy;
+pub mod buffer_drop;
 pub mod buffer_ops_rect;
 pub mod image_ops;
 pub mod buffer_fill;
@@ -19,8 +22,19 @@ pub mod concurrent;
 pub mod kernel_arg;
 pub mod vector_types;
 pub mod context_props;
+#[cfg(feature = "async")]
 pub mod r#async;
+#[cfg(feature = "async")]
 pub mod buffer_sink_stream_cycles;
+pub mod scoped_default;
+pub mod persistent_map;
+pub mod host_access;
+pub mod kernel_timing;
+pub mod device_fission;
+pub mod queue_info;
+pub mod version_info;
+#[cfg(feature = "opencl_version_2_0")]
+pub mod device_queue;
 
 use self::rand::Rng;
 use crate::core::OclScl;
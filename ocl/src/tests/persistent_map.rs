@@ -0,0 +1,91 @@
+//! Ensures that a persistently mapped buffer can be reused across enqueues
+//! and that maps and unmaps can be made blocking or non-blocking.
+
+use crate::standard::{ProQue, Buffer, Event};
+use crate::flags::MemFlags;
+
+#[test]
+fn persistent_map() {
+    let src = r#"
+        __kernel void add(__global float* buffer, float addend) {
+            buffer[get_global_id(0)] += addend;
+        }
+    "#;
+
+    let pro_que = ProQue::builder()
+        .src(src)
+        .dims(1024)
+        .build().unwrap();
+
+    let unmappable = pro_que.create_buffer::<f32>().unwrap();
+    assert!(unsafe { unmappable.persistent_map() }.is_err());
+
+    let buffer = Buffer::<f32>::builder()
+        .queue(pro_que.queue().clone())
+        .flags(MemFlags::new().read_write().alloc_host_ptr())
+        .len(1024)
+        .build().unwrap();
+
+    let kernel = pro_que.kernel_builder("add")
+        .arg(&buffer)
+        .arg(1.0f32)
+        .build().unwrap();
+
+    let mut map = unsafe { buffer.persistent_map().unwrap() };
+
+    for frame in 0..3 {
+        for ele in map.iter_mut() { *ele = frame as f32; }
+        unsafe { kernel.enq().unwrap(); }
+        pro_que.queue().finish().unwrap();
+        assert!(map.iter().all(|&ele| ele == frame as f32 + 1.0));
+    }
+
+    map.unmap().enq().unwrap();
+}
+
+#[test]
+fn map_unmap_blocking() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void add(__global float* buffer, float addend) {
+            buffer[get_global_id(0)] += addend;
+        }")
+        .dims(256)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let kernel = pro_que.kernel_builder("add")
+        .arg(&buffer)
+        .arg(1.0f32)
+        .build().unwrap();
+
+    // Non-blocking map followed by a blocking unmap:
+    let mut map_event = Event::empty();
+    let mut map = unsafe { buffer.map().block(false).enew(&mut map_event).enq().unwrap() };
+    map_event.wait_for().unwrap();
+    for ele in map.iter_mut() { *ele = 1.0; }
+
+    let mut unmap_event = Event::empty();
+    map.unmap().block(true).enew(&mut unmap_event).enq().unwrap();
+    assert!(unmap_event.is_complete().unwrap());
+
+    unsafe { kernel.enq().unwrap(); }
+
+    // Blocking map with a blocking unmap on drop:
+    {
+        let mut map = unsafe { buffer.map().enq().unwrap() };
+        assert!(map.iter().all(|&ele| ele == 2.0));
+        for ele in map.iter_mut() { *ele = 5.0; }
+        map.set_block_on_drop(true);
+    }
+
+    // Non-blocking unmap on drop (the default):
+    {
+        let map = unsafe { buffer.map().enq().unwrap() };
+        assert!(map.iter().all(|&ele| ele == 5.0));
+    }
+
+    unsafe { kernel.enq().unwrap(); }
+    let mut vec = vec![0.0f32; 256];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&ele| ele == 6.0));
+}
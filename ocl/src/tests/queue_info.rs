@@ -0,0 +1,76 @@
+//! Tests `Queue` properties, markers, and SVM commands.
+
+use crate::standard::{Platform, Device, Context, Queue, ProQue};
+use crate::flags::CommandQueueProperties;
+
+#[test]
+fn queue_properties() {
+    let platform = Platform::default();
+    let device = Device::first(platform).unwrap();
+    let context = Context::builder().platform(platform).devices(device).build().unwrap();
+
+    let plain = Queue::new(&context, device, None).unwrap();
+    assert!(!plain.properties().unwrap().contains(CommandQueueProperties::PROFILING_ENABLE));
+
+    let profiling = Queue::new(&context, device,
+        Some(CommandQueueProperties::new().profiling())).unwrap();
+    assert!(profiling.properties().unwrap().contains(CommandQueueProperties::PROFILING_ENABLE));
+}
+
+#[test]
+fn queue_marker() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void add(__global int* buffer) { buffer[get_global_id(0)] += 1; }")
+        .dims(256)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<i32>().unwrap();
+    let kernel = pro_que.kernel_builder("add").arg(&buffer).build().unwrap();
+
+    for _ in 0..4 { unsafe { kernel.enq().unwrap(); } }
+
+    let marker = pro_que.marker().unwrap();
+    marker.wait_for().unwrap();
+    assert!(marker.is_complete().unwrap());
+    assert_eq!(buffer.read_all().unwrap(), vec![4; 256]);
+}
+
+#[cfg(feature = "opencl_version_2_0")]
+#[test]
+fn queue_svm_memcpy_memfill() {
+    use crate::ffi::{self, c_void};
+    use crate::standard::Event;
+
+    let pro_que = ProQue::builder().src("__kernel void nop() {}").dims(1).build().unwrap();
+    let queue = pro_que.queue();
+    let len = 256;
+
+    let src = vec![3i32; len];
+    let mut dst = vec![0i32; len];
+
+    if !pro_que.device().supports_svm().unwrap() {
+        assert!(unsafe { queue.svm_memcpy(dst.as_mut_ptr(), src.as_ptr(), len, None::<&Event>) }
+            .is_err());
+        return;
+    }
+
+    unsafe {
+        let svm_ptr = ffi::clSVMAlloc(pro_que.context().as_ptr(), ffi::CL_MEM_READ_WRITE,
+            len * std::mem::size_of::<i32>(), 0) as *mut i32;
+        assert!(!svm_ptr.is_null());
+
+        let fill_event = queue.svm_memfill(svm_ptr, 7, len, None::<&Event>).unwrap();
+        let copy_event = queue.svm_memcpy(dst.as_mut_ptr(), svm_ptr, len, Some(&fill_event))
+            .unwrap();
+        copy_event.wait_for().unwrap();
+        assert!(dst.iter().all(|&v| v == 7));
+
+        let upload_event = queue.svm_memcpy(svm_ptr, src.as_ptr(), len, None::<&Event>)
+            .unwrap();
+        queue.svm_memcpy(dst.as_mut_ptr(), svm_ptr, len, Some(&upload_event)).unwrap()
+            .wait_for().unwrap();
+        assert_eq!(dst, src);
+
+        ffi::clSVMFree(pro_que.context().as_ptr(), svm_ptr as *mut c_void);
+    }
+}
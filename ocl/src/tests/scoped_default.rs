@@ -0,0 +1,42 @@
+//! Tests the scoped default queue set by `ocl::with_default`.
+
+use crate::standard::{ProQue, Buffer, Kernel, Queue, with_default};
+
+#[test]
+fn scoped_default_queue() {
+    let src = r#"
+        __kernel void add(__global float* buffer, float addend) {
+            buffer[get_global_id(0)] += addend;
+        }
+    "#;
+
+    let pro_que = ProQue::builder()
+        .src(src)
+        .dims(1024)
+        .build().unwrap();
+
+    assert!(Queue::scoped_default().is_none());
+
+    with_default(pro_que.queue(), || {
+        assert!(Queue::scoped_default().is_some());
+
+        let buffer = Buffer::<f32>::builder()
+            .len(1024)
+            .fill_val(1.0f32)
+            .build().unwrap();
+
+        let kernel = Kernel::builder()
+            .program(pro_que.program())
+            .name("add")
+            .global_work_size(1024)
+            .arg(&buffer)
+            .arg(2.0f32)
+            .build().unwrap();
+
+        assert!(kernel.default_queue().is_some());
+        unsafe { kernel.enq().unwrap(); }
+        buffer.assert_eq(&vec![3.0f32; 1024]);
+    });
+
+    assert!(Queue::scoped_default().is_none());
+}
@@ -0,0 +1,29 @@
+//! Tests the parsed platform and device versions.
+
+use crate::standard::{Platform, Device};
+use crate::core::{PlatformInfo, PlatformInfoResult, DeviceInfo, DeviceInfoResult};
+use crate::OpenclVersion;
+
+#[test]
+fn platform_device_version() {
+    for platform in Platform::list() {
+        let platform_version = platform.version().unwrap();
+        assert!(platform_version >= OpenclVersion::new(1, 0));
+
+        match platform.info(PlatformInfo::Version).unwrap() {
+            PlatformInfoResult::Version(ver) => assert_eq!(ver, platform.version_string().unwrap()),
+            _ => panic!("Unexpected 'PlatformInfoResult' variant."),
+        }
+
+        for device in Device::list_all(platform).unwrap() {
+            let device_version = device.version().unwrap();
+
+            match device.info(DeviceInfo::Version).unwrap() {
+                DeviceInfoResult::Version(ver) => assert_eq!(ver, device_version),
+                _ => panic!("Unexpected 'DeviceInfoResult' variant."),
+            }
+
+            assert_eq!(device.describe().unwrap().version, device_version);
+        }
+    }
+}